@@ -92,6 +92,12 @@ enum Commands {
         utc_time: Option<NaiveDateTime>,
         #[clap(long, short, conflicts_with_all = ["generation", "utc_time"], long_help = "Restore from a local directory")]
         from_dir: Option<PathBuf>,
+        #[clap(
+            long,
+            conflicts_with = "from_dir",
+            long_help = "Print the number of segments, total bytes, and expected duration of the restore, then exit without restoring"
+        )]
+        estimate: bool,
     },
     #[clap(about = "Verify integrity of the database")]
     Verify {
@@ -127,6 +133,20 @@ enum Commands {
         #[clap(long, short)]
         generation: Option<uuid::Uuid>,
     },
+    #[clap(about = "Compare the pages changed by two generations")]
+    Diff {
+        #[clap(long, short = 'a')]
+        generation_a: uuid::Uuid,
+        #[clap(long, short = 'b')]
+        generation_b: uuid::Uuid,
+    },
+    #[clap(about = "Detect objects in the bucket not referenced by any generation")]
+    Gc {
+        #[clap(long, long_help = "Remove the detected orphaned objects instead of just listing them")]
+        clean: bool,
+        #[clap(long, short)]
+        verbose: bool,
+    },
 }
 
 async fn detect_database(options: &Cli, namespace: &str) -> Result<(String, String)> {
@@ -165,6 +185,7 @@ async fn run() -> Result<()> {
         generation: _,
         utc_time: _,
         from_dir: Some(from_dir),
+        estimate: _,
     } = options.command
     {
         let database = match &options.database {
@@ -332,11 +353,30 @@ async fn run() -> Result<()> {
         Commands::Restore {
             generation,
             utc_time,
+            estimate,
             ..
         } => {
             let (database, database_dir) = detect_database(&options, &namespace).await?;
             let mut client = Replicator::new(database.clone()).await?;
             tokio::fs::create_dir_all(&database_dir).await?;
+
+            if estimate {
+                match client.estimate_restore(generation, utc_time).await? {
+                    Some((generation, estimate)) => {
+                        println!("Restore estimate for generation {generation}:");
+                        println!("\tWAL segments:     {}", estimate.segment_count);
+                        println!("\tWAL bytes:        {}", estimate.segment_bytes);
+                        println!("\tsnapshot bytes:   {}", estimate.snapshot_bytes);
+                        println!(
+                            "\testimated time:   {:.1}s",
+                            estimate.estimated_duration.as_secs_f64()
+                        );
+                    }
+                    None => println!("no generation to restore from; nothing to do"),
+                }
+                return Ok(());
+            }
+
             client.restore(generation, utc_time).await?;
             let db_path = PathBuf::from(&database);
             if let Err(e) = verify_db(&db_path) {
@@ -427,6 +467,19 @@ async fn run() -> Result<()> {
                 tokio::fs::remove_dir_all(&database_dir).await?;
             }
         }
+        Commands::Diff {
+            generation_a,
+            generation_b,
+        } => {
+            let (database, _) = detect_database(&options, &namespace).await?;
+            let client = Replicator::new(database.clone()).await?;
+            client.diff_generations(generation_a, generation_b).await?;
+        }
+        Commands::Gc { clean, verbose } => {
+            let (database, _) = detect_database(&options, &namespace).await?;
+            let client = Replicator::new(database.clone()).await?;
+            client.gc(clean, verbose).await?;
+        }
     };
     Ok(())
 }
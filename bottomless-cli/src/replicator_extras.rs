@@ -234,6 +234,94 @@ impl Replicator {
         }
     }
 
+    pub(crate) async fn diff_generations(
+        &self,
+        generation_a: uuid::Uuid,
+        generation_b: uuid::Uuid,
+    ) -> Result<()> {
+        let diff = self
+            .inner
+            .diff_generations(&generation_a, &generation_b)
+            .await?;
+
+        let table_names = self.rootpage_to_table_name().unwrap_or_default();
+        let describe = |pages: &std::collections::BTreeSet<u32>| -> String {
+            pages
+                .iter()
+                .map(|page| match table_names.get(page) {
+                    Some(name) => format!("{page} ({name})"),
+                    None => page.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        println!("Diff between {} and {}:", generation_a, generation_b);
+        println!(
+            "\tpages only in {} ({}): {}",
+            generation_a,
+            diff.pages_only_in_a.len(),
+            describe(&diff.pages_only_in_a)
+        );
+        println!(
+            "\tpages only in {} ({}): {}",
+            generation_b,
+            diff.pages_only_in_b.len(),
+            describe(&diff.pages_only_in_b)
+        );
+        println!(
+            "\tpages in both ({}): {}",
+            diff.pages_in_both.len(),
+            describe(&diff.pages_in_both)
+        );
+        if table_names.is_empty() {
+            println!("\t(no local database file found at {} to resolve page numbers to table names)", self.inner.db_path);
+        }
+        Ok(())
+    }
+
+    /// Maps sqlite root page numbers to table names, using `sqlite_master` from the local database
+    /// file this replicator is attached to. This is a heuristic: pages belonging to multi-page
+    /// tables/indexes or to overflow pages are not resolved, only the root page of each table is.
+    fn rootpage_to_table_name(&self) -> Result<std::collections::HashMap<u32, String>> {
+        let conn = rusqlite::Connection::open_with_flags(
+            &self.inner.db_path,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )?;
+        let mut stmt = conn.prepare("SELECT rootpage, name FROM sqlite_master")?;
+        let mut rows = stmt.query(())?;
+        let mut map = std::collections::HashMap::new();
+        while let Some(row) = rows.next()? {
+            let rootpage: i64 = row.get(0)?;
+            let name: String = row.get(1)?;
+            map.insert(rootpage as u32, name);
+        }
+        Ok(map)
+    }
+
+    pub(crate) async fn gc(&self, clean: bool, verbose: bool) -> Result<()> {
+        let orphaned = self.inner.list_orphaned_objects().await?;
+        if orphaned.is_empty() {
+            println!("No orphaned objects found");
+            return Ok(());
+        }
+        if verbose || !clean {
+            for key in &orphaned {
+                println!("{key}");
+            }
+        }
+        if clean {
+            let removed = self.inner.delete_orphaned_objects().await?;
+            println!("Removed {removed} orphaned objects");
+        } else {
+            println!(
+                "Found {} orphaned objects; re-run with --clean to remove them",
+                orphaned.len()
+            );
+        }
+        Ok(())
+    }
+
     pub(crate) async fn list_generation(&self, generation: uuid::Uuid) -> Result<()> {
         let res = self
             .client
@@ -0,0 +1,179 @@
+use aws_sdk_s3::Client;
+use metrics::gauge;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub type Result<T> = anyhow::Result<T>;
+
+/// Wraps a primary S3-compatible client together with an optional secondary one, transparently
+/// switching between them when the primary object-store endpoint becomes unreachable. This is
+/// meant for operators who need backups to survive an outage of a single object-store region:
+/// the primary is always preferred while it's healthy, so failover never changes where backups
+/// normally live, only where they temporarily go during an outage. Both endpoints are assumed to
+/// serve the same bucket name.
+///
+/// `Deref`s to the currently preferred [Client], so existing call sites that used a plain
+/// `Client` keep working unchanged and automatically observe failover as it happens - every
+/// method call re-reads the current state through `deref`, it isn't cached at clone time.
+#[derive(Clone, Debug)]
+pub struct FailoverClient {
+    primary: Client,
+    secondary: Option<Client>,
+    db_name: String,
+    using_secondary: Arc<AtomicBool>,
+}
+
+impl Deref for FailoverClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        if self.using_secondary.load(Ordering::Acquire) {
+            if let Some(secondary) = &self.secondary {
+                return secondary;
+            }
+        }
+        &self.primary
+    }
+}
+
+impl FailoverClient {
+    pub fn new(db_name: String, primary: Client, secondary: Option<Client>) -> Self {
+        FailoverClient {
+            primary,
+            secondary,
+            db_name,
+            using_secondary: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// `true` if a secondary endpoint is configured and requests are currently being served by it.
+    pub fn is_failed_over(&self) -> bool {
+        self.secondary.is_some() && self.using_secondary.load(Ordering::Acquire)
+    }
+
+    /// Probes the primary endpoint with a cheap `HeadBucket` call and flips between primary and
+    /// secondary accordingly. No-op if no secondary endpoint is configured.
+    pub async fn check_health(&self, bucket: &str) {
+        if self.secondary.is_none() {
+            return;
+        }
+        let primary_healthy = self
+            .primary
+            .head_bucket()
+            .bucket(bucket)
+            .send()
+            .await
+            .is_ok();
+        let was_using_secondary = self
+            .using_secondary
+            .swap(!primary_healthy, Ordering::AcqRel);
+        gauge!("bottomless_using_secondary_endpoint", if primary_healthy { 0.0 } else { 1.0 }, "db_name" => self.db_name.clone());
+        if was_using_secondary && primary_healthy {
+            tracing::info!(
+                "primary object-store endpoint for {} recovered, failing back",
+                self.db_name
+            );
+            let prefix = format!("{}-", self.db_name);
+            match self.catch_up_primary(bucket, &prefix).await {
+                Ok(copied) if copied > 0 => tracing::info!(
+                    "caught primary object-store endpoint for {} up on {} objects written during failover",
+                    self.db_name,
+                    copied
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::error!(
+                    "failed to catch primary object-store endpoint for {} up after failback: {}",
+                    self.db_name,
+                    e
+                ),
+            }
+        } else if !was_using_secondary && !primary_healthy {
+            tracing::warn!(
+                "primary object-store endpoint for {} unreachable, failing over to secondary",
+                self.db_name
+            );
+        }
+    }
+
+    /// Spawns a background task that calls [Self::check_health] on `interval` until `shutdown`
+    /// fires. Returns `None` (and spawns nothing) if no secondary endpoint is configured.
+    pub fn spawn_health_check(
+        &self,
+        bucket: String,
+        interval: Duration,
+        shutdown: Arc<tokio::sync::watch::Receiver<()>>,
+    ) -> Option<tokio::task::JoinHandle<()>> {
+        if self.secondary.is_none() {
+            return None;
+        }
+        let this = self.clone();
+        Some(tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if shutdown.has_changed().is_err() {
+                    return;
+                }
+                this.check_health(&bucket).await;
+            }
+        }))
+    }
+
+    /// Copies objects that exist in the secondary bucket but not in the primary one, so that
+    /// backups written while failed over aren't lost once the primary recovers. One-directional,
+    /// best-effort: it only ever copies secondary -> primary, and only for keys the primary is
+    /// currently missing.
+    pub async fn catch_up_primary(&self, bucket: &str, prefix: &str) -> Result<usize> {
+        let Some(secondary) = self.secondary.as_ref() else {
+            return Ok(0);
+        };
+        let mut copied = 0;
+        let mut next_marker = None;
+        loop {
+            let mut list = secondary.list_objects().bucket(bucket).prefix(prefix);
+            if let Some(marker) = next_marker {
+                list = list.marker(marker);
+            }
+            let response = list.send().await?;
+            let objs = response.contents();
+            if objs.is_empty() {
+                break;
+            }
+            for obj in objs {
+                let Some(key) = obj.key() else { continue };
+                let exists_in_primary = self
+                    .primary
+                    .head_object()
+                    .bucket(bucket)
+                    .key(key)
+                    .send()
+                    .await
+                    .is_ok();
+                if exists_in_primary {
+                    continue;
+                }
+                tracing::info!("catching up primary object-store endpoint: copying {key}");
+                let object = secondary.get_object().bucket(bucket).key(key).send().await?;
+                let body = object.body.collect().await?.into_bytes();
+                self.primary
+                    .put_object()
+                    .bucket(bucket)
+                    .key(key)
+                    .body(body.into())
+                    .send()
+                    .await?;
+                copied += 1;
+            }
+            next_marker = response
+                .is_truncated()
+                .unwrap_or(true)
+                .then(|| objs.last().map(|elem| elem.key().unwrap().to_string()))
+                .flatten();
+            if next_marker.is_none() {
+                break;
+            }
+        }
+        Ok(copied)
+    }
+}
@@ -1,9 +1,10 @@
 use crate::backup::WalCopier;
 use crate::completion_progress::{CompletionProgress, SavepointTracker};
+use crate::endpoint::FailoverClient;
 use crate::read::BatchReader;
 use crate::uuid_utils::decode_unix_timestamp;
 use crate::wal::WalFileReader;
-use anyhow::{anyhow, bail};
+use anyhow::{anyhow, bail, Context};
 use arc_swap::ArcSwapOption;
 use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
 use aws_config::BehaviorVersion;
@@ -14,6 +15,7 @@ use aws_sdk_s3::operation::get_object::GetObjectError;
 use aws_sdk_s3::operation::list_objects::builders::ListObjectsFluentBuilder;
 use aws_sdk_s3::operation::list_objects::ListObjectsOutput;
 use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::ObjectAttributes;
 use aws_sdk_s3::{Client, Config};
 use bytes::{Buf, Bytes};
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
@@ -21,11 +23,13 @@ use libsql_replication::injector::Injector as _;
 use libsql_replication::rpc::replication::Frame as RpcFrame;
 use libsql_sys::{Cipher, EncryptionConfig};
 use metrics::{counter, gauge, histogram};
+use std::collections::VecDeque;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::fs::{File, OpenOptions};
 use tokio::io::AsyncWriteExt;
 use tokio::sync::watch::{channel, Receiver, Sender};
@@ -45,7 +49,7 @@ pub type Result<T> = anyhow::Result<T>;
 
 #[derive(Debug)]
 pub struct Replicator {
-    pub client: Client,
+    pub client: FailoverClient,
 
     /// Frame number, incremented whenever a new frame is written from SQLite.
     next_frame_no: Arc<AtomicU32>,
@@ -55,6 +59,14 @@ pub struct Replicator {
     /// Last frame which has been confirmed as stored locally outside of WAL file.
     /// Always: [last_committed_frame_no] <= [last_sent_frame_no].
     last_committed_frame_no: Receiver<Result<u32>>,
+    /// Unix timestamp, in milliseconds, of the most recent commit submitted for replication.
+    /// Used together with [rpo_target] to report how far behind the continuous WAL streaming
+    /// is from the configured recovery point objective.
+    last_commit_millis: Arc<AtomicU64>,
+    /// Recovery point objective: the maximum amount of time a committed frame is allowed to sit
+    /// unreplicated before we consider the replication gap worth alerting on. `None` disables
+    /// the check - the gap is still measured and exposed as a metric either way.
+    rpo_target: Option<Duration>,
     flush_trigger: Option<Sender<()>>,
     shutdown_trigger: Option<tokio::sync::watch::Sender<()>>,
     snapshot_waiter: Receiver<Result<Option<Uuid>>>,
@@ -100,6 +112,13 @@ pub struct Options {
     pub use_compression: CompressionKind,
     pub encryption_config: Option<EncryptionConfig>,
     pub aws_endpoint: Option<String>,
+    /// Secondary S3-compatible endpoint, serving the same bucket name as `aws_endpoint`, that
+    /// replication automatically fails over to when the primary endpoint becomes unreachable.
+    /// `None` disables failover - this is the default.
+    pub aws_endpoint_secondary: Option<String>,
+    /// How often the primary endpoint is probed to decide whether to fail over to (or back from)
+    /// the secondary. Only takes effect when `aws_endpoint_secondary` is set.
+    pub health_check_interval: Duration,
     pub access_key_id: Option<String>,
     pub secret_access_key: Option<String>,
     pub session_token: Option<String>,
@@ -115,8 +134,14 @@ pub struct Options {
     pub max_frames_per_batch: usize,
     /// Max time before next frame of batched frames should be synced. This works in the case
     /// when we don't explicitly run into `max_frames_per_batch` threshold and the corresponding
-    /// checkpoint never commits.
+    /// checkpoint never commits. Set below one second for continuous, sub-second streaming of
+    /// WAL frames instead of waiting for a checkpoint boundary.
     pub max_batch_interval: Duration,
+    /// Recovery point objective: how far behind S3 is allowed to lag behind the local WAL before
+    /// the replication gap is considered out of budget. Purely observational - exceeding it does
+    /// not change batching behavior, it only drives the `bottomless_rpo_target_exceeded` metric
+    /// and a warning log, so operators can alert on it. `None` disables the check.
+    pub rpo_target: Option<Duration>,
     /// Maximum number of S3 file upload requests that may happen in parallel.
     pub s3_max_parallelism: usize,
     /// Max number of retries for S3 operations
@@ -129,8 +154,15 @@ pub struct Options {
 
 impl Options {
     pub async fn client_config(&self) -> Result<Config> {
+        self.client_config_for(self.aws_endpoint.as_deref()).await
+    }
+
+    /// Builds client config for `endpoint`, reusing every other setting (credentials, region,
+    /// retry policy) as-is. Used to build both the primary client and, when configured, the
+    /// secondary failover client, which share everything but the endpoint URL.
+    pub async fn client_config_for(&self, endpoint: Option<&str>) -> Result<Config> {
         let mut loader = aws_config::SdkConfig::builder();
-        if let Some(endpoint) = self.aws_endpoint.as_deref() {
+        if let Some(endpoint) = endpoint {
             loader = loader.endpoint_url(endpoint);
         }
         let region = self
@@ -191,10 +223,21 @@ impl Options {
 
         let db_id = env_var("LIBSQL_BOTTOMLESS_DATABASE_ID").ok();
         let aws_endpoint = env_var("LIBSQL_BOTTOMLESS_ENDPOINT").ok();
-        let bucket_name = env_var_or("LIBSQL_BOTTOMLESS_BUCKET", "bottomless");
-        let max_batch_interval = Duration::from_secs(
-            env_var_or("LIBSQL_BOTTOMLESS_BATCH_INTERVAL_SECS", 15).parse::<u64>()?,
+        let aws_endpoint_secondary = env_var("LIBSQL_BOTTOMLESS_ENDPOINT_SECONDARY").ok();
+        let health_check_interval = Duration::from_secs(
+            env_var_or("LIBSQL_BOTTOMLESS_HEALTH_CHECK_INTERVAL_SECS", 10).parse::<u64>()?,
         );
+        let bucket_name = env_var_or("LIBSQL_BOTTOMLESS_BUCKET", "bottomless");
+        let max_batch_interval = match env_var("LIBSQL_BOTTOMLESS_BATCH_INTERVAL_MS") {
+            Ok(millis) => Duration::from_millis(millis.parse::<u64>()?),
+            Err(_) => Duration::from_secs(
+                env_var_or("LIBSQL_BOTTOMLESS_BATCH_INTERVAL_SECS", 15).parse::<u64>()?,
+            ),
+        };
+        let rpo_target = match env_var("LIBSQL_BOTTOMLESS_RPO_TARGET_MS") {
+            Ok(millis) => Some(Duration::from_millis(millis.parse::<u64>()?)),
+            Err(_) => None,
+        };
         let access_key_id = env_var("LIBSQL_BOTTOMLESS_AWS_ACCESS_KEY_ID").ok();
         let secret_access_key = env_var("LIBSQL_BOTTOMLESS_AWS_SECRET_ACCESS_KEY").ok();
         let session_token = env_var("LIBSQL_BOTTOMLESS_AWS_SESSION_TOKEN").ok();
@@ -252,9 +295,12 @@ impl Options {
             use_compression,
             encryption_config,
             max_batch_interval,
+            rpo_target,
             max_frames_per_batch,
             s3_max_parallelism,
             aws_endpoint,
+            aws_endpoint_secondary,
+            health_check_interval,
             access_key_id,
             secret_access_key,
             session_token,
@@ -267,6 +313,43 @@ impl Options {
     }
 }
 
+/// Metadata of a single batch of WAL frames stored as one S3 object, as parsed from its key,
+/// without having downloaded its body yet.
+struct WalSegment {
+    key: String,
+    first_frame_no: u32,
+    last_frame_no: u32,
+    compression_kind: CompressionKind,
+    size: u64,
+}
+
+/// Estimate of the cost and duration of restoring a generation, produced without actually
+/// restoring it. See [Replicator::estimate_restore].
+#[derive(Debug, Clone, Copy)]
+pub struct RestoreEstimate {
+    /// Number of WAL segment objects that would need to be downloaded.
+    pub segment_count: u64,
+    /// Total size, in bytes, of the WAL segments that would need to be downloaded.
+    pub segment_bytes: u64,
+    /// Size, in bytes, of the main database snapshot that would need to be downloaded, if any.
+    pub snapshot_bytes: u64,
+    /// Projected wall-clock duration of the restore, extrapolated from a measured download
+    /// throughput sample.
+    pub estimated_duration: Duration,
+}
+
+/// Result of comparing the WAL pages touched by two generations. See
+/// [Replicator::diff_generations].
+#[derive(Debug, Clone)]
+pub struct GenerationDiff {
+    /// Pages written by `generation_a` but not by `generation_b`.
+    pub pages_only_in_a: std::collections::BTreeSet<u32>,
+    /// Pages written by `generation_b` but not by `generation_a`.
+    pub pages_only_in_b: std::collections::BTreeSet<u32>,
+    /// Pages written by both generations.
+    pub pages_in_both: std::collections::BTreeSet<u32>,
+}
+
 impl Replicator {
     pub const UNSET_PAGE_SIZE: usize = usize::MAX;
 
@@ -320,6 +403,23 @@ impl Replicator {
         gauge!("bottomless_s3_queue_size", size as f64, "db_name" => db_name);
     }
 
+    fn set_replication_gap(db_name: &str, gap: Duration) {
+        let db_name = db_name.to_string();
+        gauge!("bottomless_replication_gap_seconds", gap.as_secs_f64(), "db_name" => db_name);
+    }
+
+    fn record_rpo_target_exceeded(db_name: &str) {
+        let db_name = db_name.to_string();
+        counter!("bottomless_rpo_target_exceeded", 1, "db_name" => db_name);
+    }
+
+    fn now_millis() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64
+    }
+
     pub async fn with_options<S: Into<String>>(db_path: S, options: Options) -> Result<Self> {
         let config = options.client_config().await?;
         let client = Client::from_conf(config);
@@ -351,6 +451,36 @@ impl Replicator {
         };
         tracing::debug!("Database path: '{}', name: '{}'", db_path, db_name);
 
+        let mut join_set = JoinSet::new();
+        let (shutdown_trigger, shutdown_watch) = tokio::sync::watch::channel(());
+
+        let secondary_client = match options.aws_endpoint_secondary.as_deref() {
+            Some(endpoint) => {
+                let secondary_config = options.client_config_for(Some(endpoint)).await?;
+                let secondary_client = Client::from_conf(secondary_config);
+                match secondary_client.head_bucket().bucket(&bucket).send().await {
+                    Ok(_) => tracing::info!("Secondary endpoint {} is accessible", endpoint),
+                    Err(e) => tracing::warn!(
+                        "Secondary endpoint {} is not accessible yet: {}",
+                        endpoint,
+                        e
+                    ),
+                }
+                Some(secondary_client)
+            }
+            None => None,
+        };
+        let client = FailoverClient::new(db_name.clone(), client, secondary_client);
+        if let Some(health_check) = client.spawn_health_check(
+            bucket.clone(),
+            options.health_check_interval,
+            Arc::new(shutdown_watch.clone()),
+        ) {
+            join_set.spawn(async move {
+                let _ = health_check.await;
+            });
+        }
+
         let skip_shutdown_upload = options.skip_shutdown_upload;
 
         if skip_shutdown_upload {
@@ -362,10 +492,9 @@ impl Replicator {
 
         let next_frame_no = Arc::new(AtomicU32::new(1));
         let last_sent_frame_no = Arc::new(AtomicU32::new(0));
+        let last_commit_millis = Arc::new(AtomicU64::new(0));
+        let rpo_target = options.rpo_target;
 
-        let mut join_set = JoinSet::new();
-
-        let (shutdown_trigger, shutdown_watch) = tokio::sync::watch::channel(());
         let (frames_outbox, mut frames_inbox) = tokio::sync::mpsc::unbounded_channel();
         let _local_backup = {
             let mut copier = WalCopier::new(
@@ -379,6 +508,7 @@ impl Replicator {
             );
             let next_frame_no = next_frame_no.clone();
             let last_sent_frame_no = last_sent_frame_no.clone();
+            let last_commit_millis = last_commit_millis.clone();
             let batch_interval = options.max_batch_interval;
             let db_name = db_name.clone();
             join_set.spawn(async move {
@@ -408,6 +538,23 @@ impl Replicator {
                                 Self::set_local_last_frame_no(&db_name, last_frame_no);
                                 Self::increment_local_ready_frame_ranges(&db_name, ready_ranges);
                             }
+
+                            let commit_millis = last_commit_millis.load(Ordering::Acquire);
+                            if commit_millis > 0 {
+                                let gap = Duration::from_millis(
+                                    Self::now_millis().saturating_sub(commit_millis),
+                                );
+                                Self::set_replication_gap(&db_name, gap);
+                                if matches!(rpo_target, Some(target) if gap > target) {
+                                    tracing::warn!(
+                                        "replication gap {:?} exceeds RPO target {:?}",
+                                        gap,
+                                        rpo_target.unwrap()
+                                    );
+                                    Self::record_rpo_target_exceeded(&db_name);
+                                }
+                            }
+
                             if last_committed_frame_no_sender
                                 .send(res.map(|r| r.0))
                                 .is_err()
@@ -496,6 +643,8 @@ impl Replicator {
             generation,
             next_frame_no,
             last_sent_frame_no,
+            last_commit_millis,
+            rpo_target,
             flush_trigger: Some(flush_trigger),
             shutdown_trigger: Some(shutdown_trigger),
             last_committed_frame_no,
@@ -876,6 +1025,10 @@ impl Replicator {
 
     /// Submit next `frame_count` of frames to be replicated.
     pub fn submit_frames(&mut self, frame_count: u32) {
+        if frame_count > 0 {
+            self.last_commit_millis
+                .store(Self::now_millis(), Ordering::Release);
+        }
         let prev = self.next_frame_no.fetch_add(frame_count, Ordering::SeqCst);
         let last_sent = self.last_sent_frame_no();
         let most_recent = prev + frame_count - 1;
@@ -1563,40 +1716,26 @@ impl Replicator {
         Ok(false)
     }
 
-    async fn restore_wal(
+    /// Lists the WAL segments of `generation` that are eligible for restoration, in application
+    /// order, applying the same continuity/consistency/timestamp cutoffs that [Self::restore_wal]
+    /// uses while actually injecting frames. Does not download any segment body, so it is cheap
+    /// enough to use for a restore preflight estimate as well as to plan ahead for prefetching.
+    async fn list_wal_segments(
         &self,
         generation: &Uuid,
-        page_size: usize,
         last_consistent_frame: Option<u32>,
-        mut checksum: (u32, u32),
         utc_time: Option<NaiveDateTime>,
-        db_path: &Path,
-    ) -> Result<bool> {
-        let encryption_config = self.encryption_config.clone();
-        let mut injector = libsql_replication::injector::SqliteInjector::new(
-            db_path.to_path_buf(),
-            4096,
-            libsql_sys::connection::NO_AUTOCHECKPOINT,
-            encryption_config,
-        )
-        .await?;
+    ) -> Result<Vec<WalSegment>> {
         let prefix = format!("{}-{}/", self.db_name, generation);
-        let mut page_buf = {
-            let mut v = Vec::with_capacity(page_size);
-            v.spare_capacity_mut();
-            unsafe { v.set_len(page_size) };
-            v
-        };
+        let mut segments = Vec::new();
         let mut next_marker = None;
-        let mut applied_wal_frame = false;
-        let mut last_injected_frame_no = 0;
-        'restore_wal: loop {
+        let mut last_seen_frame_no = 0;
+        'list: loop {
             let mut list_request = self.list_objects().prefix(&prefix);
             if let Some(marker) = next_marker {
                 list_request = list_request.marker(marker);
             }
             let response = list_request.send().await?;
-
             let objs = response.contents();
 
             if objs.is_empty() {
@@ -1608,7 +1747,6 @@ impl Replicator {
                 let key = obj
                     .key()
                     .ok_or_else(|| anyhow::anyhow!("Failed to get key for an object"))?;
-                tracing::debug!("Loading {}", key);
 
                 let (first_frame_no, last_frame_no, timestamp, compression_kind) =
                     match Self::parse_frame_range(key) {
@@ -1626,16 +1764,16 @@ impl Replicator {
                             continue;
                         }
                     };
-                if first_frame_no != last_injected_frame_no + 1 {
+                if first_frame_no != last_seen_frame_no + 1 {
                     tracing::warn!("Missing series of consecutive frames. Last applied frame: {}, next found: {}. Stopping the restoration process",
-                            last_injected_frame_no, first_frame_no);
-                    break;
+                            last_seen_frame_no, first_frame_no);
+                    break 'list;
                 }
                 if let Some(frame) = last_consistent_frame {
                     if last_frame_no > frame {
                         tracing::warn!("Remote log contains frame {} larger than last consistent frame ({}), stopping the restoration process",
                                 last_frame_no, frame);
-                        break;
+                        break 'list;
                     }
                 }
                 if let Some(threshold) = utc_time.as_ref() {
@@ -1643,48 +1781,23 @@ impl Replicator {
                         Some(timestamp) => {
                             if &timestamp > threshold {
                                 tracing::info!("Frame batch {} has timestamp more recent than expected {}. Stopping recovery.", key, timestamp);
-                                break 'restore_wal; // reached end of restoration timestamp
+                                break 'list; // reached end of restoration timestamp
                             }
                         }
                         _ => {
                             tracing::trace!("Couldn't parse requested frame batch {} timestamp. Stopping recovery.", key);
-                            break 'restore_wal;
+                            break 'list;
                         }
                     }
                 }
-                let frame = self.get_object(key.into()).send().await?;
-                let mut reader = BatchReader::new(
+                last_seen_frame_no = last_frame_no;
+                segments.push(WalSegment {
+                    key: key.to_string(),
                     first_frame_no,
-                    frame.body.into_async_read(),
-                    self.page_size,
+                    last_frame_no,
                     compression_kind,
-                );
-
-                while let Some(frame) = reader.next_frame_header().await? {
-                    last_injected_frame_no = reader.next_frame_no();
-                    reader.next_page(&mut page_buf).await?;
-                    if self.verify_crc {
-                        checksum = frame.verify(checksum, &page_buf)?;
-                    }
-                    let (crc1, crc2) = frame.crc();
-                    let checksum = (crc1 as u64) << 32 | crc2 as u64;
-                    let frame_to_inject = libsql_replication::frame::Frame::from_parts(
-                        &libsql_replication::frame::FrameHeader {
-                            frame_no: (last_injected_frame_no as u64).into(),
-                            checksum: checksum.into(),
-                            page_no: frame.pgno().into(),
-                            size_after: frame.size_after().into(),
-                        },
-                        page_buf.as_slice(),
-                    );
-                    let frame = RpcFrame {
-                        data: frame_to_inject.bytes(),
-                        timestamp: None,
-                        durable_frame_no: None,
-                    };
-                    injector.inject_frame(frame).await?;
-                    applied_wal_frame = true;
-                }
+                    size: obj.size().unwrap_or(0) as u64,
+                });
             }
             next_marker = response
                 .is_truncated()
@@ -1696,10 +1809,127 @@ impl Replicator {
                 .then(|| objs.last().map(|elem| elem.key().unwrap().to_string()))
                 .flatten();
             if next_marker.is_none() {
-                tracing::trace!("Restored DB from S3 backup using generation {}", generation);
                 break;
             }
         }
+        Ok(segments)
+    }
+
+    /// Downloads a single WAL segment body fully into memory, so that it can be fetched ahead of
+    /// when it is actually needed for injection.
+    async fn download_segment(
+        client: FailoverClient,
+        bucket: String,
+        key: String,
+    ) -> Result<Bytes> {
+        let object = client.get_object().bucket(bucket).key(key).send().await?;
+        Ok(object.body.collect().await?.into_bytes())
+    }
+
+    async fn restore_wal(
+        &self,
+        generation: &Uuid,
+        page_size: usize,
+        last_consistent_frame: Option<u32>,
+        mut checksum: (u32, u32),
+        utc_time: Option<NaiveDateTime>,
+        db_path: &Path,
+    ) -> Result<bool> {
+        let encryption_config = self.encryption_config.clone();
+        let mut injector = libsql_replication::injector::SqliteInjector::new(
+            db_path.to_path_buf(),
+            4096,
+            libsql_sys::connection::NO_AUTOCHECKPOINT,
+            encryption_config,
+        )
+        .await?;
+        let mut page_buf = {
+            let mut v = Vec::with_capacity(page_size);
+            v.spare_capacity_mut();
+            unsafe { v.set_len(page_size) };
+            v
+        };
+
+        let segments = self
+            .list_wal_segments(generation, last_consistent_frame, utc_time)
+            .await?;
+
+        // Download segments ahead of when they are needed for injection, bounded by
+        // `s3_max_parallelism`: injection into sqlite must stay strictly sequential, but the S3
+        // downloads that feed it don't have to, so this turns the restore's network time from
+        // sum-of-segment-latencies into roughly max-of-segment-latencies.
+        let window = self.s3_max_parallelism.max(1);
+        let mut inflight: VecDeque<tokio::task::JoinHandle<Result<Bytes>>> =
+            VecDeque::with_capacity(window);
+        let mut next_to_spawn = 0;
+        let spawn_download = |idx: usize| {
+            tokio::spawn(Self::download_segment(
+                self.client.clone(),
+                self.bucket.clone(),
+                segments[idx].key.clone(),
+            ))
+        };
+        while next_to_spawn < segments.len() && inflight.len() < window {
+            inflight.push_back(spawn_download(next_to_spawn));
+            next_to_spawn += 1;
+        }
+
+        let mut applied_wal_frame = false;
+        let mut last_injected_frame_no = 0;
+        for segment in &segments {
+            tracing::debug!(
+                "Loading {} (frames {}..={})",
+                segment.key,
+                segment.first_frame_no,
+                segment.last_frame_no
+            );
+            let handle = inflight
+                .pop_front()
+                .expect("prefetch window should always stay ahead of consumption");
+            if next_to_spawn < segments.len() {
+                inflight.push_back(spawn_download(next_to_spawn));
+                next_to_spawn += 1;
+            }
+            let bytes = handle
+                .await
+                .context("WAL segment download task panicked")??;
+
+            let mut reader = BatchReader::new(
+                segment.first_frame_no,
+                std::io::Cursor::new(bytes),
+                self.page_size,
+                segment.compression_kind,
+            );
+
+            while let Some(frame) = reader.next_frame_header().await? {
+                last_injected_frame_no = reader.next_frame_no();
+                reader.next_page(&mut page_buf).await?;
+                if self.verify_crc {
+                    checksum = frame.verify(checksum, &page_buf)?;
+                }
+                let (crc1, crc2) = frame.crc();
+                let checksum = (crc1 as u64) << 32 | crc2 as u64;
+                let frame_to_inject = libsql_replication::frame::Frame::from_parts(
+                    &libsql_replication::frame::FrameHeader {
+                        frame_no: (last_injected_frame_no as u64).into(),
+                        checksum: checksum.into(),
+                        page_no: frame.pgno().into(),
+                        size_after: frame.size_after().into(),
+                    },
+                    page_buf.as_slice(),
+                );
+                let frame = RpcFrame {
+                    data: frame_to_inject.bytes(),
+                    timestamp: None,
+                    durable_frame_no: None,
+                };
+                injector.inject_frame(frame).await?;
+                applied_wal_frame = true;
+            }
+        }
+        if applied_wal_frame {
+            tracing::trace!("Restored DB from S3 backup using generation {}", generation);
+        }
         Ok(applied_wal_frame)
     }
 
@@ -1824,6 +2054,183 @@ impl Replicator {
         Ok((action, recovered))
     }
 
+    /// Estimates the cost and duration of restoring a generation, without actually restoring it:
+    /// counts the WAL segments and total bytes that [Self::restore] would need to download, and
+    /// projects a duration from a throughput sample taken by downloading the first WAL segment.
+    /// Returns `None` if there is no generation to restore.
+    pub async fn estimate_restore(
+        &mut self,
+        generation: Option<Uuid>,
+        timestamp: Option<NaiveDateTime>,
+    ) -> Result<Option<(Uuid, RestoreEstimate)>> {
+        let generation = match self.choose_generation(generation, timestamp).await {
+            Some(generation) => generation,
+            None => return Ok(None),
+        };
+
+        let last_consistent_frame = self.get_last_consistent_frame(&generation).await?;
+        let last_consistent_frame = (last_consistent_frame > 0).then_some(last_consistent_frame);
+        let segments = self
+            .list_wal_segments(&generation, last_consistent_frame, timestamp)
+            .await?;
+        let segment_count = segments.len() as u64;
+        let segment_bytes: u64 = segments.iter().map(|s| s.size).sum();
+        let snapshot_bytes = self.snapshot_size(&generation).await?;
+        let total_bytes = segment_bytes + snapshot_bytes;
+
+        let estimated_duration = if let Some(first) = segments.first() {
+            let start = Instant::now();
+            let sample_bytes = Self::download_segment(
+                self.client.clone(),
+                self.bucket.clone(),
+                first.key.clone(),
+            )
+            .await?
+            .len() as u64;
+            let sample_elapsed = start.elapsed();
+            if sample_bytes == 0 || sample_elapsed.is_zero() {
+                Duration::ZERO
+            } else {
+                let throughput = sample_bytes as f64 / sample_elapsed.as_secs_f64();
+                Duration::from_secs_f64(total_bytes as f64 / throughput)
+            }
+        } else {
+            Duration::ZERO
+        };
+
+        Ok(Some((
+            generation,
+            RestoreEstimate {
+                segment_count,
+                segment_bytes,
+                snapshot_bytes,
+                estimated_duration,
+            },
+        )))
+    }
+
+    /// Size, in bytes, of the main database snapshot of `generation`, trying every compression
+    /// kind the snapshot could have been stored with. Returns 0 if no snapshot is found.
+    async fn snapshot_size(&self, generation: &Uuid) -> Result<u64> {
+        for suffix in ["db.raw", "db.gz", "db.zstd"] {
+            let key = format!("{}-{}/{}", self.db_name, generation, suffix);
+            match self
+                .client
+                .get_object_attributes()
+                .bucket(&self.bucket)
+                .key(key)
+                .object_attributes(ObjectAttributes::ObjectSize)
+                .send()
+                .await
+            {
+                Ok(attrs) => return Ok(attrs.object_size().unwrap_or(0) as u64),
+                Err(SdkError::ServiceError(err)) if err.err().is_no_such_key() => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(0)
+    }
+
+    /// Set of page numbers written by any WAL frame belonging to `generation`, without applying
+    /// `last_consistent_frame`/`utc_time` cutoffs - this is meant for auditing a generation as a
+    /// whole, not for restoring it.
+    async fn pages_written_by(&self, generation: &Uuid) -> Result<std::collections::BTreeSet<u32>> {
+        let segments = self.list_wal_segments(generation, None, None).await?;
+        let mut pages = std::collections::BTreeSet::new();
+        let mut page_buf = vec![0u8; self.page_size];
+        for segment in &segments {
+            let bytes =
+                Self::download_segment(self.client.clone(), self.bucket.clone(), segment.key.clone())
+                    .await?;
+            let mut reader = BatchReader::new(
+                segment.first_frame_no,
+                std::io::Cursor::new(bytes),
+                self.page_size,
+                segment.compression_kind,
+            );
+            while let Some(frame) = reader.next_frame_header().await? {
+                reader.next_page(&mut page_buf).await?;
+                pages.insert(frame.pgno());
+            }
+        }
+        Ok(pages)
+    }
+
+    /// Compares the pages changed by two generations of the same database, using the WAL frames
+    /// they wrote as a (heuristic, page-granularity) proxy for the tables they touched - mapping
+    /// individual pages back to table names requires the schema, which callers can obtain by
+    /// resolving the returned page numbers against `sqlite_master.rootpage` on a restored copy.
+    pub async fn diff_generations(
+        &self,
+        generation_a: &Uuid,
+        generation_b: &Uuid,
+    ) -> Result<GenerationDiff> {
+        let pages_a = self.pages_written_by(generation_a).await?;
+        let pages_b = self.pages_written_by(generation_b).await?;
+        Ok(GenerationDiff {
+            pages_only_in_a: pages_a.difference(&pages_b).copied().collect(),
+            pages_only_in_b: pages_b.difference(&pages_a).copied().collect(),
+            pages_in_both: pages_a.intersection(&pages_b).copied().collect(),
+        })
+    }
+
+    /// Lists objects stored under this database's prefix that don't belong to any generation,
+    /// i.e. whose key isn't of the form `{db_name}-{generation}/...`. These are typically left
+    /// over from interrupted uploads or manual bucket tampering, and are safe to remove with
+    /// [Self::delete_orphaned_objects].
+    pub async fn list_orphaned_objects(&self) -> Result<Vec<String>> {
+        let prefix = format!("{}-", self.db_name);
+        let mut orphaned = Vec::new();
+        let mut next_marker = None;
+        loop {
+            let mut list_request = self.list_objects().prefix(&self.db_name);
+            if let Some(marker) = next_marker {
+                list_request = list_request.marker(marker);
+            }
+            let response = list_request.send().await?;
+            let objs = response.contents();
+            if objs.is_empty() {
+                break;
+            }
+            for obj in objs {
+                let key = obj
+                    .key()
+                    .ok_or_else(|| anyhow::anyhow!("Failed to get key for an object"))?;
+                let is_referenced = key.strip_prefix(&prefix).is_some_and(|rest| {
+                    let generation_part = rest.split('/').next().unwrap_or(rest);
+                    Uuid::try_parse(generation_part).is_ok()
+                });
+                if !is_referenced {
+                    orphaned.push(key.to_string());
+                }
+            }
+            next_marker = response
+                .is_truncated()
+                .unwrap_or(true)
+                .then(|| objs.last().map(|elem| elem.key().unwrap().to_string()))
+                .flatten();
+            if next_marker.is_none() {
+                break;
+            }
+        }
+        Ok(orphaned)
+    }
+
+    /// Removes the objects returned by [Self::list_orphaned_objects] from the bucket. Returns the
+    /// number of objects removed.
+    pub async fn delete_orphaned_objects(&self) -> Result<usize> {
+        let orphaned = self.list_orphaned_objects().await?;
+        for key in &orphaned {
+            self.client
+                .delete_object()
+                .bucket(&self.bucket)
+                .key(key)
+                .send()
+                .await?;
+        }
+        Ok(orphaned.len())
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn get_last_consistent_frame(&self, generation: &Uuid) -> Result<u32> {
         tracing::debug!("get last consistent frame");
@@ -2027,14 +2434,19 @@ impl Replicator {
 /// performs hard deletion of corresponding S3 objects.
 #[derive(Debug)]
 pub struct DeleteAll {
-    client: Client,
+    client: FailoverClient,
     bucket: String,
     db_name: String,
     threshold: NaiveDateTime,
 }
 
 impl DeleteAll {
-    fn new(client: Client, bucket: String, db_name: String, threshold: NaiveDateTime) -> Self {
+    fn new(
+        client: FailoverClient,
+        bucket: String,
+        db_name: String,
+        threshold: NaiveDateTime,
+    ) -> Self {
         DeleteAll {
             client,
             bucket,
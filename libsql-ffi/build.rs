@@ -277,6 +277,16 @@ pub fn build_bundled(out_dir: &str, out_path: &Path) {
         cfg.flag("-DLIBSQL_DISABLE_CHECKPOINT_DOWNGRADE=1");
     }
 
+    if cfg!(feature = "icu") {
+        // Registers the `icu` tokenizer for FTS3/FTS5 and ICU-aware collations/LIKE, on top of
+        // the `unicode61` tokenizer that FTS5 already ships with unconditionally. Needs the
+        // system ICU development libraries (e.g. `libicu-dev` on Debian).
+        cfg.flag("-DSQLITE_ENABLE_ICU");
+        println!("cargo:rustc-link-lib=dylib=icuuc");
+        println!("cargo:rustc-link-lib=dylib=icui18n");
+        println!("cargo:rustc-link-lib=dylib=icudata");
+    }
+
     if cfg!(feature = "bundled-sqlcipher") {
         cfg.flag("-DSQLITE_HAS_CODEC").flag("-DSQLITE_TEMP_STORE=2");
 
@@ -202,6 +202,12 @@ pub struct Stmt {
     #[serde(default, with = "option_u64_as_str")]
     #[prost(uint64, optional, tag = "6")]
     pub replication_index: Option<u64>,
+    /// If `true`, this statement's reads (and those of any other statement sent afterwards on the
+    /// same stream) are pinned to the WAL snapshot read by the first statement in the stream that
+    /// sets this flag, until the stream is closed.
+    #[serde(default)]
+    #[prost(bool, optional, tag = "7")]
+    pub snapshot: Option<bool>,
 }
 
 impl Stmt {
@@ -213,6 +219,7 @@ impl Stmt {
             named_args: vec![],
             want_rows: Some(want_rows),
             replication_index: None,
+            snapshot: None,
         }
     }
 
@@ -3,7 +3,7 @@ use std::sync::Arc;
 
 use ::bytes::{Buf, BufMut, Bytes};
 use prost::encoding::{
-    bytes, double, message, sint64, skip_field, string, uint32, DecodeContext, WireType,
+    bytes, double, message, sint64, skip_field, string, uint32, uint64, DecodeContext, WireType,
 };
 use prost::DecodeError;
 
@@ -35,34 +35,73 @@ impl prost::Message for StreamResult {
 
     fn merge_field<B>(
         &mut self,
-        _tag: u32,
-        _wire_type: WireType,
-        _buf: &mut B,
-        _ctx: DecodeContext,
+        tag: u32,
+        wire_type: WireType,
+        buf: &mut B,
+        ctx: DecodeContext,
     ) -> Result<(), DecodeError>
     where
         B: Buf,
         Self: Sized,
     {
-        panic!("StreamResult can only be encoded, not decoded")
+        match tag {
+            1 => {
+                let mut response = match replace(self, StreamResult::None) {
+                    StreamResult::Ok { response } => response,
+                    _ => StreamResponse::Close(Default::default()),
+                };
+                message::merge(wire_type, &mut response, buf, ctx)?;
+                *self = StreamResult::Ok { response };
+            }
+            2 => {
+                let mut error = match replace(self, StreamResult::None) {
+                    StreamResult::Error { error } => error,
+                    _ => Default::default(),
+                };
+                message::merge(wire_type, &mut error, buf, ctx)?;
+                *self = StreamResult::Error { error };
+            }
+            _ => skip_field(wire_type, tag, buf, ctx)?,
+        }
+        Ok(())
     }
 
     fn clear(&mut self) {
-        panic!("StreamResult can only be encoded, not decoded")
+        *self = StreamResult::None;
     }
 }
 
 impl prost::Message for StreamRequest {
-    fn encode_raw<B>(&self, _buf: &mut B)
+    fn encode_raw<B>(&self, buf: &mut B)
     where
         B: BufMut,
         Self: Sized,
     {
-        panic!("StreamRequest can only be decoded, not encoded")
+        match self {
+            StreamRequest::None => {}
+            StreamRequest::Close(msg) => message::encode(1, msg, buf),
+            StreamRequest::Execute(msg) => message::encode(2, msg, buf),
+            StreamRequest::Batch(msg) => message::encode(3, msg, buf),
+            StreamRequest::Sequence(msg) => message::encode(4, msg, buf),
+            StreamRequest::Describe(msg) => message::encode(5, msg, buf),
+            StreamRequest::StoreSql(msg) => message::encode(6, msg, buf),
+            StreamRequest::CloseSql(msg) => message::encode(7, msg, buf),
+            StreamRequest::GetAutocommit(msg) => message::encode(8, msg, buf),
+        }
     }
 
     fn encoded_len(&self) -> usize {
-        panic!("StreamRequest can only be decoded, not encoded")
+        match self {
+            StreamRequest::None => 0,
+            StreamRequest::Close(msg) => message::encoded_len(1, msg),
+            StreamRequest::Execute(msg) => message::encoded_len(2, msg),
+            StreamRequest::Batch(msg) => message::encoded_len(3, msg),
+            StreamRequest::Sequence(msg) => message::encoded_len(4, msg),
+            StreamRequest::Describe(msg) => message::encoded_len(5, msg),
+            StreamRequest::StoreSql(msg) => message::encoded_len(6, msg),
+            StreamRequest::CloseSql(msg) => message::encoded_len(7, msg),
+            StreamRequest::GetAutocommit(msg) => message::encoded_len(8, msg),
+        }
     }
 
     fn merge_field<B>(
@@ -139,20 +178,42 @@ impl prost::Message for StreamResponse {
 
     fn merge_field<B>(
         &mut self,
-        _tag: u32,
-        _wire_type: WireType,
-        _buf: &mut B,
-        _ctx: DecodeContext,
+        tag: u32,
+        wire_type: WireType,
+        buf: &mut B,
+        ctx: DecodeContext,
     ) -> Result<(), DecodeError>
     where
         B: Buf,
         Self: Sized,
     {
-        panic!("StreamResponse can only be encoded, not decoded")
+        macro_rules! merge {
+            ($variant:ident) => {{
+                let mut msg = match replace(self, StreamResponse::Close(Default::default())) {
+                    StreamResponse::$variant(msg) => msg,
+                    _ => Default::default(),
+                };
+                message::merge(wire_type, &mut msg, buf, ctx)?;
+                *self = StreamResponse::$variant(msg);
+            }};
+        }
+
+        match tag {
+            1 => merge!(Close),
+            2 => merge!(Execute),
+            3 => merge!(Batch),
+            4 => merge!(Sequence),
+            5 => merge!(Describe),
+            6 => merge!(StoreSql),
+            7 => merge!(CloseSql),
+            8 => merge!(GetAutocommit),
+            _ => skip_field(wire_type, tag, buf, ctx)?,
+        }
+        Ok(())
     }
 
     fn clear(&mut self) {
-        panic!("StreamResponse can only be encoded, not decoded")
+        *self = StreamResponse::Close(Default::default());
     }
 }
 
@@ -173,16 +234,21 @@ impl prost::Message for BatchResult {
 
     fn merge_field<B>(
         &mut self,
-        _tag: u32,
-        _wire_type: WireType,
-        _buf: &mut B,
-        _ctx: DecodeContext,
+        tag: u32,
+        wire_type: WireType,
+        buf: &mut B,
+        ctx: DecodeContext,
     ) -> Result<(), DecodeError>
     where
         B: Buf,
         Self: Sized,
     {
-        panic!("BatchResult can only be encoded, not decoded")
+        match tag {
+            1 => vec_as_map::merge(wire_type, &mut self.step_results, buf, ctx)?,
+            2 => vec_as_map::merge(wire_type, &mut self.step_errors, buf, ctx)?,
+            _ => skip_field(wire_type, tag, buf, ctx)?,
+        }
+        Ok(())
     }
 
     fn clear(&mut self) {
@@ -192,16 +258,32 @@ impl prost::Message for BatchResult {
 }
 
 impl prost::Message for BatchCond {
-    fn encode_raw<B>(&self, _buf: &mut B)
+    fn encode_raw<B>(&self, buf: &mut B)
     where
         B: BufMut,
         Self: Sized,
     {
-        panic!("BatchCond can only be decoded, not encoded")
+        match self {
+            BatchCond::None => {}
+            BatchCond::Ok { step } => uint32::encode(1, step, buf),
+            BatchCond::Error { step } => uint32::encode(2, step, buf),
+            BatchCond::Not { cond } => message::encode(3, &**cond, buf),
+            BatchCond::And(cond_list) => message::encode(4, cond_list, buf),
+            BatchCond::Or(cond_list) => message::encode(5, cond_list, buf),
+            BatchCond::IsAutocommit {} => empty_message::encode(6, buf),
+        }
     }
 
     fn encoded_len(&self) -> usize {
-        panic!("BatchCond can only be decoded, not encoded")
+        match self {
+            BatchCond::None => 0,
+            BatchCond::Ok { step } => uint32::encoded_len(1, step),
+            BatchCond::Error { step } => uint32::encoded_len(2, step),
+            BatchCond::Not { cond } => message::encoded_len(3, &**cond),
+            BatchCond::And(cond_list) => message::encoded_len(4, cond_list),
+            BatchCond::Or(cond_list) => message::encoded_len(5, cond_list),
+            BatchCond::IsAutocommit {} => empty_message::encoded_len(6),
+        }
     }
 
     fn merge_field<B>(
@@ -307,16 +389,61 @@ impl prost::Message for CursorEntry {
 
     fn merge_field<B>(
         &mut self,
-        _tag: u32,
-        _wire_type: WireType,
-        _buf: &mut B,
-        _ctx: DecodeContext,
+        tag: u32,
+        wire_type: WireType,
+        buf: &mut B,
+        ctx: DecodeContext,
     ) -> Result<(), DecodeError>
     where
         B: Buf,
         Self: Sized,
     {
-        panic!("CursorEntry can only be encoded, not decoded")
+        macro_rules! merge {
+            ($variant:ident) => {{
+                let mut entry = match replace(self, CursorEntry::None) {
+                    CursorEntry::$variant(entry) => entry,
+                    _ => Default::default(),
+                };
+                message::merge(wire_type, &mut entry, buf, ctx)?;
+                *self = CursorEntry::$variant(entry);
+            }};
+        }
+
+        match tag {
+            1 => merge!(StepBegin),
+            2 => merge!(StepEnd),
+            3 => merge!(StepError),
+            4 => {
+                let mut row = match replace(self, CursorEntry::None) {
+                    CursorEntry::Row { row } => row,
+                    _ => Default::default(),
+                };
+                message::merge(wire_type, &mut row, buf, ctx)?;
+                *self = CursorEntry::Row { row };
+            }
+            5 => {
+                let mut error = match replace(self, CursorEntry::None) {
+                    CursorEntry::Error { error } => error,
+                    _ => Default::default(),
+                };
+                message::merge(wire_type, &mut error, buf, ctx)?;
+                *self = CursorEntry::Error { error };
+            }
+            6 => {
+                let mut replication_index = match replace(self, CursorEntry::None) {
+                    CursorEntry::ReplicationIndex { replication_index } => {
+                        replication_index.unwrap_or(0)
+                    }
+                    _ => 0,
+                };
+                uint64::merge(wire_type, &mut replication_index, buf, ctx)?;
+                *self = CursorEntry::ReplicationIndex {
+                    replication_index: Some(replication_index),
+                };
+            }
+            _ => skip_field(wire_type, tag, buf, ctx)?,
+        }
+        Ok(())
     }
 
     fn clear(&mut self) {
@@ -402,10 +529,12 @@ impl prost::Message for Value {
 }
 
 mod vec_as_map {
-    use bytes::BufMut;
+    use bytes::{Buf, BufMut};
     use prost::encoding::{
-        encode_key, encode_varint, encoded_len_varint, key_len, message, uint32, WireType,
+        check_wire_type, decode_key, decode_varint, encode_key, encode_varint,
+        encoded_len_varint, key_len, message, skip_field, uint32, DecodeContext, WireType,
     };
+    use prost::DecodeError;
 
     pub fn encode<B, M>(tag: u32, values: &[Option<M>], buf: &mut B)
     where
@@ -433,6 +562,47 @@ mod vec_as_map {
             .sum()
     }
 
+    /// Merges a single map entry (as produced by [`encode`]) into `values`, growing it with
+    /// `None`s as needed so the entry lands at its original index.
+    pub fn merge<B, M>(
+        wire_type: WireType,
+        values: &mut Vec<Option<M>>,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf,
+        M: Default + prost::Message,
+    {
+        check_wire_type(WireType::LengthDelimited, wire_type)?;
+        let len = decode_varint(buf)?;
+        let remaining = buf.remaining();
+        if len > remaining as u64 {
+            return Err(DecodeError::new("buffer underflow"));
+        }
+        let limit = remaining - len as usize;
+
+        let mut index = 0u32;
+        let mut value = M::default();
+        while buf.remaining() > limit {
+            let (entry_tag, wire_type) = decode_key(buf)?;
+            match entry_tag {
+                1 => uint32::merge(wire_type, &mut index, buf, ctx.clone())?,
+                2 => message::merge(wire_type, &mut value, buf, ctx.clone())?,
+                _ => skip_field(wire_type, entry_tag, buf, ctx.clone())?,
+            }
+        }
+        if buf.remaining() != limit {
+            return Err(DecodeError::new("delimited length exceeded"));
+        }
+
+        if values.len() <= index as usize {
+            values.resize_with(index as usize + 1, || None);
+        }
+        values[index as usize] = Some(value);
+        Ok(())
+    }
+
     fn encode_map_entry<B, M>(tag: u32, key: u32, value: &M, buf: &mut B)
     where
         B: BufMut,
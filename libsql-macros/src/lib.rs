@@ -0,0 +1,175 @@
+//! Derive macros for libSQL. See `#[derive(IntoParams)]` and `#[derive(FromRow)]` below.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, LitStr};
+
+/// Derives `libsql::params::NamedParams` for a struct, mapping each field `foo` onto the named
+/// parameter `:foo`. Combined with libsql's blanket `IntoParams` implementation for
+/// `NamedParams`, this lets the struct be passed directly wherever a query takes parameters, e.g.
+/// `conn.execute(sql, my_struct)`.
+///
+/// Only plain structs with named fields are supported; tuple structs, unit structs, and enums are
+/// rejected at compile time.
+#[proc_macro_derive(IntoParams)]
+pub fn derive_into_params(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+
+    let fields = match input.data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    name,
+                    "IntoParams can only be derived for structs with named fields",
+                )
+                .to_compile_error()
+                .into()
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(name, "IntoParams can only be derived for structs")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let field_names = fields.iter().map(|f| f.ident.as_ref().unwrap());
+    let param_names = field_names.clone().map(|ident| format!(":{ident}"));
+
+    let expanded = quote! {
+        #[automatically_derived]
+        impl ::libsql::params::NamedParams for #name {
+            fn named_params(self) -> ::libsql::Result<::std::vec::Vec<(::std::string::String, ::libsql::Value)>> {
+                use ::libsql::params::IntoValue;
+
+                ::std::result::Result::Ok(::std::vec![
+                    #(
+                        (::std::string::String::from(#param_names), self.#field_names.into_value()?),
+                    )*
+                ])
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// Derives `libsql::from_row::FromRow` for a struct, mapping each field `foo` onto the result
+/// column named `foo`, looked up by name rather than by a hand-maintained positional index.
+///
+/// An optional `#[from_row(columns = "id, name, age")]` container attribute names the exact
+/// columns the deriving struct's fields are expected to come from, e.g. matching a specific
+/// query's `SELECT` list. Every field must appear in that list, checked at compile time, so a
+/// typo'd or renamed field is caught before it ever runs against a real query instead of
+/// surfacing as a runtime "no such column" error.
+///
+/// Only plain structs with named fields are supported; tuple structs, unit structs, and enums are
+/// rejected at compile time.
+#[proc_macro_derive(FromRow, attributes(from_row))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+
+    let fields = match input.data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    name,
+                    "FromRow can only be derived for structs with named fields",
+                )
+                .to_compile_error()
+                .into()
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(name, "FromRow can only be derived for structs")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let columns = match parse_columns_attr(&input.attrs) {
+        Ok(columns) => columns,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let field_idents: Vec<_> = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+
+    if let Some(columns) = &columns {
+        for ident in &field_idents {
+            let field_name = ident.to_string();
+            if !columns.iter().any(|column| column == &field_name) {
+                return syn::Error::new_spanned(
+                    ident,
+                    format!(
+                        "field `{field_name}` is not one of the columns listed in \
+                         #[from_row(columns = \"...\")]"
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            }
+        }
+    }
+
+    let field_names = field_idents.iter().map(|ident| ident.to_string());
+
+    let expanded = quote! {
+        #[automatically_derived]
+        impl ::libsql::from_row::FromRow for #name {
+            fn from_row(row: &::libsql::Row) -> ::libsql::Result<Self> {
+                let column_index = |name: &str| -> ::libsql::Result<i32> {
+                    for i in 0..row.column_count() {
+                        if row.column_name(i) == ::std::option::Option::Some(name) {
+                            return ::std::result::Result::Ok(i);
+                        }
+                    }
+                    ::std::result::Result::Err(::libsql::Error::InvalidColumnName(
+                        ::std::string::String::from(name),
+                    ))
+                };
+
+                ::std::result::Result::Ok(Self {
+                    #(
+                        #field_idents: row.get(column_index(#field_names)?)?,
+                    )*
+                })
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// Parses an optional `#[from_row(columns = "a, b, c")]` container attribute into its
+/// comma-separated column names.
+fn parse_columns_attr(attrs: &[Attribute]) -> syn::Result<Option<Vec<String>>> {
+    for attr in attrs {
+        if !attr.path().is_ident("from_row") {
+            continue;
+        }
+
+        let mut columns = None;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("columns") {
+                let list: LitStr = meta.value()?.parse()?;
+                columns = Some(
+                    list.value()
+                        .split(',')
+                        .map(|column| column.trim().to_string())
+                        .filter(|column| !column.is_empty())
+                        .collect(),
+                );
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `from_row` attribute, expected `columns`"))
+            }
+        })?;
+        return Ok(columns);
+    }
+
+    Ok(None)
+}
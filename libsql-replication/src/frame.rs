@@ -4,6 +4,7 @@ use std::mem::size_of;
 use std::ops::{Deref, DerefMut};
 
 use bytes::Bytes;
+use crc::Crc;
 use zerocopy::byteorder::little_endian::{U32 as lu32, U64 as lu64};
 use zerocopy::FromBytes;
 
@@ -12,6 +13,11 @@ use crate::LIBSQL_PAGE_SIZE;
 
 pub type FrameNo = u64;
 
+/// The rolling checksum used to chain frames together. This must stay in sync with the CRC used
+/// by the primary's replication log (`CRC_64_GO_ISO` in `libsql-server`), since a replica needs
+/// to reproduce the exact same checksums to verify the chain.
+const FRAME_CHECKSUM: Crc<u64> = Crc::<u64>::new(&crc::CRC_64_GO_ISO);
+
 /// The file header for the WAL log. All fields are represented in little-endian ordering.
 // repr C for stable sizing
 #[repr(C)]
@@ -163,6 +169,19 @@ impl FrameBorrowed {
     pub fn is_commit(&self) -> bool {
         self.header().size_after.get() != 0
     }
+
+    /// Compute the checksum this frame should carry, chained from `previous_checksum`: the
+    /// checksum of the frame immediately preceding it in the log.
+    pub fn compute_checksum(&self, previous_checksum: u64) -> u64 {
+        let mut digest = FRAME_CHECKSUM.digest_with_initial(previous_checksum);
+        digest.update(self.page());
+        digest.finalize()
+    }
+
+    /// Verify that this frame's checksum correctly chains from `previous_checksum`.
+    pub fn verify_checksum(&self, previous_checksum: u64) -> bool {
+        self.compute_checksum(previous_checksum) == self.header().checksum.get()
+    }
 }
 
 impl Deref for Frame {
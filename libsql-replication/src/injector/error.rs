@@ -1,3 +1,5 @@
+use crate::frame::FrameNo;
+
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
@@ -9,4 +11,10 @@ pub enum Error {
     Sqlite(#[from] rusqlite::Error),
     #[error("A fatal error occured injecting frames: {0}")]
     FatalInjectError(BoxError),
+    #[error("checksum chain broken at frame {frame_no}: expected {expected:016x}, got {got:016x}; primary and replica have diverged")]
+    ChecksumMismatch {
+        frame_no: FrameNo,
+        expected: u64,
+        got: u64,
+    },
 }
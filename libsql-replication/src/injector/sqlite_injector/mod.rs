@@ -81,6 +81,11 @@ pub(in super::super) struct SqliteInjectorInner {
     // connection must be dropped before the hook context
     connection: Arc<Mutex<libsql_sys::Connection<InjectorWal>>>,
     biggest_uncommitted_seen: FrameNo,
+    /// Frame number and checksum of the last frame that was verified to chain correctly, used to
+    /// verify the next one. `None` either at startup, or after a checksum mismatch was detected
+    /// and the chain was abandoned, so the next frame received is trusted as a new starting
+    /// point.
+    last_verified_frame: Option<(FrameNo, u64)>,
 
     // Connection config items used to recreate the injection connection
     path: PathBuf,
@@ -120,6 +125,7 @@ impl SqliteInjectorInner {
             capacity,
             connection: Arc::new(Mutex::new(connection)),
             biggest_uncommitted_seen: 0,
+            last_verified_frame: None,
 
             path,
             encryption_config,
@@ -129,6 +135,8 @@ impl SqliteInjectorInner {
 
     /// Inject a frame into the log. If this was a commit frame, returns Ok(Some(FrameNo)).
     pub fn inject_frame(&mut self, frame: Frame) -> Result<Option<FrameNo>, Error> {
+        self.verify_checksum_chain(&frame)?;
+
         let frame_close_txn = frame.header().size_after.get() != 0;
         self.buffer.lock().push_back(frame);
         if frame_close_txn || self.buffer.lock().len() >= self.capacity {
@@ -138,12 +146,48 @@ impl SqliteInjectorInner {
         Ok(None)
     }
 
+    /// Verify that `frame` chains correctly from the last frame we verified. Encrypted pages
+    /// can't be checked this way, since the checksum was computed over the plaintext page while
+    /// we only ever see the ciphertext here, so encrypted namespaces skip this check.
+    ///
+    /// We only know how to verify a frame that immediately follows the last one we verified: a
+    /// snapshot is injected as a set of frames in decreasing frame_no order, and the very first
+    /// frame seen by a freshly created injector (or the first one seen after a previous mismatch)
+    /// can't be compared to anything, since we don't know the checksum the primary's log started
+    /// with. In both cases we just trust the frame and use it as the new starting point.
+    fn verify_checksum_chain(&mut self, frame: &Frame) -> Result<(), Error> {
+        if self.encryption_config.is_some() {
+            return Ok(());
+        }
+
+        let frame_no = frame.header().frame_no.get();
+        let checksum = frame.header().checksum.get();
+
+        if let Some((previous_frame_no, previous_checksum)) = self.last_verified_frame {
+            if frame_no == previous_frame_no + 1 && !frame.verify_checksum(previous_checksum) {
+                self.last_verified_frame = None;
+                return Err(Error::ChecksumMismatch {
+                    frame_no,
+                    expected: frame.compute_checksum(previous_checksum),
+                    got: checksum,
+                });
+            }
+        }
+
+        self.last_verified_frame = Some((frame_no, checksum));
+
+        Ok(())
+    }
+
     pub fn rollback(&mut self) {
         self.clear_buffer();
         let conn = self.connection.lock();
         let mut rollback = conn.prepare_cached("ROLLBACK").unwrap();
         let _ = rollback.execute(());
         self.is_txn = false;
+        // we're about to resync from the primary's current position, so the next frame we see
+        // can't be verified against the last one we had: start a fresh chain.
+        self.last_verified_frame = None;
     }
 
     /// Flush the buffer to libsql WAL.
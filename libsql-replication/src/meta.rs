@@ -44,6 +44,11 @@ pub struct WalIndexMetaData {
 }
 
 impl WalIndexMetaData {
+    /// The id of the replicated log this replica last completed a handshake against.
+    pub fn log_id(&self) -> Uuid {
+        Uuid::from_u128(self.log_id.get())
+    }
+
     async fn read(file: impl AsyncRead) -> Result<Option<Self>, Error> {
         pin!(file);
         let mut buf = [0; size_of::<WalIndexMetaData>()];
@@ -1,3 +1,8 @@
+/// Encoded `FileDescriptorSet` for the protos compiled into this module, for servers that want
+/// to expose these services over gRPC server reflection (see `tonic_reflection`). Regenerated by
+/// the `bootstrap` test alongside the rest of the generated code in `generated/`.
+pub const FILE_DESCRIPTOR_SET: &[u8] = include_bytes!("generated/descriptor.bin");
+
 pub mod proxy {
     #![allow(clippy::all)]
     include!("generated/proxy.rs");
@@ -21,6 +21,9 @@ fn bootstrap() {
         .build_server(true)
         .build_transport(false)
         .out_dir(&out_dir)
+        // Emitted alongside the generated code so servers embedding these services (see
+        // `rpc::FILE_DESCRIPTOR_SET`) can expose them over gRPC server reflection.
+        .file_descriptor_set_path(out_dir.join("descriptor.bin"))
         .type_attribute(".proxy", "#[derive(serde::Serialize, serde::Deserialize)]")
         .compile_with_config(config, iface_files, dirs)
         .unwrap();
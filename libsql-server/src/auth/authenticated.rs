@@ -100,4 +100,13 @@ impl Authenticated {
             ))),
         }
     }
+
+    /// Returns the set of statement ids this token is restricted to, if it carries a `stmts`
+    /// claim. `None` means the token isn't statement-restricted (the normal case).
+    pub(crate) fn allowed_statements(&self) -> Option<&hashbrown::HashSet<String>> {
+        match self {
+            Authenticated::Authorized(a) => a.allowed_statements(),
+            _ => None,
+        }
+    }
 }
@@ -20,6 +20,12 @@ pub struct Authorized {
     /// DDL override allows ddl statement to be executed on shared_schema databases
     #[serde(rename = "ddl", default)]
     pub ddl_override: Option<Scopes>,
+    /// When set, this token may only execute statements pre-registered via the admin API (see
+    /// `http::admin::statements`) whose id is in this set, rather than arbitrary SQL. Meant for
+    /// untrusted tokens handed out to a browser: the client can only ever run the exact
+    /// parameterized statements an operator vetted ahead of time.
+    #[serde(rename = "stmts", default)]
+    pub statements: Option<HashSet<String>>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -50,6 +56,12 @@ impl Authorized {
         }
     }
 
+    /// Returns whether this token is restricted to a pre-registered statement allow-list, and if
+    /// so, which ids are in it.
+    pub fn allowed_statements(&self) -> Option<&HashSet<String>> {
+        self.statements.as_ref()
+    }
+
     pub fn merge_legacy(
         mut self,
         namespace: Option<NamespaceName>,
@@ -17,6 +17,9 @@ pub struct RpcClientConfig<C = HttpConnector> {
     pub remote_url: String,
     pub tls_config: Option<TlsConfig>,
     pub connector: C,
+    /// Keep replicating, but refuse reads/writes until the namespace is promoted. See
+    /// [`crate::namespace::configurator::replica::ReplicaConfigurator`].
+    pub standby: bool,
 }
 
 impl<C: Connector> RpcClientConfig<C> {
@@ -64,6 +67,12 @@ pub struct UserApiConfig<A = AddrIncoming> {
     pub self_url: Option<String>,
     pub primary_url: Option<String>,
     pub auth_strategy: Auth,
+    /// Origins allowed to make cross-origin requests to the HTTP API. `None` allows any origin.
+    pub cors_origins: Option<Vec<String>>,
+    /// How often the server sends an application-level WebSocket ping on idle Hrana connections.
+    /// Clients that haven't replied with a pong by the next tick are considered half-open and
+    /// dropped. `None` disables heartbeats and relies on OS-level TCP timeouts instead.
+    pub hrana_ws_heartbeat_interval: Option<Duration>,
 }
 
 impl<A> Default for UserApiConfig<A> {
@@ -75,6 +84,8 @@ impl<A> Default for UserApiConfig<A> {
             self_url: Default::default(),
             primary_url: Default::default(),
             auth_strategy: Auth::new(Disabled::new()),
+            cors_origins: Default::default(),
+            hrana_ws_heartbeat_interval: Some(Duration::from_secs(30)),
         }
     }
 }
@@ -96,6 +107,8 @@ pub struct DbConfig {
     pub hard_heap_limit_mb: Option<usize>,
     pub max_response_size: u64,
     pub max_total_response_size: u64,
+    /// Maximum size, in bytes, of an incoming request body to the HTTP/Hrana APIs.
+    pub max_request_size: u64,
     pub snapshot_exec: Option<String>,
     pub checkpoint_interval: Option<Duration>,
     pub snapshot_at_shutdown: bool,
@@ -103,6 +116,15 @@ pub struct DbConfig {
     pub max_concurrent_requests: u64,
     pub disable_intelligent_throttling: bool,
     pub connection_creation_timeout: Option<Duration>,
+    /// Upper bound on the number of namespace WAL checkpoints that may run at the same time
+    /// in this process. `None` means unbounded, so a namespace's periodic checkpoint never
+    /// waits on another namespace's, at the cost of all of them being able to contend for
+    /// I/O at once.
+    pub max_concurrent_checkpoints: Option<usize>,
+    /// Durability latency budget for the replication log: instead of syncing the log to disk on
+    /// every single commit, sync at most once per this interval, covering every commit that
+    /// landed during the window. `None` syncs on every commit, as before.
+    pub log_sync_interval: Option<Duration>,
 }
 
 impl Default for DbConfig {
@@ -116,6 +138,7 @@ impl Default for DbConfig {
             hard_heap_limit_mb: None,
             max_response_size: bytesize::mb(10u64),
             max_total_response_size: bytesize::mb(10u64),
+            max_request_size: bytesize::mb(10u64),
             snapshot_exec: None,
             checkpoint_interval: None,
             snapshot_at_shutdown: false,
@@ -123,6 +146,8 @@ impl Default for DbConfig {
             max_concurrent_requests: 128,
             disable_intelligent_throttling: false,
             connection_creation_timeout: None,
+            max_concurrent_checkpoints: None,
+            log_sync_interval: None,
         }
     }
 }
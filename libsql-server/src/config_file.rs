@@ -0,0 +1,115 @@
+//! Loads settings from a TOML file into the process environment, so they're picked up by the
+//! `env = "SQLD_*"` attributes on [`crate::Cli`]'s fields the same way a real environment
+//! variable would be. This keeps the file format and the CLI/env flags in lockstep by
+//! construction instead of needing a second, hand-maintained mapping: a top-level key `foo_bar`
+//! in the file becomes `SQLD_FOO_BAR`, exactly mirroring the naming convention already used for
+//! every flag.
+//!
+//! Settings are only seeded when the corresponding environment variable isn't already set, so
+//! the precedence is: explicit CLI flag > real environment variable > config file > built-in
+//! default.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+/// Reads `path` as TOML and seeds `SQLD_<KEY>` environment variables for each top-level entry
+/// that doesn't already have one set.
+///
+/// Arrays are joined with `,` to match the `value_delimiter = ','` flags use for list-valued
+/// settings (e.g. `http_cors_origins`). Tables aren't supported: this server doesn't have a
+/// CLI-configurable notion of per-namespace defaults today, so nesting would have nowhere to go.
+pub fn seed_env_from_file(path: &Path) -> Result<()> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read config file at {}", path.display()))?;
+    let table: toml::Table = toml::from_str(&contents)
+        .with_context(|| format!("failed to parse config file at {}", path.display()))?;
+
+    for (key, value) in table {
+        let env_name = format!("SQLD_{}", key.to_uppercase());
+        if std::env::var_os(&env_name).is_some() {
+            // an explicit environment variable always wins over the file.
+            continue;
+        }
+
+        let value = match value {
+            toml::Value::String(s) => s,
+            toml::Value::Integer(i) => i.to_string(),
+            toml::Value::Float(f) => f.to_string(),
+            toml::Value::Boolean(b) => b.to_string(),
+            toml::Value::Array(items) => items
+                .into_iter()
+                .map(|item| match item {
+                    toml::Value::String(s) => Ok(s),
+                    toml::Value::Integer(i) => Ok(i.to_string()),
+                    other => bail!(
+                        "config file key `{key}`: unsupported array element {other:?}, expected strings or integers"
+                    ),
+                })
+                .collect::<Result<Vec<_>>>()?
+                .join(","),
+            toml::Value::Table(_) => {
+                bail!("config file key `{key}`: nested tables aren't supported")
+            }
+            toml::Value::Datetime(dt) => dt.to_string(),
+        };
+
+        std::env::set_var(env_name, value);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeds_unset_vars_from_file() {
+        std::env::remove_var("SQLD_CONFIG_FILE_TEST_HTTP_LISTEN_ADDR");
+        std::env::remove_var("SQLD_CONFIG_FILE_TEST_MAX_LOG_SIZE");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sqld.toml");
+        std::fs::write(
+            &path,
+            r#"
+            config_file_test_http_listen_addr = "0.0.0.0:8080"
+            config_file_test_max_log_size = 200
+            "#,
+        )
+        .unwrap();
+
+        seed_env_from_file(&path).unwrap();
+
+        assert_eq!(
+            std::env::var("SQLD_CONFIG_FILE_TEST_HTTP_LISTEN_ADDR").unwrap(),
+            "0.0.0.0:8080"
+        );
+        assert_eq!(
+            std::env::var("SQLD_CONFIG_FILE_TEST_MAX_LOG_SIZE").unwrap(),
+            "200"
+        );
+
+        std::env::remove_var("SQLD_CONFIG_FILE_TEST_HTTP_LISTEN_ADDR");
+        std::env::remove_var("SQLD_CONFIG_FILE_TEST_MAX_LOG_SIZE");
+    }
+
+    #[test]
+    fn does_not_override_an_already_set_var() {
+        std::env::set_var("SQLD_CONFIG_FILE_TEST_HTTP_AUTH", "from-env");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sqld.toml");
+        std::fs::write(&path, r#"config_file_test_http_auth = "from-file""#).unwrap();
+
+        seed_env_from_file(&path).unwrap();
+
+        assert_eq!(
+            std::env::var("SQLD_CONFIG_FILE_TEST_HTTP_AUTH").unwrap(),
+            "from-env"
+        );
+
+        std::env::remove_var("SQLD_CONFIG_FILE_TEST_HTTP_AUTH");
+    }
+}
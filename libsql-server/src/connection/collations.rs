@@ -0,0 +1,58 @@
+//! A small, vetted set of named collations that namespaces can opt into via
+//! [`DatabaseConfig::collations`](super::config::DatabaseConfig::collations), so that `ORDER BY`
+//! and comparisons behave correctly for non-English locales without letting clients register
+//! arbitrary native code as a collation (which `sqlite3_create_collation` would otherwise allow).
+//!
+//! Registration happens once per connection, at the same point other namespace-level connection
+//! setup (`max_page_count`, `synchronous`, ...) is applied in [`super::connection_core`], so it
+//! runs identically whether the connection is on the primary or a replica.
+
+use std::cmp::Ordering;
+
+use libsql_sys::wal::Wal;
+use libsql_sys::Connection;
+
+use crate::error::Error;
+use crate::Result;
+
+/// Registers every collation named in `names` on `conn`, rejecting any name outside the vetted
+/// set instead of silently ignoring it, since a namespace config typo should surface at
+/// connection-open time rather than as a confusing "no such collation sequence" query error.
+pub(super) fn register_all<W: Wal>(
+    conn: &Connection<W>,
+    names: impl IntoIterator<Item = impl AsRef<str>>,
+) -> Result<()> {
+    for name in names {
+        register_one(conn, name.as_ref())?;
+    }
+    Ok(())
+}
+
+fn register_one<W: Wal>(conn: &Connection<W>, name: &str) -> Result<()> {
+    match name {
+        "unicase" => conn.create_collation(name, unicase_compare)?,
+        #[cfg(feature = "icu")]
+        "icu_root" => load_icu_collation(conn, "root", name)?,
+        #[cfg(feature = "icu")]
+        "icu_en_us" => load_icu_collation(conn, "en_US", name)?,
+        #[cfg(feature = "icu")]
+        "icu_de_de" => load_icu_collation(conn, "de_DE", name)?,
+        _ => return Err(Error::Anyhow(anyhow::anyhow!("unknown collation `{name}`"))),
+    }
+
+    Ok(())
+}
+
+/// Case- and width-insensitive comparison, ASCII-only. Good enough for the common "sort ignoring
+/// case" request without pulling in a real Unicode case-folding table.
+fn unicase_compare(a: &str, b: &str) -> Ordering {
+    a.to_lowercase().cmp(&b.to_lowercase())
+}
+
+/// Loads an ICU locale collation using the `icu_load_collation` SQL function that SQLite's ICU
+/// extension registers when libsql-ffi is built with the `icu` feature.
+#[cfg(feature = "icu")]
+fn load_icu_collation<W: Wal>(conn: &Connection<W>, locale: &str, name: &str) -> Result<()> {
+    conn.execute("SELECT icu_load_collation(?1, ?2)", (locale, name))?;
+    Ok(())
+}
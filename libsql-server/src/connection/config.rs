@@ -3,6 +3,7 @@ use bytesize::mb;
 use rusqlite::types::ToSqlOutput;
 use rusqlite::ToSql;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::fmt::Display;
 use std::str::FromStr;
 use url::Url;
@@ -11,7 +12,7 @@ use super::TXN_TIMEOUT;
 use libsql_replication::rpc::metadata;
 use tokio::time::Duration;
 
-#[derive(Debug, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct DatabaseConfig {
     pub block_reads: bool,
     pub block_writes: bool,
@@ -35,6 +36,56 @@ pub struct DatabaseConfig {
     pub shared_schema_name: Option<NamespaceName>,
     #[serde(default)]
     pub durability_mode: DurabilityMode,
+    /// Bounds how long a commit acknowledged on this namespace's replication log can go before
+    /// that log is fsynced, overriding the server-wide `--log-sync-interval-ms` for this
+    /// namespace only. `None` keeps the server-wide default (fsync every commit unless the
+    /// server sets its own interval). Meant for cache/analytics namespaces under
+    /// [`DurabilityMode::Relaxed`] where throughput matters more than the small window of commits
+    /// this can lose if the process crashes before the next fsync.
+    #[serde(default)]
+    pub relaxed_durability_sync_interval: Option<Duration>,
+    /// Maximum number of replicas allowed to stream frames from this namespace at once. `None`
+    /// means unlimited.
+    #[serde(default)]
+    pub max_replicas: Option<u32>,
+    /// Priority tier used to order frame delivery when more than one replica is waiting.
+    #[serde(default)]
+    pub replica_priority: ReplicaPriority,
+    /// Names of experimental features enabled for this namespace, e.g. `"new_checkpointer"`,
+    /// `"group_commit"`, or `"cursors"`. Lets operators roll out a risky change tenant by tenant
+    /// rather than flipping it on for every namespace at once.
+    #[serde(default)]
+    pub feature_flags: BTreeSet<String>,
+    /// Monotonic fencing token, bumped every time a standby namespace is promoted. Propagated to
+    /// replicas on every replication handshake so they can detect that they're still following a
+    /// primary that's since been demoted.
+    #[serde(default)]
+    pub epoch: u64,
+    /// Names of vetted collations (see `connection::collations`) to register on every connection
+    /// opened for this namespace, e.g. `"unicase"` or an ICU locale collation such as
+    /// `"icu_en_us"`. Only names from that vetted set are accepted, since a collation runs as
+    /// native code inside the connection.
+    #[serde(default)]
+    pub collations: BTreeSet<String>,
+    /// Ordering policy for the per-namespace write-lock queue (see
+    /// `connection::connection_manager`). Defaults to [`WriteQueueFairness::PriorityAging`],
+    /// which is the behavior this server always had.
+    #[serde(default)]
+    pub write_queue_fairness: WriteQueueFairness,
+    /// Names of trusted native extensions (matched against the server's checksum-verified
+    /// `trusted.lst`, see `DbConfig::validate_extensions`, by filename without extension) to load
+    /// on every connection opened for this namespace, e.g. `"crsqlite"`. Only names present in
+    /// that server-wide vetted set are accepted, since an extension runs as native code inside
+    /// the connection.
+    #[serde(default)]
+    pub extensions: BTreeSet<String>,
+}
+
+impl DatabaseConfig {
+    /// Returns whether the given experimental feature is enabled for this namespace.
+    pub fn has_feature(&self, flag: &str) -> bool {
+        self.feature_flags.contains(flag)
+    }
 }
 
 const fn default_max_size() -> u64 {
@@ -61,6 +112,14 @@ impl Default for DatabaseConfig {
             is_shared_schema: false,
             shared_schema_name: None,
             durability_mode: DurabilityMode::default(),
+            relaxed_durability_sync_interval: None,
+            max_replicas: None,
+            replica_priority: ReplicaPriority::default(),
+            feature_flags: BTreeSet::new(),
+            epoch: 0,
+            collations: BTreeSet::new(),
+            write_queue_fairness: WriteQueueFairness::default(),
+            extensions: BTreeSet::new(),
         }
     }
 }
@@ -88,6 +147,22 @@ impl From<&metadata::DatabaseConfig> for DatabaseConfig {
                 None => DurabilityMode::default(),
                 Some(m) => DurabilityMode::from(metadata::DurabilityMode::try_from(m)),
             },
+            relaxed_durability_sync_interval: value
+                .relaxed_durability_sync_interval_ms
+                .map(Duration::from_millis),
+            max_replicas: value.max_replicas,
+            replica_priority: match value.replica_priority {
+                None => ReplicaPriority::default(),
+                Some(p) => ReplicaPriority::from(metadata::ReplicaPriority::try_from(p)),
+            },
+            feature_flags: value.feature_flags.iter().cloned().collect(),
+            epoch: value.epoch.unwrap_or(0),
+            collations: value.collations.iter().cloned().collect(),
+            write_queue_fairness: match value.write_queue_fairness {
+                None => WriteQueueFairness::default(),
+                Some(f) => WriteQueueFairness::from(metadata::WriteQueueFairness::try_from(f)),
+            },
+            extensions: value.extensions.iter().cloned().collect(),
         }
     }
 }
@@ -108,6 +183,18 @@ impl From<&DatabaseConfig> for metadata::DatabaseConfig {
             shared_schema: Some(value.is_shared_schema),
             shared_schema_name: value.shared_schema_name.as_ref().map(|s| s.to_string()),
             durability_mode: Some(metadata::DurabilityMode::from(value.durability_mode).into()),
+            relaxed_durability_sync_interval_ms: value
+                .relaxed_durability_sync_interval
+                .map(|d| d.as_millis() as u64),
+            max_replicas: value.max_replicas,
+            replica_priority: Some(metadata::ReplicaPriority::from(value.replica_priority).into()),
+            feature_flags: value.feature_flags.iter().cloned().collect(),
+            epoch: Some(value.epoch),
+            collations: value.collations.iter().cloned().collect(),
+            write_queue_fairness: Some(
+                metadata::WriteQueueFairness::from(value.write_queue_fairness).into(),
+            ),
+            extensions: value.extensions.iter().cloned().collect(),
         }
     }
 }
@@ -184,3 +271,109 @@ impl From<Result<metadata::DurabilityMode, prost::DecodeError>> for DurabilityMo
         }
     }
 }
+
+/// Priority tier used by the replication frame stream to order delivery when more than one
+/// replica of a namespace is waiting on the same frame.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplicaPriority {
+    #[default]
+    Standard,
+    Priority,
+}
+
+impl FromStr for ReplicaPriority {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<ReplicaPriority, Self::Err> {
+        match input {
+            "standard" => Ok(ReplicaPriority::Standard),
+            "priority" => Ok(ReplicaPriority::Priority),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for ReplicaPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let m = match self {
+            ReplicaPriority::Standard => "standard",
+            ReplicaPriority::Priority => "priority",
+        };
+        write!(f, "{m}")
+    }
+}
+
+impl From<ReplicaPriority> for metadata::ReplicaPriority {
+    fn from(value: ReplicaPriority) -> Self {
+        match value {
+            ReplicaPriority::Standard => metadata::ReplicaPriority::Standard,
+            ReplicaPriority::Priority => metadata::ReplicaPriority::Priority,
+        }
+    }
+}
+
+impl From<Result<metadata::ReplicaPriority, prost::DecodeError>> for ReplicaPriority {
+    fn from(value: Result<metadata::ReplicaPriority, prost::DecodeError>) -> Self {
+        match value {
+            Ok(metadata::ReplicaPriority::Standard) => ReplicaPriority::Standard,
+            Ok(metadata::ReplicaPriority::Priority) => ReplicaPriority::Priority,
+            Err(_) => ReplicaPriority::default(),
+        }
+    }
+}
+
+/// Ordering policy for the per-namespace write-lock queue handed out by
+/// [`connection_manager::ConnectionManager`](super::connection_manager::ConnectionManager).
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteQueueFairness {
+    /// Grant the lock strictly in enqueue order; a write transaction that overstays its
+    /// `txn_timeout` is left alone until it finishes or times out on its own.
+    Fifo,
+    /// Same enqueue order, but a write transaction that's held the lock past `txn_timeout` is
+    /// force rolled back so the connection behind it in the queue isn't starved.
+    #[default]
+    PriorityAging,
+}
+
+impl FromStr for WriteQueueFairness {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<WriteQueueFairness, Self::Err> {
+        match input {
+            "fifo" => Ok(WriteQueueFairness::Fifo),
+            "priority_aging" => Ok(WriteQueueFairness::PriorityAging),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for WriteQueueFairness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let m = match self {
+            WriteQueueFairness::Fifo => "fifo",
+            WriteQueueFairness::PriorityAging => "priority_aging",
+        };
+        write!(f, "{m}")
+    }
+}
+
+impl From<WriteQueueFairness> for metadata::WriteQueueFairness {
+    fn from(value: WriteQueueFairness) -> Self {
+        match value {
+            WriteQueueFairness::Fifo => metadata::WriteQueueFairness::Fifo,
+            WriteQueueFairness::PriorityAging => metadata::WriteQueueFairness::PriorityAging,
+        }
+    }
+}
+
+impl From<Result<metadata::WriteQueueFairness, prost::DecodeError>> for WriteQueueFairness {
+    fn from(value: Result<metadata::WriteQueueFairness, prost::DecodeError>) -> Self {
+        match value {
+            Ok(metadata::WriteQueueFairness::Fifo) => WriteQueueFairness::Fifo,
+            Ok(metadata::WriteQueueFairness::PriorityAging) => WriteQueueFairness::PriorityAging,
+            Err(_) => WriteQueueFairness::default(),
+        }
+    }
+}
@@ -7,11 +7,13 @@ use libsql_sys::wal::{Wal, WalManager};
 use metrics::histogram;
 use parking_lot::Mutex;
 
+use crate::connection::collations;
 use crate::connection::legacy::open_conn_active_checkpoint;
 use crate::error::Error;
 use crate::metrics::{PROGRAM_EXEC_COUNT, QUERY_CANCELED, VACUUM_COUNT, WAL_CHECKPOINT_COUNT};
 use crate::namespace::broadcasters::BroadcasterHandle;
 use crate::namespace::meta_store::MetaStoreHandle;
+use crate::namespace::statements::StatementsHandle;
 use crate::namespace::ResolveNamespacePathFn;
 use crate::query_analysis::StmtKind;
 use crate::query_result_builder::{QueryBuilderConfig, QueryResultBuilder};
@@ -35,10 +37,37 @@ pub(super) struct CoreConnection<W> {
     resolve_attach_path: ResolveNamespacePathFn,
     forced_rollback: bool,
     broadcaster: BroadcasterHandle,
+    statements: StatementsHandle,
     hooked: bool,
     canceled: Arc<AtomicBool>,
 }
 
+/// Picks out the paths of the extensions named in `names` (a namespace's
+/// [`DatabaseConfig::extensions`]) from `trusted` (the server-wide, checksum-verified candidate
+/// set built by `DbConfig::validate_extensions`), matching by filename without extension, e.g.
+/// `"crsqlite"` matches `crsqlite.so`. Rejects any name not found in `trusted` instead of silently
+/// skipping it, since a namespace config typo should surface at connection-open time rather than
+/// as a confusing missing-function error later on.
+fn select_trusted_extensions<'a>(
+    trusted: &'a [PathBuf],
+    names: &std::collections::BTreeSet<String>,
+) -> Result<Vec<&'a Path>> {
+    names
+        .iter()
+        .map(|name| {
+            trusted
+                .iter()
+                .find(|p| p.file_stem().and_then(|s| s.to_str()) == Some(name.as_str()))
+                .map(PathBuf::as_path)
+                .ok_or_else(|| {
+                    Error::from(anyhow::anyhow!(
+                        "extension `{name}` is not in the server's trusted extension list"
+                    ))
+                })
+        })
+        .collect()
+}
+
 fn update_stats(
     stats: &Stats,
     sql: String,
@@ -63,6 +92,7 @@ impl<W: Wal + Send + 'static> CoreConnection<W> {
         wal_manager: T,
         stats: Arc<Stats>,
         broadcaster: BroadcasterHandle,
+        statements: StatementsHandle,
         config_store: MetaStoreHandle,
         builder_config: QueryBuilderConfig,
         get_current_frame_no: GetCurrentFrameNo,
@@ -87,6 +117,19 @@ impl<W: Wal + Send + 'static> CoreConnection<W> {
             config.max_row_size as i32,
         );
 
+        collations::register_all(&conn, &config.collations)?;
+
+        for ext in select_trusted_extensions(&extensions, &config.extensions)? {
+            unsafe {
+                let _guard = rusqlite::LoadExtensionGuard::new(&conn).unwrap();
+                if let Err(e) = conn.load_extension(ext, None) {
+                    tracing::error!("failed to load extension: {}", ext.display());
+                    Err(e)?;
+                }
+                tracing::trace!("Loaded extension {}", ext.display());
+            }
+        }
+
         let canceled = Arc::new(AtomicBool::new(false));
 
         conn.progress_handler(100, {
@@ -110,22 +153,12 @@ impl<W: Wal + Send + 'static> CoreConnection<W> {
             resolve_attach_path,
             forced_rollback: false,
             broadcaster,
+            statements,
             hooked: false,
             canceled,
             get_current_frame_no,
         };
 
-        for ext in extensions.iter() {
-            unsafe {
-                let _guard = rusqlite::LoadExtensionGuard::new(&this.conn).unwrap();
-                if let Err(e) = this.conn.load_extension(ext, None) {
-                    tracing::error!("failed to load extension: {}", ext.display());
-                    Err(e)?;
-                }
-                tracing::trace!("Loaded extension {}", ext.display());
-            }
-        }
-
         Ok(this)
     }
 
@@ -141,6 +174,10 @@ impl<W: Wal + Send + 'static> CoreConnection<W> {
         self.config_store.get()
     }
 
+    pub(super) fn statements(&self) -> StatementsHandle {
+        self.statements.clone()
+    }
+
     pub(super) async fn run_async<B: QueryResultBuilder>(
         this: Arc<Mutex<Self>>,
         pgm: Program,
@@ -168,15 +205,33 @@ impl<W: Wal + Send + 'static> CoreConnection<W> {
 
         PROGRAM_EXEC_COUNT.increment(1);
 
+        let namespace = this.lock().stats.namespace().clone();
+
         // create the bomb right before spawning the blocking task.
         let mut bomb = Bomb {
             canceled,
             defused: false,
         };
-        let ret = BLOCKING_RT
+        let ret = match BLOCKING_RT
             .spawn_blocking(move || CoreConnection::run(this, pgm, builder))
             .await
-            .unwrap();
+        {
+            Ok(ret) => ret,
+            Err(join_err) => {
+                // A panic here only unwound the blocking task it ran on; record it as an
+                // incident scoped to this namespace instead of propagating the panic any
+                // further, so other namespaces keep running undisturbed.
+                match join_err.try_into_panic() {
+                    Ok(payload) => {
+                        crate::incidents::record_panic(&namespace, &*payload);
+                    }
+                    Err(join_err) => {
+                        tracing::error!(%namespace, "connection task was cancelled: {join_err}");
+                    }
+                }
+                Err(Error::Internal("connection task crashed".to_string()))
+            }
+        };
 
         bomb.defused = true;
 
@@ -412,6 +467,7 @@ mod test {
             resolve_attach_path: Arc::new(|_| unreachable!()),
             forced_rollback: false,
             broadcaster: Default::default(),
+            statements: Default::default(),
             hooked: false,
             canceled: Arc::new(false.into()),
             get_current_frame_no: Arc::new(|| None),
@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use std::ops::Deref;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -12,6 +13,9 @@ use metrics::atomics::AtomicU64;
 use parking_lot::{Mutex, MutexGuard};
 use rusqlite::ErrorCode;
 
+use crate::metrics::WRITE_TXN_QUEUE_WAIT_TIME;
+
+use super::config::WriteQueueFairness;
 use super::connection_core::CoreConnection;
 use super::TXN_TIMEOUT;
 
@@ -72,14 +76,49 @@ impl Deref for ConnectionManager {
 }
 
 impl ConnectionManager {
-    pub fn new(txn_timeout_duration: Duration) -> ConnectionManager {
+    pub fn new(txn_timeout_duration: Duration, fairness: WriteQueueFairness) -> ConnectionManager {
         Self {
             inner: Arc::new(ConnectionManagerInner {
                 txn_timeout_duration,
+                fairness,
                 ..Default::default()
             }),
         }
     }
+
+    /// A point-in-time snapshot of the write-lock queue, for the admin API and diagnostics.
+    pub(crate) fn queue_snapshot(&self) -> QueueStats {
+        QueueStats {
+            fairness: self.inner.fairness,
+            queue_len: self.inner.write_queue.len(),
+            longest_wait: self
+                .inner
+                .enqueue_times
+                .lock()
+                .front()
+                .map(|enqueued_at| enqueued_at.elapsed()),
+            held_for: self
+                .inner
+                .current
+                .lock()
+                .as_ref()
+                .map(|slot| slot.started_at.elapsed()),
+        }
+    }
+}
+
+/// A point-in-time snapshot of a namespace's write-lock queue.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct QueueStats {
+    /// Ordering policy currently in effect for this namespace.
+    pub fairness: WriteQueueFairness,
+    /// Number of connections currently parked waiting for the write lock.
+    pub queue_len: usize,
+    /// How long the connection at the front of the queue has been waiting, if any.
+    pub longest_wait: Option<Duration>,
+    /// How long the connection currently holding the write lock (or checkpoint) has held it, if
+    /// any.
+    pub held_for: Option<Duration>,
 }
 
 pub struct ConnectionManagerInner {
@@ -92,11 +131,16 @@ pub struct ConnectionManagerInner {
     /// threads waiting to acquire the lock
     /// todo: limit how many can be push
     write_queue: crossbeam::deque::Injector<(ConnId, Unparker)>,
+    /// mirrors `write_queue`'s enqueue order, so we can report and time how long the head of the
+    /// queue has been waiting without a destructive peek into the injector
+    enqueue_times: Mutex<VecDeque<Instant>>,
     txn_timeout_duration: Duration,
     /// the time we are given to acquire a transaction after we were given a slot
     acquire_timeout_duration: Duration,
     next_conn_id: AtomicU64,
     sync_token: AtomicU64,
+    /// ordering policy for `write_queue`, see [`WriteQueueFairness`]
+    fairness: WriteQueueFairness,
 }
 
 impl Default for ConnectionManagerInner {
@@ -105,10 +149,12 @@ impl Default for ConnectionManagerInner {
             current: Default::default(),
             abort_handle: Default::default(),
             write_queue: Default::default(),
+            enqueue_times: Default::default(),
             txn_timeout_duration: TXN_TIMEOUT,
             acquire_timeout_duration: Duration::from_millis(15),
             next_conn_id: Default::default(),
             sync_token: AtomicU64::new(0),
+            fairness: WriteQueueFairness::default(),
         }
     }
 }
@@ -191,6 +237,7 @@ impl ManagedConnectionWalWrapper {
                 self.manager
                     .write_queue
                     .push((self.id, parker.unparker().clone()));
+                self.manager.enqueue_times.lock().push_back(enqueued_at);
                 enqueued = true;
                 tracing::debug!("enqueued");
             }
@@ -217,7 +264,9 @@ impl ManagedConnectionWalWrapper {
                         let deadline = slot.started_at + self.manager.txn_timeout_duration;
                         match slot.state {
                             SlotState::Acquired(..) => {
-                                if since_started >= self.manager.txn_timeout_duration {
+                                if self.manager.fairness == WriteQueueFairness::PriorityAging
+                                    && since_started >= self.manager.txn_timeout_duration
+                                {
                                     let id = slot.id;
                                     drop(current);
                                     let handle = {
@@ -341,6 +390,9 @@ impl ManagedConnectionWalWrapper {
 
         match next {
             Some((id, unpaker)) => {
+                if let Some(enqueued_at) = self.manager.enqueue_times.lock().pop_front() {
+                    WRITE_TXN_QUEUE_WAIT_TIME.record(enqueued_at.elapsed());
+                }
                 tracing::debug!(line = line!(), "unparking id={id}");
                 **current = Some(Slot {
                     id,
@@ -465,6 +517,7 @@ impl WrapWal<InnerWal> for ManagedConnectionWalWrapper {
             let queue_len = self.manager.write_queue.len();
             for _ in 0..queue_len {
                 let (id, unparker) = self.manager.write_queue.steal().success().unwrap();
+                self.manager.enqueue_times.lock().pop_front();
                 tracing::debug!("forcing queue sync for id={id}");
                 unparker.unpark();
             }
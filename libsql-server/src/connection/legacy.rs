@@ -15,6 +15,7 @@ use crate::error::Error;
 use crate::metrics::DESCRIBE_COUNT;
 use crate::namespace::broadcasters::BroadcasterHandle;
 use crate::namespace::meta_store::MetaStoreHandle;
+use crate::namespace::statements::StatementsHandle;
 use crate::namespace::ResolveNamespacePathFn;
 use crate::query_result_builder::{QueryBuilderConfig, QueryResultBuilder};
 use crate::replication::FrameNo;
@@ -34,6 +35,7 @@ pub struct MakeLegacyConnection<W> {
     wal_wrapper: W,
     stats: Arc<Stats>,
     broadcaster: BroadcasterHandle,
+    statements: StatementsHandle,
     config_store: MetaStoreHandle,
     extensions: Arc<[PathBuf]>,
     max_response_size: u64,
@@ -59,6 +61,7 @@ where
         wal_wrapper: W,
         stats: Arc<Stats>,
         broadcaster: BroadcasterHandle,
+        statements: StatementsHandle,
         config_store: MetaStoreHandle,
         extensions: Arc<[PathBuf]>,
         max_response_size: u64,
@@ -71,11 +74,13 @@ where
         make_wal_manager: Arc<dyn Fn() -> InnerWalManager + Sync + Send + 'static>,
     ) -> Result<Self> {
         let txn_timeout = config_store.get().txn_timeout.unwrap_or(TXN_TIMEOUT);
+        let write_queue_fairness = config_store.get().write_queue_fairness;
 
         let mut this = Self {
             db_path,
             stats,
             broadcaster,
+            statements,
             config_store,
             extensions,
             max_response_size,
@@ -87,7 +92,7 @@ where
             encryption_config,
             block_writes,
             resolve_attach_path,
-            connection_manager: ConnectionManager::new(txn_timeout),
+            connection_manager: ConnectionManager::new(txn_timeout, write_queue_fairness),
             make_wal_manager,
         };
 
@@ -134,6 +139,7 @@ where
             self.wal_wrapper.clone(),
             self.stats.clone(),
             self.broadcaster.clone(),
+            self.statements.clone(),
             self.config_store.clone(),
             QueryBuilderConfig {
                 max_size: Some(self.max_response_size),
@@ -161,6 +167,10 @@ where
     async fn create(&self) -> Result<Self::Connection, Error> {
         self.make_connection().await
     }
+
+    fn queue_stats(&self) -> Option<super::connection_manager::QueueStats> {
+        Some(self.connection_manager.queue_snapshot())
+    }
 }
 
 pub struct LegacyConnection<T> {
@@ -178,12 +188,13 @@ impl LegacyConnection<libsql_sys::wal::wrapper::PassthroughWalWrapper> {
             libsql_sys::wal::wrapper::PassthroughWalWrapper,
             Default::default(),
             Default::default(),
+            Default::default(),
             MetaStoreHandle::new_test(),
             QueryBuilderConfig::default(),
             Arc::new(|| None),
             Default::default(),
             Arc::new(|_| unreachable!()),
-            ConnectionManager::new(TXN_TIMEOUT),
+            ConnectionManager::new(TXN_TIMEOUT, Default::default()),
             Arc::new(|| Sqlite3WalManager::default()),
         )
         .await
@@ -314,6 +325,7 @@ where
         wal_wrapper: W,
         stats: Arc<Stats>,
         broadcaster: BroadcasterHandle,
+        statements: StatementsHandle,
         config_store: MetaStoreHandle,
         builder_config: QueryBuilderConfig,
         current_frame_no_receiver: GetCurrentFrameNo,
@@ -335,6 +347,7 @@ where
                     wal,
                     stats,
                     broadcaster,
+                    statements,
                     config_store,
                     builder_config,
                     current_frame_no_receiver,
@@ -376,10 +389,11 @@ where
         builder: B,
     ) -> Result<B> {
         let inner = self.inner.clone();
-        let config = tokio::task::spawn_blocking(move || inner.lock().config())
-            .await
-            .unwrap();
-        check_program_auth(&ctx, &pgm, &config).await?;
+        let (config, statements) =
+            tokio::task::spawn_blocking(move || (inner.lock().config(), inner.lock().statements()))
+                .await
+                .unwrap();
+        check_program_auth(&ctx, &pgm, &config, &statements).await?;
         let conn = self.inner.clone();
         CoreConnection::run_async(conn, pgm, builder).await
     }
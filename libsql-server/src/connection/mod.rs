@@ -25,6 +25,7 @@ use crate::Result;
 
 use self::program::{Cond, DescribeResponse, Program, Step};
 
+mod collations;
 pub mod config;
 mod connection_core;
 pub mod connection_manager;
@@ -199,6 +200,13 @@ pub trait MakeConnection: Send + Sync + 'static {
     /// Create a new connection of type Self::Connection
     async fn create(&self) -> Result<Self::Connection, Error>;
 
+    /// A snapshot of the pending write-lock queue for this connection maker, or `None` if
+    /// connections it creates don't serialize write transactions through a local queue (e.g. a
+    /// replica proxying writes to its primary).
+    fn queue_stats(&self) -> Option<connection_manager::QueueStats> {
+        None
+    }
+
     fn throttled(
         self,
         semaphore: Arc<Semaphore>,
@@ -247,6 +255,10 @@ where
         let conn = self.inner.create().await?;
         Ok((self.f)(conn))
     }
+
+    fn queue_stats(&self) -> Option<connection_manager::QueueStats> {
+        self.inner.queue_stats()
+    }
 }
 
 #[async_trait::async_trait]
@@ -256,6 +268,10 @@ impl<T: MakeConnection> MakeConnection for Arc<T> {
     async fn create(&self) -> Result<Self::Connection, Error> {
         self.as_ref().create().await
     }
+
+    fn queue_stats(&self) -> Option<connection_manager::QueueStats> {
+        self.as_ref().queue_stats()
+    }
 }
 
 #[async_trait::async_trait]
@@ -397,6 +413,10 @@ impl<F: MakeConnection> MakeConnection for MakeThrottledConnection<F> {
             created_at: Instant::now(),
         })
     }
+
+    fn queue_stats(&self) -> Option<connection_manager::QueueStats> {
+        self.connection_maker.queue_stats()
+    }
 }
 
 #[derive(Debug)]
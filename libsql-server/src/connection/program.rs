@@ -7,6 +7,7 @@ use rusqlite::StatementStatus;
 use crate::auth::Permission;
 use crate::error::Error;
 use crate::metrics::{READ_QUERY_COUNT, WRITE_QUERY_COUNT};
+use crate::namespace::statements::StatementsHandle;
 use crate::namespace::{NamespaceName, ResolveNamespacePathFn};
 use crate::query::Query;
 use crate::query_analysis::StmtKind;
@@ -352,8 +353,21 @@ pub async fn check_program_auth(
     ctx: &RequestContext,
     pgm: &Program,
     config: &DatabaseConfig,
+    statements: &StatementsHandle,
 ) -> crate::Result<()> {
     for step in pgm.steps() {
+        if let Some(allowed_ids) = ctx.auth().allowed_statements() {
+            let sql = &step.query.stmt.stmt;
+            let is_registered = allowed_ids
+                .iter()
+                .any(|id| statements.is_registered(id, sql));
+            if !is_registered {
+                return Err(Error::Forbidden(
+                    "this token may only execute pre-registered statements".to_string(),
+                ));
+            }
+        }
+
         match &step.query.stmt.kind {
             StmtKind::TxnBegin
             | StmtKind::TxnEnd
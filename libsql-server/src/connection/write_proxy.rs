@@ -35,6 +35,11 @@ pub type WaitForFrameNo = Arc<
     dyn Fn(FrameNo) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> + Send + 'static + Sync,
 >;
 
+/// Builds connections that proxy writes to the primary over `channel`. Write-proxy RPCs aren't
+/// separately tagged with the namespace's fencing epoch: they share the same gRPC channel as the
+/// replication client, so when that client detects it's talking to a demoted primary (epoch
+/// regression during a handshake, see `replicator_client::Client::handshake`) and tears itself
+/// down, proxied writes stop along with it instead of being individually rejected.
 pub struct MakeWriteProxyConn<M> {
     client: ProxyClient<Channel>,
     stats: Arc<Stats>,
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use bottomless::replicator::Replicator;
 use tokio::sync::watch;
 
+use crate::connection::connection_manager::QueueStats;
 use crate::connection::{MakeConnection, RequestContext};
 use crate::replication::{FrameNo, ReplicationLogger};
 
@@ -239,4 +240,14 @@ impl Database {
             Database::Schema(db) => db.replicator(),
         }
     }
+
+    /// A snapshot of this namespace's write-lock queue, or `None` if it doesn't serialize write
+    /// transactions locally (a replica proxies writes to its primary instead).
+    pub(crate) fn queue_stats(&self) -> Option<QueueStats> {
+        match self {
+            Database::Primary(db) => db.connection_maker.queue_stats(),
+            Database::Replica(_) => None,
+            Database::Schema(db) => db.queue_stats(),
+        }
+    }
 }
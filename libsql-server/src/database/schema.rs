@@ -8,6 +8,7 @@ use crate::connection::program::{check_program_auth, Program};
 use crate::connection::{MakeConnection, RequestContext};
 use crate::namespace::meta_store::MetaStoreHandle;
 use crate::namespace::replication_wal::ReplicationWalWrapper;
+use crate::namespace::statements::StatementsHandle;
 use crate::namespace::NamespaceName;
 use crate::query_result_builder::QueryBuilderConfig;
 use crate::schema::{perform_migration, validate_migration, MigrationJobStatus, SchedulerHandle};
@@ -17,6 +18,7 @@ pub struct SchemaConnection<C> {
     schema: NamespaceName,
     connection: Arc<C>,
     config: MetaStoreHandle,
+    statements: StatementsHandle,
 }
 
 impl<C> SchemaConnection<C> {
@@ -49,7 +51,7 @@ impl<C: crate::connection::Connection> crate::connection::Connection for SchemaC
 
             res
         } else {
-            check_program_auth(&ctx, &migration, &self.config.get()).await?;
+            check_program_auth(&ctx, &migration, &self.config.get(), &self.statements).await?;
             let connection = self.connection.clone();
             let disable_foreign_key = validate_migration(&mut migration)?;
             let migration = Arc::new(migration);
@@ -158,6 +160,7 @@ pub struct SchemaDatabase<M> {
     pub wal_wrapper: Option<ReplicationWalWrapper>,
     config: MetaStoreHandle,
     pub new_frame_notifier: Receiver<Option<u64>>,
+    statements: StatementsHandle,
 }
 
 impl<M> Clone for SchemaDatabase<M> {
@@ -169,6 +172,7 @@ impl<M> Clone for SchemaDatabase<M> {
             wal_wrapper: self.wal_wrapper.clone(),
             config: self.config.clone(),
             new_frame_notifier: self.new_frame_notifier.clone(),
+            statements: self.statements.clone(),
         }
     }
 }
@@ -184,8 +188,13 @@ impl<M: MakeConnection> MakeConnection for SchemaDatabase<M> {
             schema: self.schema.clone(),
             connection,
             config: self.config.clone(),
+            statements: self.statements.clone(),
         })
     }
+
+    fn queue_stats(&self) -> Option<crate::connection::connection_manager::QueueStats> {
+        self.connection_maker.queue_stats()
+    }
 }
 
 impl<M> SchemaDatabase<M> {
@@ -196,6 +205,7 @@ impl<M> SchemaDatabase<M> {
         wal_wrapper: Option<ReplicationWalWrapper>,
         config: MetaStoreHandle,
         new_frame_notifier: Receiver<Option<u64>>,
+        statements: StatementsHandle,
     ) -> Self {
         Self {
             connection_maker,
@@ -204,6 +214,7 @@ impl<M> SchemaDatabase<M> {
             wal_wrapper,
             config,
             new_frame_notifier,
+            statements,
         }
     }
 
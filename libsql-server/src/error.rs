@@ -68,6 +68,8 @@ pub enum Error {
     NamespaceDoesntExist(String),
     #[error("Namespace `{0}` already exists")]
     NamespaceAlreadyExist(String),
+    #[error("{0}")]
+    NamespaceRenameError(String),
     #[error("Invalid namespace")]
     InvalidNamespace,
     #[error("Invalid namespace bytes: `{0}`")]
@@ -128,6 +130,10 @@ pub enum Error {
     RuntimeTaskJoinError(#[from] tokio::task::JoinError),
     #[error("database is not a primary")]
     NotAPrimary,
+    #[error("database is too large to serialize in one response: {0} bytes, limit is {1} bytes")]
+    SerializedDbTooLarge(u64, u64),
+    #[error("cross-namespace transaction failed: {0}")]
+    XaError(String),
 }
 
 impl AsRef<Self> for Error {
@@ -191,6 +197,7 @@ impl IntoResponse for &Error {
             NamespaceDoesntExist(_) => self.format_err(StatusCode::NOT_FOUND),
             PrimaryConnectionTimeout => self.format_err(StatusCode::INTERNAL_SERVER_ERROR),
             NamespaceAlreadyExist(_) => self.format_err(StatusCode::BAD_REQUEST),
+            NamespaceRenameError(_) => self.format_err(StatusCode::BAD_REQUEST),
             InvalidNamespace => self.format_err(StatusCode::BAD_REQUEST),
             InvalidNamespaceBytes(_) => self.format_err(StatusCode::BAD_REQUEST),
             LoadDumpError(e) => e.into_response(),
@@ -224,6 +231,8 @@ impl IntoResponse for &Error {
             AttachInMigration => self.format_err(StatusCode::BAD_REQUEST),
             RuntimeTaskJoinError(_) => self.format_err(StatusCode::INTERNAL_SERVER_ERROR),
             NotAPrimary => self.format_err(StatusCode::BAD_REQUEST),
+            SerializedDbTooLarge(_, _) => self.format_err(StatusCode::PAYLOAD_TOO_LARGE),
+            XaError(_) => self.format_err(StatusCode::INTERNAL_SERVER_ERROR),
         }
     }
 }
@@ -227,9 +227,7 @@ async fn read_decode_request<T: DeserializeOwned + prost::Message + Default>(
     req: hyper::Request<hyper::Body>,
     encoding: Encoding,
 ) -> Result<T> {
-    let req_body = hyper::body::to_bytes(req.into_body())
-        .await
-        .context("Could not read request body")?;
+    let req_body = read_request_body(req).await?;
     match encoding {
         Encoding::Json => serde_json::from_slice(&req_body)
             .map_err(|err| ProtocolError::JsonDeserialize { source: err })
@@ -241,7 +239,11 @@ async fn read_decode_request<T: DeserializeOwned + prost::Message + Default>(
 }
 
 fn protocol_error_response(err: ProtocolError) -> hyper::Response<hyper::Body> {
-    text_response(hyper::StatusCode::BAD_REQUEST, err.to_string())
+    let status = match err {
+        ProtocolError::PayloadTooLarge { .. } => hyper::StatusCode::PAYLOAD_TOO_LARGE,
+        _ => hyper::StatusCode::BAD_REQUEST,
+    };
+    text_response(status, err.to_string())
 }
 
 fn stream_error_response(err: StreamError, encoding: Encoding) -> hyper::Response<hyper::Body> {
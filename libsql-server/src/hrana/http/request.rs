@@ -4,6 +4,8 @@ use bytesize::ByteSize;
 use super::super::{batch, stmt, ProtocolError, Version};
 use super::stream;
 use crate::connection::{Connection, RequestContext};
+use crate::query::{Params, Query};
+use crate::query_analysis::Statement;
 use libsql_hrana::proto;
 
 const MAX_SQL_COUNT: usize = 50;
@@ -62,10 +64,16 @@ async fn try_handle(
     Ok(match request {
         proto::StreamRequest::None => bail!(ProtocolError::NoneStreamRequest),
         proto::StreamRequest::Close(_req) => {
+            if stream_guard.is_snapshot_pinned() {
+                unpin_snapshot(stream_guard, &ctx).await?;
+            }
             stream_guard.close_db();
             proto::StreamResponse::Close(proto::CloseStreamResp {})
         }
         proto::StreamRequest::Execute(req) => {
+            if req.stmt.snapshot == Some(true) && !stream_guard.is_snapshot_pinned() {
+                pin_snapshot(stream_guard, &ctx).await?;
+            }
             let db = stream_guard.get_db()?;
             let sqls = stream_guard.sqls();
             let query =
@@ -76,6 +84,14 @@ async fn try_handle(
             proto::StreamResponse::Execute(proto::ExecuteStreamResp { result })
         }
         proto::StreamRequest::Batch(req) => {
+            let wants_snapshot = req
+                .batch
+                .steps
+                .iter()
+                .any(|step| step.stmt.snapshot == Some(true));
+            if wants_snapshot && !stream_guard.is_snapshot_pinned() {
+                pin_snapshot(stream_guard, &ctx).await?;
+            }
             let db = stream_guard.get_db()?;
             let sqls = stream_guard.sqls();
             let pgm = batch::proto_batch_to_program(&req.batch, sqls, version)
@@ -135,6 +151,42 @@ async fn try_handle(
     })
 }
 
+/// Opens a deferred read transaction on the stream's connection, pinning every subsequent read
+/// (in this request and any later one on the same stream) to the WAL snapshot it observes, until
+/// [`unpin_snapshot`] closes it. Being deferred, it takes no lock of its own until the first read
+/// runs, so it never blocks a concurrent writer the way an upfront write transaction would.
+async fn pin_snapshot(stream_guard: &mut stream::Guard<'_>, ctx: &RequestContext) -> Result<()> {
+    let db = stream_guard.get_db()?;
+    let query = begin_or_end_txn_query("BEGIN DEFERRED");
+    stmt::execute_stmt(db, ctx.clone(), query, None)
+        .await
+        .map_err(catch_stmt_error)?;
+    stream_guard.pin_snapshot();
+    Ok(())
+}
+
+/// Closes the transaction opened by [`pin_snapshot`], releasing the pinned snapshot.
+async fn unpin_snapshot(stream_guard: &mut stream::Guard<'_>, ctx: &RequestContext) -> Result<()> {
+    let db = stream_guard.get_db()?;
+    let query = begin_or_end_txn_query("ROLLBACK");
+    stmt::execute_stmt(db, ctx.clone(), query, None)
+        .await
+        .map_err(catch_stmt_error)?;
+    stream_guard.unpin_snapshot();
+    Ok(())
+}
+
+fn begin_or_end_txn_query(sql: &'static str) -> Query {
+    Query {
+        stmt: Statement::parse(sql)
+            .next()
+            .expect("BEGIN DEFERRED/ROLLBACK always parse to exactly one statement")
+            .expect("BEGIN DEFERRED/ROLLBACK are always valid SQL"),
+        params: Params::empty(),
+        want_rows: false,
+    }
+}
+
 fn catch_stmt_error(err: anyhow::Error) -> anyhow::Error {
     match err.downcast::<stmt::StmtError>() {
         Ok(stmt_err) => anyhow!(StreamResponseError::Stmt(stmt_err)),
@@ -67,6 +67,10 @@ pub(crate) struct Stream {
     /// requests sequentially, the baton returned from each HTTP request includes this sequence
     /// number, and the following HTTP request must show a baton with the same sequence number.
     baton_seq: u64,
+    /// Whether a statement with `snapshot: true` has already pinned this stream's reads to a WAL
+    /// snapshot by opening a deferred read transaction on [`Stream::db`]. Reset to `false` once the
+    /// pinning transaction is closed (see [`Guard::unpin_snapshot`]).
+    snapshot_pinned: bool,
 }
 
 /// Guard object that is used to access a stream from the outside. The guard makes sure that the
@@ -168,6 +172,7 @@ pub async fn acquire<'srv>(
                 // initializing the sequence number randomly makes it much harder to exploit
                 // collisions in batons
                 baton_seq: rand::random(),
+                snapshot_pinned: false,
             });
             state.handles.insert(stream.stream_id, Handle::Acquired);
             STREAM_HANDLES_COUNT.increment(1.0);
@@ -213,6 +218,23 @@ impl<'srv> Guard<'srv> {
         &mut self.stream.as_mut().unwrap().sqls
     }
 
+    /// Whether this stream's reads are currently pinned to a WAL snapshot.
+    pub fn is_snapshot_pinned(&self) -> bool {
+        self.stream.as_ref().unwrap().snapshot_pinned
+    }
+
+    /// Marks this stream's reads as pinned to a WAL snapshot. The caller is responsible for
+    /// actually opening the deferred read transaction on [`Guard::get_db`] beforehand.
+    pub fn pin_snapshot(&mut self) {
+        self.stream.as_mut().unwrap().snapshot_pinned = true;
+    }
+
+    /// Marks this stream's reads as no longer pinned. The caller is responsible for actually
+    /// closing the pinning transaction on [`Guard::get_db`] beforehand.
+    pub fn unpin_snapshot(&mut self) {
+        self.stream.as_mut().unwrap().snapshot_pinned = false;
+    }
+
     /// Releases the guard and returns the baton that can be used to access this stream in the next
     /// HTTP request. Returns `None` if the stream has been closed (and thus cannot be accessed
     /// again).
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 pub mod batch;
 mod cursor;
@@ -6,8 +7,40 @@ pub mod http;
 mod result_builder;
 pub mod stmt;
 pub mod ws;
+/// Shared Hrana wire types, generated once and serialized as either JSON or Protobuf depending
+/// on [`Encoding`]; both [`http`] (the `/v3` and `/v3-protobuf` endpoints) and [`ws`] encode and
+/// decode requests through these same types rather than keeping a separate schema per transport.
 pub use libsql_hrana::proto;
 
+/// Maximum size, in bytes, of an incoming Hrana/HTTP request body, checked before the body is
+/// fully buffered so an oversized request is rejected instead of exhausting memory. Set once at
+/// startup from `DbConfig::max_request_size`.
+pub static MAX_REQUEST_SIZE: AtomicU64 = AtomicU64::new(10 * 1024 * 1024);
+
+pub fn set_max_request_size(bytes: u64) {
+    MAX_REQUEST_SIZE.store(bytes, Ordering::Relaxed);
+}
+
+pub(crate) fn max_request_size() -> u64 {
+    MAX_REQUEST_SIZE.load(Ordering::Relaxed)
+}
+
+/// Reads a full HTTP request body, rejecting it early with [`ProtocolError::PayloadTooLarge`]
+/// instead of buffering arbitrarily large input.
+pub(crate) async fn read_request_body(
+    req: hyper::Request<hyper::Body>,
+) -> anyhow::Result<bytes::Bytes> {
+    let max_size = max_request_size();
+    let limited_body = http_body::Limited::new(req.into_body(), max_size as usize);
+    hyper::body::to_bytes(limited_body).await.map_err(|err| {
+        if err.downcast_ref::<http_body::LengthLimitError>().is_some() {
+            anyhow::Error::new(ProtocolError::PayloadTooLarge { max_size })
+        } else {
+            anyhow::Error::from(err).context("Could not read request body")
+        }
+    })
+}
+
 #[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq)]
 pub enum Version {
     Hrana1,
@@ -76,6 +109,9 @@ pub enum ProtocolError {
     #[error("{0}")]
     ResponseTooLarge(String),
 
+    #[error("request body exceeds the maximum size of {max_size} bytes")]
+    PayloadTooLarge { max_size: u64 },
+
     #[error("BatchCond type not recognized")]
     NoneBatchCond,
     #[error("Value type not recognized")]
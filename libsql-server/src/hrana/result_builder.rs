@@ -1,6 +1,6 @@
 use std::fmt::{self, Write as _};
 use std::io;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
 use bytes::Bytes;
@@ -14,6 +14,23 @@ use crate::replication::FrameNo;
 
 use super::proto;
 
+/// Exponential moving average (times 8, for fixed-point) of the number of rows returned by
+/// recent statements, used to pre-size a fresh [`SingleStatementBuilder`]'s row buffer instead
+/// of growing it one reallocation at a time.
+static AVG_ROW_COUNT_X8: AtomicU64 = AtomicU64::new(0);
+
+fn adaptive_row_capacity_hint() -> usize {
+    (AVG_ROW_COUNT_X8.load(Ordering::Relaxed) / 8) as usize
+}
+
+fn record_row_count(rows: usize) {
+    let rows = rows as u64;
+    let _ = AVG_ROW_COUNT_X8.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |avg_x8| {
+        // avg = avg * 7/8 + rows * 1/8, kept as avg_x8 = avg * 8 to avoid floats.
+        Some(avg_x8 - avg_x8 / 8 + rows)
+    });
+}
+
 #[derive(Debug, Default)]
 pub struct SingleStatementBuilder {
     has_step: bool,
@@ -128,6 +145,7 @@ impl QueryResultBuilder for SingleStatementBuilder {
 
         self.max_response_size = config.max_size.unwrap_or(u64::MAX);
         self.max_total_response_size = config.max_total_size.unwrap_or(u64::MAX);
+        self.rows = Vec::with_capacity(adaptive_row_capacity_hint());
 
         Ok(())
     }
@@ -239,6 +257,7 @@ impl QueryResultBuilder for SingleStatementBuilder {
     }
 
     fn into_ret(mut self) -> Self::Ret {
+        record_row_count(self.rows.len());
         match std::mem::take(&mut self.err) {
             Some(err) => Err(err),
             None => Ok(proto::StmtResult {
@@ -35,6 +35,10 @@ struct Conn {
     responses: FuturesUnordered<ResponseFuture>,
     /// Namespace queried by this connections
     namespace: NamespaceName,
+    /// Set while we're waiting for a pong to the heartbeat ping we most recently sent. If we
+    /// have to send another one before this is cleared, the peer missed a full heartbeat
+    /// interval and we consider the connection dead.
+    awaiting_pong: bool,
 }
 
 /// A `Future` that stores a handle to a future response to request which is being evaluated
@@ -105,8 +109,19 @@ async fn handle_ws(
         join_set: tokio::task::JoinSet::new(),
         responses: FuturesUnordered::new(),
         namespace,
+        awaiting_pong: false,
     };
 
+    let mut heartbeat = conn
+        .server
+        .heartbeat_interval
+        .map(tokio::time::interval);
+    if let Some(heartbeat) = heartbeat.as_mut() {
+        // the first tick fires immediately; consume it so the first real ping is a full
+        // interval after the connection was established, not right away.
+        heartbeat.tick().await;
+    }
+
     loop {
         tokio::select! {
             Some(client_msg_res) = conn.ws.recv() => {
@@ -142,6 +157,21 @@ async fn handle_ws(
                 let response_msg = response_res?;
                 send_msg(&mut conn, &response_msg).await?;
             },
+            _ = async { heartbeat.as_mut().unwrap().tick().await }, if heartbeat.is_some() => {
+                if conn.awaiting_pong {
+                    tracing::warn!(
+                        "Connection #{} missed a heartbeat pong, closing as dead",
+                        conn.conn_id,
+                    );
+                    close(&mut conn, CloseCode::Policy, "Heartbeat timeout".into()).await;
+                    return Ok(());
+                }
+                conn.awaiting_pong = true;
+                conn.ws
+                    .send(tungstenite::Message::Ping(Vec::new()))
+                    .await
+                    .context("Could not send heartbeat ping to the WebSocket")?;
+            },
             else => break,
         }
 
@@ -187,7 +217,10 @@ async fn handle_msg(conn: &mut Conn, client_msg: tungstenite::Message) -> Result
                 .context("Could not send pong to the WebSocket")?;
             Ok(true)
         }
-        tungstenite::Message::Pong(_) => Ok(true),
+        tungstenite::Message::Pong(_) => {
+            conn.awaiting_pong = false;
+            Ok(true)
+        }
         tungstenite::Message::Close(_) => Ok(false),
         tungstenite::Message::Frame(_) => panic!("Received a tungstenite::Message::Frame"),
     }
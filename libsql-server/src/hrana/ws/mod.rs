@@ -2,6 +2,7 @@ use std::future::poll_fn;
 use std::net::SocketAddr;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use enclose::enclose;
@@ -28,6 +29,8 @@ struct Server {
     next_conn_id: AtomicU64,
     disable_default_namespace: bool,
     disable_namespaces: bool,
+    /// See [`UserApiConfig::hrana_ws_heartbeat_interval`](crate::config::UserApiConfig::hrana_ws_heartbeat_interval).
+    heartbeat_interval: Option<Duration>,
 }
 
 pub struct Accept {
@@ -51,6 +54,7 @@ pub async fn serve(
     namespaces: NamespaceStore,
     disable_default_namespace: bool,
     disable_namespaces: bool,
+    heartbeat_interval: Option<Duration>,
 ) -> Result<()> {
     let server = Arc::new(Server {
         user_auth_strategy,
@@ -60,6 +64,7 @@ pub async fn serve(
         namespaces,
         disable_default_namespace,
         disable_namespaces,
+        heartbeat_interval,
     });
 
     let mut join_set = tokio::task::JoinSet::new();
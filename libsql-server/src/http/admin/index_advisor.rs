@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::Json;
+
+use crate::index_advisor::IndexAdvisorReport;
+use crate::namespace::NamespaceName;
+
+use super::AppState;
+
+pub(super) async fn handle_index_advisor<C>(
+    State(app_state): State<Arc<AppState<C>>>,
+    Path(namespace): Path<String>,
+) -> crate::Result<Json<IndexAdvisorReport>> {
+    let report = app_state
+        .namespaces
+        .index_advisor_report(NamespaceName::from_string(namespace)?)
+        .await?;
+
+    Ok(Json(report))
+}
@@ -1,16 +1,19 @@
 use anyhow::Context as _;
 use axum::body::StreamBody;
-use axum::extract::{FromRef, Path, State};
+use axum::extract::{FromRef, Path, Query, State};
 use axum::middleware::Next;
 use axum::routing::delete;
 use axum::Json;
+use base64::prelude::{Engine as _, BASE64_STANDARD_NO_PAD};
 use chrono::NaiveDateTime;
 use futures::{SinkExt, StreamExt, TryStreamExt};
+use hmac::Mac as _;
 use hyper::{Body, Request, StatusCode};
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::cell::OnceCell;
+use std::collections::BTreeSet;
 use std::convert::Infallible;
 use std::io::ErrorKind;
 use std::path::PathBuf;
@@ -23,14 +26,19 @@ use tower_http::trace::DefaultOnResponse;
 use url::Url;
 
 use crate::auth::parse_jwt_keys;
-use crate::connection::config::{DatabaseConfig, DurabilityMode};
+use crate::connection::config::{DatabaseConfig, DurabilityMode, WriteQueueFairness};
+use crate::connection::Connection as _;
 use crate::error::{Error, LoadDumpError};
 use crate::hrana;
 use crate::namespace::{DumpStream, NamespaceName, NamespaceStore, RestoreOption};
 use crate::net::Connector;
 use crate::LIBSQL_PAGE_SIZE;
 
+pub mod index_advisor;
+pub mod queue;
+pub mod statements;
 pub mod stats;
+pub mod xa;
 
 #[derive(Clone)]
 struct Metrics {
@@ -49,6 +57,10 @@ struct AppState<C> {
     connector: C,
     metrics: Metrics,
     set_env_filter: Option<Box<dyn Fn(&str) -> anyhow::Result<()> + Sync + Send + 'static>>,
+    /// The admin API bearer token, if configured. Doubles as the signing key for namespace
+    /// config bundles, so a bundle exported from one instance only imports cleanly on another
+    /// instance that shares the same admin secret.
+    admin_auth: Option<Arc<str>>,
 }
 
 impl<C> FromRef<Arc<AppState<C>>> for Metrics {
@@ -142,6 +154,8 @@ where
     };
     let router = axum::Router::new()
         .route("/", get(handle_get_index))
+        .route("/v1/namespaces/export", get(handle_export_namespaces))
+        .route("/v1/namespaces/import", post(handle_import_namespaces))
         .route(
             "/v1/namespaces/:namespace/config",
             get(handle_get_config).post(handle_post_config),
@@ -150,6 +164,10 @@ where
             "/v1/namespaces/:namespace/fork/:to",
             post(handle_fork_namespace),
         )
+        .route(
+            "/v1/namespaces/:namespace/rename/:to",
+            post(handle_rename_namespace),
+        )
         .route(
             "/v1/namespaces/:namespace/create",
             post(handle_create_namespace),
@@ -158,12 +176,42 @@ where
             "/v1/namespaces/:namespace/checkpoint",
             post(handle_checkpoint),
         )
+        .route(
+            "/v1/namespaces/:namespace/promote",
+            post(handle_promote_namespace),
+        )
         .route("/v1/namespaces/:namespace", delete(handle_delete_namespace))
+        .route(
+            "/v1/namespaces/:namespace/delete_status",
+            get(handle_get_delete_status),
+        )
         .route("/v1/namespaces/:namespace/stats", get(stats::handle_stats))
+        .route("/v1/namespaces/:namespace/queue", get(queue::handle_queue))
+        .route(
+            "/v1/namespaces/:namespace/index-advisor",
+            get(index_advisor::handle_index_advisor),
+        )
+        .route(
+            "/v1/namespaces/:namespace/statements",
+            get(statements::handle_list_statements),
+        )
+        .route(
+            "/v1/namespaces/:namespace/statements/:id",
+            post(statements::handle_register_statement).delete(statements::handle_delete_statement),
+        )
+        .route(
+            "/v1/namespaces/:namespace/stats/history",
+            get(stats::handle_stats_history),
+        )
+        .route(
+            "/v1/namespaces/:namespace/incidents",
+            get(handle_get_incidents),
+        )
         .route(
             "/v1/namespaces/:namespace/stats/:stats_type",
             delete(stats::handle_delete_stats),
         )
+        .route("/v1/transactions", post(xa::handle_two_phase_write))
         .route("/v1/diagnostics", get(handle_diagnostics))
         .route("/metrics", get(handle_metrics))
         .route("/profile/heap/enable", post(enable_profile_heap))
@@ -176,6 +224,7 @@ where
             user_http_server,
             metrics,
             set_env_filter,
+            admin_auth: auth.clone(),
         }))
         .layer(
             tower_http::trace::TraceLayer::new_for_http()
@@ -262,20 +311,56 @@ async fn handle_get_config<C: Connector>(
         allow_attach: config.allow_attach,
         txn_timeout_s: config.txn_timeout.map(|d| d.as_secs() as u64),
         durability_mode: Some(config.durability_mode),
+        relaxed_durability_sync_interval_ms: config
+            .relaxed_durability_sync_interval
+            .map(|d| d.as_millis() as u64),
+        feature_flags: Some(config.feature_flags.clone()),
+        epoch: Some(config.epoch),
+        collations: Some(config.collations.clone()),
+        extensions: Some(config.extensions.clone()),
+        write_queue_fairness: Some(config.write_queue_fairness),
     };
     Ok(Json(resp))
 }
 
+#[derive(Debug, Serialize)]
+struct TokioRuntimeDiagnostics {
+    num_workers: usize,
+    num_alive_tasks: usize,
+    num_blocking_threads: usize,
+    blocking_queue_depth: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct DiagnosticsResponse {
+    /// One entry per open Hrana-over-HTTP stream, describing the state of its pooled connection.
+    connections: Vec<String>,
+    /// Number of file descriptors currently open by this process, if available (Linux only).
+    open_file_descriptors: Option<usize>,
+    tokio_runtime: TokioRuntimeDiagnostics,
+    /// Panics recently caught from a namespace's connection tasks, keyed by namespace name.
+    /// Empty for namespaces that haven't had one.
+    recent_errors: std::collections::HashMap<String, Vec<crate::incidents::Incident>>,
+}
+
+/// Returns the number of file descriptors this process currently has open, by counting the
+/// entries of `/proc/self/fd`. `None` on platforms without a `/proc` filesystem.
+fn open_file_descriptors() -> Option<usize> {
+    std::fs::read_dir("/proc/self/fd")
+        .ok()
+        .map(|entries| entries.count())
+}
+
 async fn handle_diagnostics<C>(
     State(app_state): State<Arc<AppState<C>>>,
-) -> crate::Result<Json<Vec<String>>> {
+) -> crate::Result<Json<DiagnosticsResponse>> {
     use crate::connection::Connection;
     use hrana::http::stream;
 
     let server = app_state.user_http_server.as_ref();
     let stream_state = server.stream_state().lock();
     let handles = stream_state.handles();
-    let mut diagnostics: Vec<String> = Vec::with_capacity(handles.len());
+    let mut connections: Vec<String> = Vec::with_capacity(handles.len());
     for handle in handles.values() {
         let handle_info: String = match handle {
             stream::Handle::Available(stream) => match &stream.db {
@@ -285,10 +370,30 @@ async fn handle_diagnostics<C>(
             stream::Handle::Acquired => "acquired".into(),
             stream::Handle::Expired => "expired".into(),
         };
-        diagnostics.push(handle_info);
+        connections.push(handle_info);
     }
     drop(stream_state);
 
+    let runtime_metrics = tokio::runtime::Handle::current().metrics();
+    let tokio_runtime = TokioRuntimeDiagnostics {
+        num_workers: runtime_metrics.num_workers(),
+        num_alive_tasks: runtime_metrics.num_alive_tasks(),
+        num_blocking_threads: runtime_metrics.num_blocking_threads(),
+        blocking_queue_depth: runtime_metrics.blocking_queue_depth(),
+    };
+
+    let recent_errors = crate::incidents::all_incidents()
+        .into_iter()
+        .map(|(namespace, incidents)| (namespace.as_str().to_owned(), incidents))
+        .collect();
+
+    let diagnostics = DiagnosticsResponse {
+        connections,
+        open_file_descriptors: open_file_descriptors(),
+        tokio_runtime,
+        recent_errors,
+    };
+
     tracing::trace!("diagnostics: {diagnostics:?}");
     Ok(Json(diagnostics))
 }
@@ -311,6 +416,19 @@ struct HttpDatabaseConfig {
     txn_timeout_s: Option<u64>,
     #[serde(default)]
     durability_mode: Option<DurabilityMode>,
+    #[serde(default)]
+    relaxed_durability_sync_interval_ms: Option<u64>,
+    #[serde(default)]
+    feature_flags: Option<BTreeSet<String>>,
+    /// Current fencing epoch. Read-only here: it's only ever bumped via the `/promote` endpoint.
+    #[serde(default)]
+    epoch: Option<u64>,
+    #[serde(default)]
+    collations: Option<BTreeSet<String>>,
+    #[serde(default)]
+    extensions: Option<BTreeSet<String>>,
+    #[serde(default)]
+    write_queue_fairness: Option<WriteQueueFairness>,
 }
 
 async fn handle_post_config<C>(
@@ -343,6 +461,22 @@ async fn handle_post_config<C>(
     if let Some(mode) = req.durability_mode {
         updated.durability_mode = mode;
     }
+    if let Some(sync_interval_ms) = req.relaxed_durability_sync_interval_ms {
+        updated.relaxed_durability_sync_interval = (sync_interval_ms > 0)
+            .then(|| Duration::from_millis(sync_interval_ms));
+    }
+    if let Some(feature_flags) = req.feature_flags {
+        updated.feature_flags = feature_flags;
+    }
+    if let Some(collations) = req.collations {
+        updated.collations = collations;
+    }
+    if let Some(extensions) = req.extensions {
+        updated.extensions = extensions;
+    }
+    if let Some(fairness) = req.write_queue_fairness {
+        updated.write_queue_fairness = fairness;
+    }
 
     store.store(updated.clone()).await?;
     // we better to not log jwt token - so let's explicitly log necessary fields
@@ -359,6 +493,9 @@ async fn handle_post_config<C>(
         max_db_pages_after = updated.max_db_pages,
         durability_mode_before = original.durability_mode.to_string(),
         durability_mode_after = updated.durability_mode.to_string(),
+        relaxed_durability_sync_interval_before = ?original.relaxed_durability_sync_interval,
+        relaxed_durability_sync_interval_after = ?updated.relaxed_durability_sync_interval,
+        feature_flags_after = ?updated.feature_flags,
     );
 
     Ok(())
@@ -383,6 +520,16 @@ struct CreateNamespaceReq {
     allow_attach: bool,
     #[serde(default)]
     durability_mode: Option<DurabilityMode>,
+    #[serde(default)]
+    relaxed_durability_sync_interval_ms: Option<u64>,
+    #[serde(default)]
+    feature_flags: BTreeSet<String>,
+    #[serde(default)]
+    collations: BTreeSet<String>,
+    #[serde(default)]
+    extensions: BTreeSet<String>,
+    #[serde(default)]
+    write_queue_fairness: WriteQueueFairness,
 }
 
 async fn handle_create_namespace<C: Connector>(
@@ -435,6 +582,13 @@ async fn handle_create_namespace<C: Connector>(
         config.max_db_pages = max_db_size.as_u64() / LIBSQL_PAGE_SIZE;
     }
     config.durability_mode = req.durability_mode.unwrap_or(DurabilityMode::default());
+    config.relaxed_durability_sync_interval = req
+        .relaxed_durability_sync_interval_ms
+        .map(Duration::from_millis);
+    config.feature_flags = req.feature_flags;
+    config.collations = req.collations;
+    config.extensions = req.extensions;
+    config.write_queue_fairness = req.write_queue_fairness;
 
     app_state.namespaces.create(namespace, dump, config).await?;
 
@@ -470,6 +624,171 @@ async fn handle_fork_namespace<C>(
     Ok(())
 }
 
+async fn handle_rename_namespace<C>(
+    State(app_state): State<Arc<AppState<C>>>,
+    Path((from, to)): Path<(String, String)>,
+) -> crate::Result<()> {
+    let from = NamespaceName::from_string(from)?;
+    let to = NamespaceName::from_string(to)?;
+    app_state.namespaces.rename(from, to).await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct ExportNamespacesQuery {
+    /// Also include each namespace's `CREATE TABLE`/`CREATE INDEX`/... statements, read from
+    /// `sqlite_master`. Off by default since it requires loading every namespace.
+    #[serde(default)]
+    with_schemas: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NamespaceConfigEntry {
+    namespace: String,
+    config: DatabaseConfig,
+    #[serde(default)]
+    schema: Option<String>,
+}
+
+/// A portable snapshot of every namespace's config (and, optionally, schema) on an instance, for
+/// disaster recovery or cloning namespaces onto another instance.
+#[derive(Debug, Serialize, Deserialize)]
+struct NamespaceConfigBundle {
+    namespaces: Vec<NamespaceConfigEntry>,
+    /// Base64-encoded HMAC-SHA256 over `namespaces`, keyed with the admin API bearer token. This
+    /// only proves the bundle came from (or is destined for) an instance configured with the
+    /// same admin secret; it isn't a substitute for transporting the bundle over a secure
+    /// channel.
+    signature: String,
+}
+
+/// Builds the HMAC-SHA256 over `namespaces`, keyed with the admin API bearer token, or an empty
+/// key if the admin API has no auth configured.
+fn mac_namespace_bundle(
+    admin_auth: &Option<Arc<str>>,
+    namespaces: &[NamespaceConfigEntry],
+) -> crate::Result<hmac::Hmac<sha2::Sha256>> {
+    let key = admin_auth.as_deref().unwrap_or("").as_bytes();
+    let mut mac = hmac::Hmac::<sha2::Sha256>::new_from_slice(key)
+        .expect("HMAC-SHA256 accepts keys of any length");
+    mac.update(&serde_json::to_vec(namespaces)?);
+    Ok(mac)
+}
+
+/// Signs `namespaces` with the admin API bearer token, or an empty key if the admin API has no
+/// auth configured.
+fn sign_namespace_bundle(
+    admin_auth: &Option<Arc<str>>,
+    namespaces: &[NamespaceConfigEntry],
+) -> crate::Result<String> {
+    let mac = mac_namespace_bundle(admin_auth, namespaces)?;
+    Ok(BASE64_STANDARD_NO_PAD.encode(mac.finalize().into_bytes()))
+}
+
+/// Checks `signature` against the HMAC-SHA256 over `namespaces`, in constant time.
+fn verify_namespace_bundle(
+    admin_auth: &Option<Arc<str>>,
+    namespaces: &[NamespaceConfigEntry],
+    signature: &str,
+) -> crate::Result<bool> {
+    let mac = mac_namespace_bundle(admin_auth, namespaces)?;
+    let Ok(signature) = BASE64_STANDARD_NO_PAD.decode(signature) else {
+        return Ok(false);
+    };
+    Ok(mac.verify_slice(&signature).is_ok())
+}
+
+async fn handle_export_namespaces<C>(
+    State(app_state): State<Arc<AppState<C>>>,
+    Query(query): Query<ExportNamespacesQuery>,
+) -> crate::Result<Json<NamespaceConfigBundle>> {
+    let mut namespaces = Vec::new();
+    for name in app_state.namespaces.meta_store().all_namespaces() {
+        let config = app_state.namespaces.config_store(name.clone()).await?.get();
+        let schema = if query.with_schemas {
+            Some(dump_namespace_schema(&app_state.namespaces, name.clone()).await?)
+        } else {
+            None
+        };
+        namespaces.push(NamespaceConfigEntry {
+            namespace: name.to_string(),
+            config: (*config).clone(),
+            schema,
+        });
+    }
+
+    let signature = sign_namespace_bundle(&app_state.admin_auth, &namespaces)?;
+    Ok(Json(NamespaceConfigBundle {
+        namespaces,
+        signature,
+    }))
+}
+
+/// Reads every `CREATE ...` statement out of `sqlite_master`, joined into a single script that
+/// can be replayed with `execute_batch` on another database.
+async fn dump_namespace_schema<C>(
+    namespaces: &NamespaceStore,
+    namespace: NamespaceName,
+) -> crate::Result<String> {
+    let conn_maker = namespaces
+        .with(namespace, |ns| ns.db.connection_maker())
+        .await?;
+    let conn = conn_maker.create().await?;
+    let statements = conn.with_raw(|conn| -> rusqlite::Result<Vec<String>> {
+        let mut stmt =
+            conn.prepare("SELECT sql FROM sqlite_master WHERE sql IS NOT NULL ORDER BY rowid")?;
+        stmt.query_map((), |row| row.get::<_, String>(0))?
+            .collect()
+    })?;
+
+    Ok(statements
+        .into_iter()
+        .map(|s| format!("{s};\n"))
+        .collect())
+}
+
+async fn handle_import_namespaces<C>(
+    State(app_state): State<Arc<AppState<C>>>,
+    Json(bundle): Json<NamespaceConfigBundle>,
+) -> crate::Result<Json<Vec<String>>> {
+    if !verify_namespace_bundle(&app_state.admin_auth, &bundle.namespaces, &bundle.signature)? {
+        return Err(Error::Forbidden(
+            "namespace config bundle signature is invalid or the bundle was tampered with"
+                .to_string(),
+        ));
+    }
+
+    let mut imported = Vec::new();
+    for entry in bundle.namespaces {
+        let namespace = NamespaceName::from_string(entry.namespace)?;
+        if app_state.namespaces.meta_store().exists(&namespace).await {
+            tracing::warn!(
+                "skipping import of `{namespace}`: a namespace with that name already exists"
+            );
+            continue;
+        }
+
+        app_state
+            .namespaces
+            .create(namespace.clone(), RestoreOption::Latest, entry.config)
+            .await?;
+
+        if let Some(schema) = entry.schema {
+            let conn_maker = app_state
+                .namespaces
+                .with(namespace.clone(), |ns| ns.db.connection_maker())
+                .await?;
+            let conn = conn_maker.create().await?;
+            conn.with_raw(|conn| conn.execute_batch(&schema))?;
+        }
+
+        imported.push(namespace.to_string());
+    }
+
+    Ok(Json(imported))
+}
+
 async fn dump_stream_from_url<C>(url: &Url, connector: C) -> Result<DumpStream, LoadDumpError>
 where
     C: Connector,
@@ -532,6 +851,39 @@ async fn handle_delete_namespace<C>(
     Ok(())
 }
 
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum DeleteStatusResp {
+    InProgress,
+    Completed,
+    Failed { error: String },
+    Unknown,
+}
+
+impl From<Option<crate::namespace::DeletionStatus>> for DeleteStatusResp {
+    fn from(status: Option<crate::namespace::DeletionStatus>) -> Self {
+        use crate::namespace::DeletionStatus;
+        match status {
+            Some(DeletionStatus::InProgress) => Self::InProgress,
+            Some(DeletionStatus::Completed) => Self::Completed,
+            Some(DeletionStatus::Failed { error }) => Self::Failed { error },
+            None => Self::Unknown,
+        }
+    }
+}
+
+/// Reports how a namespace deletion started with [`handle_delete_namespace`] is progressing.
+/// Returns `Unknown` both for namespaces that were never deleted and for deletions that completed
+/// before this node's last restart, since the tombstone registry isn't persisted.
+async fn handle_get_delete_status<C>(
+    State(app_state): State<Arc<AppState<C>>>,
+    Path(namespace): Path<String>,
+) -> crate::Result<Json<DeleteStatusResp>> {
+    let namespace = NamespaceName::from_string(namespace)?;
+    let status = app_state.namespaces.deletion_status(&namespace);
+    Ok(Json(status.into()))
+}
+
 async fn handle_set_log_filter<C>(
     State(app_state): State<Arc<AppState<C>>>,
     body: String,
@@ -550,6 +902,44 @@ async fn handle_checkpoint<C>(
     Ok(())
 }
 
+/// Promotes a namespace out of standby mode: bumps its fencing epoch and lifts the read/write
+/// block that `--standby` puts in place until promotion. This only flips the flags that gate
+/// traffic on this node; it doesn't change which node the rest of the cluster treats as primary,
+/// so operators still need to repoint writers (DNS, a replica's `--primary-grpc-url`, ...) at the
+/// newly promoted node themselves.
+async fn handle_promote_namespace<C>(
+    State(app_state): State<Arc<AppState<C>>>,
+    Path(namespace): Path<String>,
+) -> crate::Result<()> {
+    let store = app_state
+        .namespaces
+        .config_store(NamespaceName::from_string(namespace.clone())?)
+        .await?;
+    let original = (*store.get()).clone();
+    let mut updated = original.clone();
+    updated.epoch = original.epoch + 1;
+    updated.block_reads = false;
+    updated.block_writes = false;
+    updated.block_reason = None;
+
+    store.store(updated.clone()).await?;
+    tracing::info!(
+        message = "promoted namespace out of standby",
+        namespace = namespace,
+        epoch_before = original.epoch,
+        epoch_after = updated.epoch,
+    );
+
+    Ok(())
+}
+
+async fn handle_get_incidents<C>(
+    State(_app_state): State<Arc<AppState<C>>>,
+    Path(namespace): Path<NamespaceName>,
+) -> crate::Result<Json<Vec<crate::incidents::Incident>>> {
+    Ok(Json(crate::incidents::incidents_for(&namespace)))
+}
+
 #[derive(serde::Deserialize)]
 struct EnableHeapProfileRequest {
     #[serde(default)]
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::Json;
+use serde::Serialize;
+
+use crate::connection::config::WriteQueueFairness;
+use crate::namespace::NamespaceName;
+
+use super::AppState;
+
+#[derive(Serialize)]
+pub struct QueueResponse {
+    /// Ordering policy currently in effect for this namespace.
+    pub fairness: WriteQueueFairness,
+    /// Number of connections currently parked waiting for the write lock.
+    pub queue_len: usize,
+    /// How long the connection at the front of the queue has been waiting, in milliseconds, if
+    /// any.
+    pub longest_wait_ms: Option<u128>,
+    /// How long the connection currently holding the write lock (or checkpoint) has held it, in
+    /// milliseconds, if any.
+    pub held_for_ms: Option<u128>,
+}
+
+pub(super) async fn handle_queue<C>(
+    State(app_state): State<Arc<AppState<C>>>,
+    Path(namespace): Path<String>,
+) -> crate::Result<Json<QueueResponse>> {
+    let queue_stats = app_state
+        .namespaces
+        .queue_stats(NamespaceName::from_string(namespace)?)
+        .await?;
+
+    let resp = match queue_stats {
+        Some(stats) => QueueResponse {
+            fairness: stats.fairness,
+            queue_len: stats.queue_len,
+            longest_wait_ms: stats.longest_wait.map(|d| d.as_millis()),
+            held_for_ms: stats.held_for.map(|d| d.as_millis()),
+        },
+        // replicas proxy writes to their primary instead of queuing them locally
+        None => QueueResponse {
+            fairness: WriteQueueFairness::default(),
+            queue_len: 0,
+            longest_wait_ms: None,
+            held_for_ms: None,
+        },
+    };
+
+    Ok(Json(resp))
+}
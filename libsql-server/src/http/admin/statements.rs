@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::namespace::statements::RegisteredStatement;
+use crate::namespace::NamespaceName;
+
+use super::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterStatementReq {
+    pub sql: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatementResp {
+    pub id: String,
+    pub sql: String,
+}
+
+pub(super) async fn handle_list_statements<C>(
+    State(app_state): State<Arc<AppState<C>>>,
+    Path(namespace): Path<String>,
+) -> crate::Result<Json<Vec<StatementResp>>> {
+    let statements = app_state
+        .namespaces
+        .statements(NamespaceName::from_string(namespace)?)
+        .list()
+        .into_iter()
+        .map(|(id, RegisteredStatement { sql })| StatementResp { id, sql })
+        .collect();
+
+    Ok(Json(statements))
+}
+
+pub(super) async fn handle_register_statement<C>(
+    State(app_state): State<Arc<AppState<C>>>,
+    Path((namespace, id)): Path<(String, String)>,
+    Json(req): Json<RegisterStatementReq>,
+) -> crate::Result<()> {
+    app_state
+        .namespaces
+        .statements(NamespaceName::from_string(namespace)?)
+        .register(id, req.sql);
+
+    Ok(())
+}
+
+pub(super) async fn handle_delete_statement<C>(
+    State(app_state): State<Arc<AppState<C>>>,
+    Path((namespace, id)): Path<(String, String)>,
+) -> crate::Result<()> {
+    app_state
+        .namespaces
+        .statements(NamespaceName::from_string(namespace)?)
+        .remove(&id);
+
+    Ok(())
+}
@@ -3,15 +3,15 @@ use std::time::Duration;
 
 use hdrhistogram::Histogram;
 use itertools::Itertools;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::Json;
 use uuid::Uuid;
 
 use crate::namespace::NamespaceName;
 use crate::replication::FrameNo;
-use crate::stats::{QueryStats, SlowestQuery, Stats, TopQuery};
+use crate::stats::{QueryStats, SlowestQuery, Stats, StatsSample, TopQuery};
 
 use super::AppState;
 
@@ -149,6 +149,30 @@ pub(super) async fn handle_stats<C>(
     Ok(Json(resp))
 }
 
+#[derive(Deserialize)]
+pub struct HistoryQuery {
+    /// Start of the range, as a unix timestamp in seconds. Defaults to 24 hours ago.
+    from: Option<i64>,
+    /// End of the range, as a unix timestamp in seconds. Defaults to now.
+    to: Option<i64>,
+}
+
+pub(super) async fn handle_stats_history<C>(
+    State(app_state): State<Arc<AppState<C>>>,
+    Path(namespace): Path<String>,
+    Query(range): Query<HistoryQuery>,
+) -> crate::Result<Json<Vec<StatsSample>>> {
+    let stats = app_state
+        .namespaces
+        .stats(NamespaceName::from_string(namespace)?)
+        .await?;
+    let now = chrono::Utc::now().timestamp();
+    let from = range.from.unwrap_or(now - 24 * 3600);
+    let to = range.to.unwrap_or(now);
+
+    Ok(Json(stats.history_range(from, to)))
+}
+
 pub(super) async fn handle_delete_stats<C>(
     State(app_state): State<Arc<AppState<C>>>,
     Path((namespace, stats_type)): Path<(String, String)>,
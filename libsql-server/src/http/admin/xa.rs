@@ -0,0 +1,17 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::Json;
+
+use crate::xa::{TwoPhaseCoordinator, TwoPhaseWrite};
+
+use super::AppState;
+
+pub(super) async fn handle_two_phase_write<C>(
+    State(app_state): State<Arc<AppState<C>>>,
+    Json(batch): Json<TwoPhaseWrite>,
+) -> crate::Result<()> {
+    TwoPhaseCoordinator::new(app_state.namespaces.clone())
+        .execute(batch)
+        .await
+}
@@ -0,0 +1,90 @@
+//! Plain HTTP/1.1 fallback for streaming replication frames, for environments where gRPC/h2
+//! egress is blocked by a proxy or firewall. Frames are sent as newline-delimited, base64-encoded
+//! JSON objects over a regular chunked HTTP response, so a client only needs to be able to issue a
+//! GET request and read a streamed body - no h2, no grpc-web framing.
+//!
+//! This is a fallback, not a replacement: unlike the gRPC `ReplicationLog` service, it doesn't
+//! support snapshots, so a client that falls behind enough to need one should reconnect over gRPC.
+//! The stream closes after [`MAX_FRAMES_PER_REQUEST`] frames (mirroring
+//! [`crate::rpc::replication::replication_log::MAX_FRAMES_PER_BATCH`]) so a client just issues
+//! another long-poll request with an advanced `next_offset` to keep going.
+
+use axum::extract::{Query, State as AxumState};
+use base64::prelude::BASE64_STANDARD_NO_PAD;
+use base64::Engine;
+use bytes::Bytes;
+use futures::StreamExt;
+use hyper::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::Authenticated;
+use crate::error::Error;
+use crate::replication::primary::frame_stream::FrameStream;
+use crate::replication::LogReadError;
+
+use super::db_factory::namespace_from_headers;
+use super::AppState;
+
+const MAX_FRAMES_PER_REQUEST: usize = 1024;
+
+#[derive(Deserialize)]
+pub struct FramesQuery {
+    next_offset: u64,
+}
+
+#[derive(Serialize)]
+struct FrameLine {
+    /// base64-encoded frame bytes, in the same wire format as the gRPC `Frame.data` field.
+    data: String,
+}
+
+pub(super) async fn handle_frames(
+    auth: Authenticated,
+    AxumState(state): AxumState<AppState>,
+    headers: HeaderMap,
+    query: Query<FramesQuery>,
+) -> crate::Result<axum::body::StreamBody<impl futures::Stream<Item = Result<Bytes, Error>>>> {
+    let namespace = namespace_from_headers(
+        &headers,
+        state.disable_default_namespace,
+        state.disable_namespaces,
+    )?;
+
+    if !auth.is_namespace_authorized(&namespace) {
+        return Err(Error::NamespaceDoesntExist(namespace.to_string()));
+    }
+
+    let logger = state
+        .namespaces
+        .with(namespace, |ns| ns.db.logger().ok_or(Error::NotAPrimary))
+        .await??;
+
+    let frames = FrameStream::new(
+        logger,
+        query.next_offset,
+        true,
+        Some(MAX_FRAMES_PER_REQUEST),
+        None,
+    )?;
+
+    let stream = frames.map(|res| match res {
+        Ok((frame, _timestamp)) => {
+            let line = FrameLine {
+                data: BASE64_STANDARD_NO_PAD.encode(frame.bytes()),
+            };
+            let mut line = serde_json::to_vec(&line).map_err(|e| Error::Anyhow(e.into()))?;
+            line.push(b'\n');
+            Ok(Bytes::from(line))
+        }
+        Err(LogReadError::SnapshotRequired) => Err(Error::Anyhow(anyhow::anyhow!(
+            "replica is too far behind and needs a snapshot, reconnect over the gRPC replication API"
+        ))),
+        Err(LogReadError::Error(e)) => Err(Error::Anyhow(e)),
+        // caught by `wait_for_more` internally, but handled here for completeness
+        Err(LogReadError::Ahead) => {
+            Err(Error::Anyhow(anyhow::anyhow!("frame not yet available")))
+        }
+    });
+
+    Ok(axum::body::StreamBody::new(stream))
+}
@@ -101,7 +101,7 @@ where
     FT: MakeConnection + ?Sized,
 {
     let res: Result<_> = async move {
-        let req_body = hyper::body::to_bytes(req.into_body()).await?;
+        let req_body = hrana::read_request_body(req).await?;
         let req_body = serde_json::from_slice(&req_body)
             .map_err(|e| hrana::ProtocolError::JsonDeserialize { source: e })?;
 
@@ -158,8 +158,12 @@ fn response_error_response(err: ResponseError) -> hyper::Response<hyper::Body> {
 }
 
 fn protocol_error_response(err: hrana::ProtocolError) -> hyper::Response<hyper::Body> {
+    let status = match err {
+        hrana::ProtocolError::PayloadTooLarge { .. } => hyper::StatusCode::PAYLOAD_TOO_LARGE,
+        _ => hyper::StatusCode::BAD_REQUEST,
+    };
     hyper::Response::builder()
-        .status(hyper::StatusCode::BAD_REQUEST)
+        .status(status)
         .header(hyper::http::header::CONTENT_TYPE, "text/plain")
         .body(hyper::Body::from(err.to_string()))
         .unwrap()
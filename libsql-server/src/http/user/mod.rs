@@ -1,15 +1,18 @@
 pub mod db_factory;
 mod dump;
 mod extract;
+mod frames;
 mod hrana_over_http_1;
 mod listen;
 mod result_builder;
+mod serialize;
 mod trace;
 mod types;
 #[macro_use]
 pub mod timing;
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Context;
 use axum::extract::{FromRef, FromRequest, FromRequestParts, Path as AxumPath, State as AxumState};
@@ -203,6 +206,32 @@ async fn handle_fallback() -> impl IntoResponse {
     (StatusCode::NOT_FOUND).into_response()
 }
 
+/// Builds the CORS policy for the user HTTP API. When `origins` is `None`, any origin is
+/// allowed, which is the historical behavior and is appropriate when the API sits behind a
+/// trusted proxy rather than being called directly from a browser.
+fn make_cors_layer(origins: Option<&[String]>) -> cors::CorsLayer {
+    let layer = cors::CorsLayer::new()
+        .allow_methods(cors::AllowMethods::any())
+        .allow_headers(cors::Any);
+
+    match origins {
+        Some(origins) => {
+            let origins = origins
+                .iter()
+                .filter_map(|origin| match HeaderValue::from_str(origin) {
+                    Ok(value) => Some(value),
+                    Err(_) => {
+                        tracing::warn!("ignoring invalid CORS origin: {origin}");
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+            layer.allow_origin(cors::AllowOrigin::list(origins))
+        }
+        None => layer.allow_origin(cors::Any),
+    }
+}
+
 async fn handle_hrana_pipeline(
     AxumState(state): AxumState<AppState>,
     MakeConnectionExtractorPath(connection_maker): MakeConnectionExtractorPath,
@@ -256,6 +285,8 @@ pub struct UserApi<A, P, S> {
     pub enable_console: bool,
     pub self_url: Option<String>,
     pub primary_url: Option<String>,
+    pub cors_origins: Option<Vec<String>>,
+    pub hrana_ws_heartbeat_interval: Option<Duration>,
 }
 
 impl<A, P, S> UserApi<A, P, S>
@@ -279,6 +310,7 @@ where
             let disable_default_namespace = self.disable_default_namespace;
             let disable_namespaces = self.disable_namespaces;
             let max_response_size = self.max_response_size;
+            let heartbeat_interval = self.hrana_ws_heartbeat_interval;
             async move {
                 hrana::ws::serve(
                     user_auth_strategy,
@@ -289,6 +321,7 @@ where
                     namespaces,
                     disable_default_namespace,
                     disable_namespaces,
+                    heartbeat_interval,
                 )
                 .await
                 .context("Hrana server failed")
@@ -353,7 +386,9 @@ where
                 .route("/console", get(show_console))
                 .route("/health", get(handle_health))
                 .route("/dump", get(dump::handle_dump))
+                .route("/serialize", get(serialize::handle_serialize))
                 .route("/beta/listen", get(listen::handle_listen))
+                .route("/sync/frames", get(frames::handle_frames))
                 .route("/v1", get(hrana_over_http_1::handle_index))
                 .route("/v1/execute", post(hrana_over_http_1::handle_execute))
                 .route("/v1/batch", post(hrana_over_http_1::handle_batch))
@@ -435,12 +470,7 @@ where
                     // TODO: remove this when we upgrade tower-http to 0.5.3
                     DefaultPredicate::new().and(NotForContentType::new("text/event-stream")),
                 ))
-                .layer(
-                    cors::CorsLayer::new()
-                        .allow_methods(cors::AllowMethods::any())
-                        .allow_headers(cors::Any)
-                        .allow_origin(cors::Any),
-                );
+                .layer(make_cors_layer(self.cors_origins.as_deref()));
 
             let router = router.fallback(handle_fallback);
             let h2c = crate::h2c::H2cMaker::new(router);
@@ -0,0 +1,77 @@
+use axum::extract::State as AxumState;
+use bytes::Bytes;
+use hyper::HeaderMap;
+
+use crate::auth::Authenticated;
+use crate::connection::Connection as _;
+use crate::error::Error;
+
+use super::db_factory::namespace_from_headers;
+use super::AppState;
+
+/// Upper bound on the size of a database this endpoint will serialize in one response. Bootstrap
+/// use cases should be sized well under this, and anything larger is better served by `/dump` or
+/// embedded replica sync.
+const MAX_SERIALIZE_SIZE: u64 = 16 * 1024 * 1024;
+
+pub(super) async fn handle_serialize(
+    auth: Authenticated,
+    AxumState(state): AxumState<AppState>,
+    headers: HeaderMap,
+) -> crate::Result<Bytes> {
+    let namespace = namespace_from_headers(
+        &headers,
+        state.disable_default_namespace,
+        state.disable_namespaces,
+    )?;
+
+    if !auth.is_namespace_authorized(&namespace) {
+        return Err(Error::NamespaceDoesntExist(namespace.to_string()));
+    }
+
+    let conn_maker = state
+        .namespaces
+        .with(namespace, |ns| {
+            if !ns.db.is_primary() {
+                return Err(Error::NotAPrimary);
+            }
+
+            Ok::<_, crate::Error>(ns.db.connection_maker())
+        })
+        .await??;
+
+    let conn = conn_maker.create().await.unwrap();
+
+    let bytes = crate::BLOCKING_RT
+        .spawn_blocking(move || conn.with_raw(serialize))
+        .await?;
+    bytes
+}
+
+/// # Safety
+///
+/// `sqlite3_serialize` hands back a buffer it owns; we copy it into a `Bytes` and free it with
+/// `sqlite3_free` before returning, so nothing escapes with a dangling pointer.
+fn serialize(conn: &mut rusqlite::Connection) -> crate::Result<Bytes> {
+    let mut size: rusqlite::ffi::sqlite3_int64 = 0;
+    let data = unsafe {
+        rusqlite::ffi::sqlite3_serialize(conn.handle(), std::ptr::null(), &mut size, 0)
+    };
+
+    if data.is_null() {
+        return Err(Error::Internal("failed to serialize database".into()));
+    }
+
+    let size = size as u64;
+    if size > MAX_SERIALIZE_SIZE {
+        unsafe { rusqlite::ffi::sqlite3_free(data as *mut _) };
+        return Err(Error::SerializedDbTooLarge(size, MAX_SERIALIZE_SIZE));
+    }
+
+    let bytes = Bytes::copy_from_slice(unsafe {
+        std::slice::from_raw_parts(data, size as usize)
+    });
+    unsafe { rusqlite::ffi::sqlite3_free(data as *mut _) };
+
+    Ok(bytes)
+}
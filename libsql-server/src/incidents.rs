@@ -0,0 +1,77 @@
+//! In-memory record of panics caught while running a namespace's connection tasks.
+//!
+//! A panic inside [`crate::BLOCKING_RT`] only ever unwinds the blocking task it ran on - tokio
+//! reports it to the awaiting task as a [`tokio::task::JoinError`] instead of taking down the
+//! process - but left unhandled that `JoinError` still propagates up and fails the request. This
+//! module gives call sites a place to convert that into a namespace-scoped incident instead:
+//! record what happened here, then answer the request with an ordinary error so a crash in one
+//! namespace can't be mistaken for, or interfere with, any other.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock;
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::namespace::NamespaceName;
+
+/// Number of incidents kept per namespace before the oldest ones are dropped.
+const MAX_INCIDENTS_PER_NAMESPACE: usize = 50;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Incident {
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+static INCIDENTS: Lazy<RwLock<HashMap<NamespaceName, VecDeque<Incident>>>> =
+    Lazy::new(Default::default);
+
+/// Records a panic caught from a namespace's connection task.
+pub fn record_panic(namespace: &NamespaceName, payload: &(dyn std::any::Any + Send)) {
+    let message = panic_message(payload);
+    tracing::error!(%namespace, "connection task panicked: {message}");
+
+    let mut incidents = INCIDENTS.write().unwrap();
+    let namespace_incidents = incidents.entry(namespace.clone()).or_default();
+    namespace_incidents.push_back(Incident {
+        message,
+        timestamp: Utc::now(),
+    });
+    while namespace_incidents.len() > MAX_INCIDENTS_PER_NAMESPACE {
+        namespace_incidents.pop_front();
+    }
+}
+
+/// Returns the recorded incidents for `namespace`, oldest first.
+pub fn incidents_for(namespace: &NamespaceName) -> Vec<Incident> {
+    INCIDENTS
+        .read()
+        .unwrap()
+        .get(namespace)
+        .map(|incidents| incidents.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Returns the recorded incidents for every namespace that has had at least one, oldest first.
+/// Used by the `/v1/diagnostics` endpoint to fold recent errors into a single support bundle
+/// without the caller needing to already know which namespaces to ask about.
+pub fn all_incidents() -> HashMap<NamespaceName, Vec<Incident>> {
+    INCIDENTS
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(namespace, incidents)| (namespace.clone(), incidents.iter().cloned().collect()))
+        .collect()
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "connection task panicked with a non-string payload".to_string()
+    }
+}
@@ -0,0 +1,296 @@
+//! Suggests missing indexes for a namespace by running `EXPLAIN QUERY PLAN` for its recently
+//! tracked top/slowest queries (see [`crate::stats::Stats`]) and using the parser to extract
+//! which columns each full-scanned query filters on, then cross-references the schema's existing
+//! indexes to flag ones none of those queries touched. Exposed to operators via the
+//! `/v1/namespaces/:namespace/index-advisor` admin endpoint.
+//!
+//! This is a heuristic, not a query optimizer: it only looks at a query's single driving table
+//! (not joined tables), only at top-level `WHERE` comparisons (not `OR` branches or expressions
+//! inside a function call), and "unused" only means "not touched by anything currently in the
+//! tracked query window", which resets whenever `Stats::reset_top_queries`/
+//! `reset_slowest_queries` does. Treat its output as a starting point for a human to verify, not
+//! something to apply blindly.
+
+use std::collections::{HashMap, HashSet};
+
+use fallible_iterator::FallibleIterator;
+use sqlite3_parser::ast::{Cmd, Expr, FromClause, Operator, OneSelect, Select, SelectTable, Stmt};
+use sqlite3_parser::lexer::sql::Parser;
+
+use crate::stats::Stats;
+
+/// A query tracked by [`crate::stats::Stats`], along with how many rows it read -- used as a
+/// proxy for how much a missing index on it would save.
+pub struct TrackedQuery {
+    pub sql: String,
+    pub rows_read: u64,
+}
+
+/// Snapshots `stats`'s currently tracked top and slowest queries into the flat list [`analyze`]
+/// works from.
+pub(crate) fn tracked_queries(stats: &Stats) -> Vec<TrackedQuery> {
+    let top: Vec<_> = stats
+        .top_queries()
+        .read()
+        .unwrap()
+        .iter()
+        .map(|q| TrackedQuery {
+            sql: q.query.clone(),
+            rows_read: q.rows_read,
+        })
+        .collect();
+    let slowest = stats
+        .slowest_queries()
+        .read()
+        .unwrap()
+        .iter()
+        .map(|q| TrackedQuery {
+            sql: q.query.clone(),
+            rows_read: q.rows_read,
+        });
+    top.into_iter().chain(slowest).collect()
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct IndexSuggestion {
+    pub table: String,
+    pub columns: Vec<String>,
+    /// Sum of `rows_read` across every tracked query that full-scans `table` filtering on
+    /// `columns`, i.e. roughly how many fewer rows a matching index would let SQLite examine.
+    pub estimated_benefit: u64,
+    /// One of the queries this suggestion was derived from, for context.
+    pub sample_query: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct UnusedIndexSuggestion {
+    pub table: String,
+    pub index: String,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct IndexAdvisorReport {
+    pub suggested_indexes: Vec<IndexSuggestion>,
+    pub possibly_unused_indexes: Vec<UnusedIndexSuggestion>,
+}
+
+/// Builds an [`IndexAdvisorReport`] for `queries` against `conn`'s current schema.
+///
+/// Best-effort: a query that fails to parse or to `EXPLAIN` is silently skipped rather than
+/// failing the whole report, since one statement using a feature the vendored parser doesn't
+/// support shouldn't hide suggestions derived from every other query.
+pub fn analyze(
+    conn: &rusqlite::Connection,
+    queries: &[TrackedQuery],
+) -> rusqlite::Result<IndexAdvisorReport> {
+    let mut benefit: HashMap<(String, Vec<String>), (u64, String)> = HashMap::new();
+    let mut used_indexes: HashSet<String> = HashSet::new();
+
+    for query in queries {
+        let Some(step) = explain_scan(conn, &query.sql, &mut used_indexes)? else {
+            continue;
+        };
+        if step.uses_index {
+            continue;
+        }
+        let Some(columns) = where_columns_for_table(&query.sql, &step.table) else {
+            continue;
+        };
+        if columns.is_empty() {
+            continue;
+        }
+
+        let entry = benefit
+            .entry((step.table, columns))
+            .or_insert((0, query.sql.clone()));
+        entry.0 += query.rows_read;
+    }
+
+    let mut suggested_indexes: Vec<IndexSuggestion> = benefit
+        .into_iter()
+        .map(
+            |((table, columns), (estimated_benefit, sample_query))| IndexSuggestion {
+                table,
+                columns,
+                estimated_benefit,
+                sample_query,
+            },
+        )
+        .collect();
+    suggested_indexes.sort_by(|a, b| b.estimated_benefit.cmp(&a.estimated_benefit));
+
+    let possibly_unused_indexes = existing_indexes(conn)?
+        .into_iter()
+        .filter(|(_, index)| !used_indexes.contains(index))
+        .map(|(table, index)| UnusedIndexSuggestion { table, index })
+        .collect();
+
+    Ok(IndexAdvisorReport {
+        suggested_indexes,
+        possibly_unused_indexes,
+    })
+}
+
+/// One step of an `EXPLAIN QUERY PLAN` output that scans or searches a table.
+struct PlanStep {
+    table: String,
+    uses_index: bool,
+}
+
+/// Runs `EXPLAIN QUERY PLAN` for `sql` and returns its driving table, plus whether that table was
+/// reached through an index. Also records any index name mentioned in the plan into
+/// `used_indexes`. Returns `None` if `sql` isn't a statement `EXPLAIN QUERY PLAN` accepts.
+fn explain_scan(
+    conn: &rusqlite::Connection,
+    sql: &str,
+    used_indexes: &mut HashSet<String>,
+) -> rusqlite::Result<Option<PlanStep>> {
+    let mut stmt = match conn.prepare(&format!("EXPLAIN QUERY PLAN {sql}")) {
+        Ok(stmt) => stmt,
+        Err(_) => return Ok(None),
+    };
+    let mut rows = match stmt.query([]) {
+        Ok(rows) => rows,
+        Err(_) => return Ok(None),
+    };
+
+    let mut scan: Option<PlanStep> = None;
+    while let Some(row) = rows.next()? {
+        let detail: String = row.get(3)?;
+        let Some((verb, rest)) = detail.split_once(' ') else {
+            continue;
+        };
+        if verb != "SCAN" && verb != "SEARCH" {
+            continue;
+        }
+        let Some(table) = rest.split_whitespace().next() else {
+            continue;
+        };
+
+        let uses_index = detail.contains("USING INDEX")
+            || detail.contains("USING COVERING INDEX")
+            || detail.contains("PRIMARY KEY");
+        if let Some(index) = index_name_in_plan_detail(&detail) {
+            used_indexes.insert(index);
+        }
+
+        // Only the first (outermost) scan/search step is this query's driving table; later steps
+        // are joins or subqueries this heuristic doesn't chase.
+        scan.get_or_insert(PlanStep {
+            table: table.to_string(),
+            uses_index,
+        });
+    }
+
+    Ok(scan)
+}
+
+fn index_name_in_plan_detail(detail: &str) -> Option<String> {
+    for marker in ["USING INDEX ", "USING COVERING INDEX "] {
+        if let Some(rest) = detail.split(marker).nth(1) {
+            return rest.split_whitespace().next().map(str::to_string);
+        }
+    }
+    None
+}
+
+/// Names of every non-autogenerated index in the schema, paired with the table they're on.
+fn existing_indexes(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT tbl_name, name FROM sqlite_master \
+         WHERE type = 'index' AND name NOT LIKE 'sqlite_autoindex_%'",
+    )?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
+
+/// The columns `sql`'s top-level `WHERE` clause compares against a constant or bind parameter,
+/// if `sql` is a `SELECT` whose single driving table is `table`. `None` if `sql` doesn't parse as
+/// such a statement, or joins another table into the mix -- a WHERE column on a joined table
+/// needs join-aware analysis this heuristic doesn't attempt.
+fn where_columns_for_table(sql: &str, table: &str) -> Option<Vec<String>> {
+    let select = parse_select(sql)?;
+    let OneSelect::Select {
+        from: Some(from),
+        where_clause: Some(where_clause),
+        ..
+    } = &select.body.select
+    else {
+        return None;
+    };
+    if from.joins.is_some() {
+        return None;
+    }
+    if !from_clause_table_name(from)?.eq_ignore_ascii_case(table) {
+        return None;
+    }
+
+    let mut columns = Vec::new();
+    collect_where_columns(where_clause, &mut columns);
+    columns.sort_unstable();
+    columns.dedup();
+    Some(columns)
+}
+
+fn parse_select(sql: &str) -> Option<Select> {
+    match Parser::new(sql.as_bytes()).next().ok().flatten()? {
+        Cmd::Stmt(Stmt::Select(select)) => Some(select),
+        _ => None,
+    }
+}
+
+fn from_clause_table_name(from: &FromClause) -> Option<String> {
+    match from.select.as_deref()? {
+        SelectTable::Table(name, ..) => Some(name.name.0.clone()),
+        _ => None,
+    }
+}
+
+/// Collects every column compared against a constant or bind parameter in `expr`, recursing
+/// through `AND` so a multi-column `WHERE a = ? AND b = ?` suggests a composite index. Stops at
+/// `OR`, since an index on either side alone wouldn't serve the whole predicate.
+fn collect_where_columns(expr: &Expr, out: &mut Vec<String>) {
+    match expr {
+        Expr::Binary(lhs, Operator::And, rhs) => {
+            collect_where_columns(lhs, out);
+            collect_where_columns(rhs, out);
+        }
+        Expr::Binary(lhs, op, rhs) if is_comparison(*op) => {
+            if let Some(column) = column_name(lhs).or_else(|| column_name(rhs)) {
+                out.push(column);
+            }
+        }
+        Expr::InList {
+            lhs, rhs: Some(_), ..
+        }
+        | Expr::Between { lhs, .. } => {
+            if let Some(column) = column_name(lhs) {
+                out.push(column);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_comparison(op: Operator) -> bool {
+    matches!(
+        op,
+        Operator::Equals
+            | Operator::NotEquals
+            | Operator::Greater
+            | Operator::GreaterEquals
+            | Operator::Less
+            | Operator::LessEquals
+            | Operator::Is
+            | Operator::IsNot
+    )
+}
+
+fn column_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Id(id) => Some(id.0.clone()),
+        Expr::Qualified(_, name) => Some(name.0.clone()),
+        Expr::DoublyQualified(_, _, name) => Some(name.0.clone()),
+        _ => None,
+    }
+}
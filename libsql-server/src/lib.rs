@@ -78,7 +78,10 @@ mod error;
 mod h2c;
 mod heartbeat;
 mod hrana;
+mod incidents;
 mod http;
+mod index_advisor;
+mod io_backend;
 mod metrics;
 mod migration;
 mod namespace;
@@ -92,6 +95,7 @@ mod stats;
 #[cfg(test)]
 mod test;
 mod utils;
+mod xa;
 
 const DB_CREATE_TIMEOUT: Duration = Duration::from_secs(1);
 const DEFAULT_AUTO_CHECKPOINT: u32 = 1000;
@@ -290,6 +294,8 @@ where
             enable_console: self.user_api_config.enable_http_console,
             self_url: self.user_api_config.self_url,
             primary_url: self.user_api_config.primary_url,
+            cors_origins: self.user_api_config.cors_origins,
+            hrana_ws_heartbeat_interval: self.user_api_config.hrana_ws_heartbeat_interval,
         };
 
         let user_http_service = user_http.configure(task_manager);
@@ -317,11 +323,16 @@ where
     }
 }
 
-#[tracing::instrument(skip(connection_maker))]
+/// Default bound on concurrent namespace checkpoints when `DbConfig::max_concurrent_checkpoints`
+/// is unset, i.e. effectively unbounded for any realistic deployment.
+const MAX_CONCURRENT_CHECKPOINTS: usize = 10_000;
+
+#[tracing::instrument(skip(connection_maker, checkpoint_semaphore))]
 async fn run_periodic_checkpoint<C>(
     connection_maker: Arc<MakeThrottledConnection<C>>,
     period: Duration,
     namespace_name: NamespaceName,
+    checkpoint_semaphore: Arc<Semaphore>,
 ) -> anyhow::Result<()>
 where
     C: MakeConnection,
@@ -352,6 +363,7 @@ where
                     tracing::warn!("vacuum failed: {}", e);
                 }
                 tracing::info!("database checkpoint starts");
+                let _permit = checkpoint_semaphore.acquire().await;
                 let start = Instant::now();
                 match conn.checkpoint().await {
                     Ok(_) => {
@@ -565,6 +577,7 @@ where
         init_version_file(&self.path)?;
         maybe_migrate(&self.path)?;
         self.init_sqlite_globals();
+        hrana::set_max_request_size(self.db_config.max_request_size);
         let idle_shutdown_kicker = self.setup_shutdown();
 
         let extensions = self.db_config.validate_extensions()?;
@@ -821,9 +834,15 @@ where
         scripted_backup: Option<ScriptBackupManager>,
     ) -> anyhow::Result<(NamespaceConfigurators, MakeReplicationSvc)> {
         let make_wal_manager = Arc::new(|| Sqlite3WalManager::default());
+        let standby = self
+            .rpc_client_config
+            .as_ref()
+            .map(|c| c.standby)
+            .unwrap_or(false);
         let configurators = self.configurators_common(
             base_config,
             client_config,
+            standby,
             make_wal_manager,
             migration_scheduler_handle,
             scripted_backup,
@@ -855,6 +874,7 @@ where
         &self,
         base_config: BaseNamespaceConfig,
         client_config: Option<(Channel, Uri)>,
+        standby: bool,
         make_wal_manager: Arc<dyn Fn() -> InnerWalManager + Sync + Send + 'static>,
         migration_scheduler_handle: SchedulerHandle,
         scripted_backup: Option<ScriptBackupManager>,
@@ -864,7 +884,7 @@ where
             // replica mode
             Some((channel, uri)) => {
                 let replica_configurator =
-                    ReplicaConfigurator::new(base_config, channel, uri, make_wal_manager);
+                    ReplicaConfigurator::new(base_config, channel, uri, standby, make_wal_manager);
                 configurators.with_replica(replica_configurator);
             }
             // primary mode
@@ -888,12 +908,18 @@ where
         migration_scheduler_handle: SchedulerHandle,
         scripted_backup: Option<ScriptBackupManager>,
     ) {
+        let checkpoint_semaphore = Arc::new(Semaphore::new(
+            self.db_config.max_concurrent_checkpoints.unwrap_or(MAX_CONCURRENT_CHECKPOINTS),
+        ));
+
         let primary_config = PrimaryConfig {
             max_log_size: self.db_config.max_log_size,
             max_log_duration: self.db_config.max_log_duration.map(Duration::from_secs_f32),
             bottomless_replication: self.db_config.bottomless_replication.clone(),
             scripted_backup,
             checkpoint_interval: self.db_config.checkpoint_interval,
+            checkpoint_semaphore,
+            log_sync_interval: self.db_config.log_sync_interval,
         };
 
         let primary_configurator = PrimaryConfigurator::new(
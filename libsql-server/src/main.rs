@@ -19,11 +19,13 @@ use libsql_server::config::{
     AdminApiConfig, BottomlessConfig, DbConfig, HeartbeatConfig, MetaStoreConfig, RpcClientConfig,
     RpcServerConfig, TlsConfig, UserApiConfig,
 };
-use libsql_server::net::AddrIncoming;
+use libsql_server::net::{AddrIncoming, NetworkAcl};
 use libsql_server::version::Version;
 use libsql_server::Server;
 use libsql_sys::{Cipher, EncryptionConfig};
 
+mod config_file;
+
 /// SQL daemon
 #[derive(Debug, Parser)]
 #[command(name = "sqld")]
@@ -75,6 +77,40 @@ struct Cli {
     http_self_url: Option<String>,
     #[clap(long, env = "SQLD_HTTP_PRIMARY_URL")]
     http_primary_url: Option<String>,
+    /// Comma-separated list of origins allowed to make cross-origin requests to the HTTP API,
+    /// e.g. "https://example.com,https://app.example.com". If not set, all origins are allowed,
+    /// which is appropriate for server-to-server use but not recommended when the API is called
+    /// directly from a browser.
+    #[clap(long, env = "SQLD_HTTP_CORS_ORIGINS", value_delimiter = ',')]
+    http_cors_origins: Option<Vec<String>>,
+    /// How often, in seconds, the server sends an application-level WebSocket ping on idle Hrana
+    /// connections to detect half-open clients faster than the OS TCP timeout would. Set to 0 to
+    /// disable heartbeats.
+    #[clap(long, env = "SQLD_HRANA_WS_HEARTBEAT_INTERVAL_S", default_value = "30")]
+    hrana_ws_heartbeat_interval_s: u64,
+
+    /// Comma-separated list of IPs/CIDR blocks allowed to connect to the user-facing HTTP and
+    /// hrana listeners. If not set, all addresses not explicitly denied are allowed.
+    #[clap(long, env = "SQLD_HTTP_ALLOW_IPS", value_delimiter = ',')]
+    http_allow_ips: Vec<String>,
+    /// Comma-separated list of IPs/CIDR blocks denied from connecting to the user-facing HTTP
+    /// and hrana listeners. Takes precedence over `--http-allow-ips`.
+    #[clap(long, env = "SQLD_HTTP_DENY_IPS", value_delimiter = ',')]
+    http_deny_ips: Vec<String>,
+
+    /// Comma-separated list of IPs/CIDR blocks allowed to connect to the admin listener.
+    #[clap(long, env = "SQLD_ADMIN_ALLOW_IPS", value_delimiter = ',')]
+    admin_allow_ips: Vec<String>,
+    /// Comma-separated list of IPs/CIDR blocks denied from connecting to the admin listener.
+    /// Takes precedence over `--admin-allow-ips`.
+    #[clap(long, env = "SQLD_ADMIN_DENY_IPS", value_delimiter = ',')]
+    admin_deny_ips: Vec<String>,
+
+    /// Expect every incoming connection on the user-facing listeners to start with a PROXY
+    /// protocol v2 header, so the real client IP is used for rate limiting and audit logging
+    /// instead of the address of the TCP load balancer in front of sqld.
+    #[clap(long, env = "SQLD_PROXY_PROTOCOL")]
+    proxy_protocol: bool,
 
     /// The address and port the inter-node RPC protocol listens to. Example: `0.0.0.0:5001`.
     #[clap(
@@ -114,6 +150,13 @@ struct Cli {
     #[clap(long)]
     primary_grpc_ca_cert_file: Option<PathBuf>,
 
+    /// Run as a warm standby: keep replicating from `--primary-grpc-url` like a regular replica,
+    /// but reject reads and writes until promoted with `POST /v1/namespaces/:namespace/promote`
+    /// on the admin API. Namespaces that have already been promoted (tracked by their fencing
+    /// epoch) stay unblocked across restarts.
+    #[clap(long, env = "SQLD_STANDBY", requires = "primary_grpc_url")]
+    standby: bool,
+
     /// Don't display welcome message
     #[clap(long)]
     no_welcome: bool,
@@ -174,6 +217,10 @@ struct Cli {
     #[clap(long, env = "SQLD_MAX_TOTAL_RESPONSE_SIZE", default_value = "32MB")]
     max_total_response_size: ByteSize,
 
+    /// Set the maximum size for an incoming request body to the HTTP/Hrana APIs. e.g 5KB, 10MB...
+    #[clap(long, env = "SQLD_MAX_REQUEST_SIZE", default_value = "10MB")]
+    max_request_size: ByteSize,
+
     /// Set a command to execute when a snapshot file is generated.
     #[clap(long, env = "SQLD_SNAPSHOT_EXEC")]
     snapshot_exec: Option<String>,
@@ -183,6 +230,19 @@ struct Cli {
     #[clap(long, env = "SQLD_CHECKPOINT_INTERVAL_S")]
     checkpoint_interval_s: Option<u64>,
 
+    /// Maximum number of namespace WAL checkpoints allowed to run at the same time in this
+    /// process. By default, checkpoints are unbounded and each namespace checkpoints fully
+    /// independently of the others.
+    #[clap(long, env = "SQLD_MAX_CONCURRENT_CHECKPOINTS")]
+    max_concurrent_checkpoints: Option<usize>,
+
+    /// Durability latency budget, in milliseconds, for the replication log: commits are synced
+    /// to disk at most once per this interval instead of on every commit, amortizing the sync
+    /// cost across everything that committed during the window. By default, every commit is
+    /// synced immediately.
+    #[clap(long, env = "SQLD_LOG_SYNC_INTERVAL_MS")]
+    log_sync_interval_ms: Option<u64>,
+
     /// By default, all request for which a namespace can't be determined fallback to the default
     /// namespace `default`. This flag disables that.
     #[clap(long)]
@@ -310,10 +370,75 @@ struct Cli {
     #[clap(long, env = "LIBSQL_DISABLE_METRICS")]
     disable_metrics: bool,
 
+    /// Where structured logs are sent. `journald` and `syslog` require sqld to be built with
+    /// the matching cargo feature, and `file` requires `--log-dir` to also be set.
+    #[clap(
+        long,
+        value_enum,
+        default_value_t = LogTarget::Stdout,
+        env = "SQLD_LOG_TARGET"
+    )]
+    log_target: LogTarget,
+
+    /// Directory log files are written to when `--log-target file` is selected. Files are
+    /// rotated daily.
+    #[clap(long, env = "SQLD_LOG_DIR")]
+    log_dir: Option<PathBuf>,
+
+    /// Path to a TOML configuration file. Every top-level key corresponds to one of the flags
+    /// above in snake_case (e.g. `http_listen_addr`, `max_log_size`), and values from the file
+    /// are applied as if passed through the matching `SQLD_*` environment variable: an explicit
+    /// CLI flag or an already-set environment variable always takes precedence over the file.
+    #[clap(long)]
+    config: Option<PathBuf>,
+
+    /// Load and validate `--config`/the environment/CLI flags, print the result, and exit
+    /// without starting the server. Useful in deployment pipelines to catch a bad config before
+    /// it's rolled out.
+    #[clap(long)]
+    validate_config: bool,
+
     #[clap(subcommand)]
     subcommand: Option<UtilsSubcommands>,
 }
 
+/// Finds a `--config <path>`/`--config=<path>` argument without pulling in the full `Cli`
+/// definition, so the file it points to can be loaded and have its settings seeded into the
+/// environment before the real [`Cli::parse`] call (and the `env = "SQLD_*"` attributes on its
+/// fields) runs.
+fn find_config_flag(args: impl Iterator<Item = String>) -> Option<PathBuf> {
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        if let Some(value) = arg.strip_prefix("--config=") {
+            return Some(PathBuf::from(value));
+        }
+        if arg == "--config" {
+            return args.next().map(PathBuf::from);
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum LogTarget {
+    Stdout,
+    Journald,
+    Syslog,
+    File,
+}
+
+impl std::fmt::Display for LogTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            LogTarget::Stdout => "stdout",
+            LogTarget::Journald => "journald",
+            LogTarget::Syslog => "syslog",
+            LogTarget::File => "file",
+        };
+        f.write_str(s)
+    }
+}
+
 #[derive(clap::Subcommand, Debug)]
 enum UtilsSubcommands {
     AdminShell {
@@ -410,6 +535,7 @@ fn make_db_config(config: &Cli) -> anyhow::Result<DbConfig> {
         hard_heap_limit_mb: config.hard_heap_limit_mb,
         max_response_size: config.max_response_size.as_u64(),
         max_total_response_size: config.max_total_response_size.as_u64(),
+        max_request_size: config.max_request_size.as_u64(),
         snapshot_exec: config.snapshot_exec.clone(),
         checkpoint_interval: config.checkpoint_interval_s.map(Duration::from_secs),
         snapshot_at_shutdown: config.snapshot_at_shutdown,
@@ -419,6 +545,8 @@ fn make_db_config(config: &Cli) -> anyhow::Result<DbConfig> {
         connection_creation_timeout: config
             .connection_creation_timeout_sec
             .map(|x| Duration::from_secs(x)),
+        max_concurrent_checkpoints: config.max_concurrent_checkpoints,
+        log_sync_interval: config.log_sync_interval_ms.map(Duration::from_millis),
     })
 }
 
@@ -460,8 +588,12 @@ async fn make_user_auth_strategy(config: &Cli) -> anyhow::Result<Auth> {
 }
 
 async fn make_user_api_config(config: &Cli) -> anyhow::Result<UserApiConfig> {
+    let http_acl = NetworkAcl::parse(&config.http_allow_ips, &config.http_deny_ips)?.map(Arc::new);
+
     let http_acceptor =
-        AddrIncoming::new(tokio::net::TcpListener::bind(config.http_listen_addr).await?);
+        AddrIncoming::new(tokio::net::TcpListener::bind(config.http_listen_addr).await?)
+            .with_acl(http_acl.clone())
+            .with_proxy_protocol(config.proxy_protocol);
     tracing::info!(
         "listening for incoming user HTTP connection on {}",
         config.http_listen_addr
@@ -469,7 +601,9 @@ async fn make_user_api_config(config: &Cli) -> anyhow::Result<UserApiConfig> {
 
     let hrana_ws_acceptor = match config.hrana_listen_addr {
         Some(addr) => {
-            let incoming = AddrIncoming::new(tokio::net::TcpListener::bind(addr).await?);
+            let incoming = AddrIncoming::new(tokio::net::TcpListener::bind(addr).await?)
+                .with_acl(http_acl)
+                .with_proxy_protocol(config.proxy_protocol);
 
             tracing::info!(
                 "listening for incoming user hrana websocket connection on {}",
@@ -490,13 +624,19 @@ async fn make_user_api_config(config: &Cli) -> anyhow::Result<UserApiConfig> {
         self_url: config.http_self_url.clone(),
         primary_url: config.http_primary_url.clone(),
         auth_strategy,
+        cors_origins: config.http_cors_origins.clone(),
+        hrana_ws_heartbeat_interval: (config.hrana_ws_heartbeat_interval_s > 0)
+            .then(|| Duration::from_secs(config.hrana_ws_heartbeat_interval_s)),
     })
 }
 
 async fn make_admin_api_config(config: &Cli) -> anyhow::Result<Option<AdminApiConfig>> {
     match config.admin_listen_addr {
         Some(addr) => {
-            let acceptor = AddrIncoming::new(tokio::net::TcpListener::bind(addr).await?);
+            let admin_acl =
+                NetworkAcl::parse(&config.admin_allow_ips, &config.admin_deny_ips)?.map(Arc::new);
+            let acceptor = AddrIncoming::new(tokio::net::TcpListener::bind(addr).await?)
+                .with_acl(admin_acl);
 
             tracing::info!("listening for incoming adming HTTP connection on {}", addr);
             let connector = hyper_rustls::HttpsConnectorBuilder::new()
@@ -580,6 +720,7 @@ async fn make_rpc_client_config(config: &Cli) -> anyhow::Result<Option<RpcClient
                 remote_url: url.clone(),
                 connector,
                 tls_config,
+                standby: config.standby,
             }))
         }
         None => Ok(None),
@@ -727,10 +868,80 @@ async fn build_server(
     })
 }
 
+/// Builds the fmt/journald/syslog layer selected by `--log-target`, generic over whatever
+/// subscriber it ends up attached to so it composes with the optional `debug-tools` console
+/// layer above it.
+fn build_log_layer<S>(
+    log_target: LogTarget,
+    log_dir: Option<PathBuf>,
+    filter: tracing_subscriber::reload::Layer<EnvFilter, S>,
+) -> Result<Box<dyn Layer<S> + Send + Sync>>
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a> + Send + Sync,
+{
+    Ok(match log_target {
+        LogTarget::Stdout => Box::new(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_filter(filter),
+        ),
+        LogTarget::File => {
+            let log_dir = log_dir.context("--log-dir is required when --log-target=file")?;
+            let file_appender = tracing_appender::rolling::daily(log_dir, "sqld.log");
+            Box::new(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(file_appender)
+                    .with_filter(filter),
+            )
+        }
+        #[cfg(feature = "journald")]
+        LogTarget::Journald => {
+            let layer = tracing_journald::layer()
+                .context("failed to connect to the systemd-journald socket")?;
+            Box::new(layer.with_filter(filter))
+        }
+        #[cfg(not(feature = "journald"))]
+        LogTarget::Journald => {
+            bail!("sqld was not built with journald support: rebuild with `--features journald`")
+        }
+        #[cfg(feature = "syslog")]
+        LogTarget::Syslog => {
+            let syslog_writer = syslog_tracing::Syslog::new(
+                std::ffi::CString::new("sqld").unwrap(),
+                syslog_tracing::Options::LOG_PID,
+                syslog_tracing::Facility::Daemon,
+            )
+            .context("failed to connect to the syslog socket")?;
+            Box::new(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(syslog_writer)
+                    .with_filter(filter),
+            )
+        }
+        #[cfg(not(feature = "syslog"))]
+        LogTarget::Syslog => {
+            bail!("sqld was not built with syslog support: rebuild with `--features syslog`")
+        }
+    })
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    if let Some(config_path) = find_config_flag(std::env::args().skip(1)) {
+        config_file::seed_env_from_file(&config_path)?;
+    }
     let args = Cli::parse();
 
+    if args.validate_config {
+        match &args.config {
+            Some(path) => println!("configuration OK (loaded from {})", path.display()),
+            None => println!("configuration OK"),
+        }
+        return Ok(());
+    }
+
     if std::env::var("RUST_LOG").is_err() {
         std::env::set_var("RUST_LOG", "info");
     }
@@ -751,13 +962,8 @@ async fn main() -> Result<()> {
         Ok(())
     };
 
-    registry
-        .with(
-            tracing_subscriber::fmt::layer()
-                .with_ansi(false)
-                .with_filter(filter),
-        )
-        .init();
+    let log_layer = build_log_layer(args.log_target, args.log_dir.clone(), filter)?;
+    registry.with(log_layer).init();
 
     if let Some(ref subcommand) = args.subcommand {
         match subcommand {
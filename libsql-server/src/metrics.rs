@@ -57,6 +57,14 @@ pub static WRITE_TXN_DURATION: Lazy<Histogram> = Lazy::new(|| {
     describe_histogram!(NAME, "duration for which a write transaction was kept open");
     register_histogram!(NAME)
 });
+pub static WRITE_TXN_QUEUE_WAIT_TIME: Lazy<Histogram> = Lazy::new(|| {
+    const NAME: &str = "libsql_server_write_txn_queue_wait_time";
+    describe_histogram!(
+        NAME,
+        "time a connection spent waiting in the write-lock queue before being granted the lock"
+    );
+    register_histogram!(NAME)
+});
 
 pub static STATEMENT_EXECUTION_TIME: Lazy<Histogram> = Lazy::new(|| {
     const NAME: &str = "libsql_server_statement_execution_time";
@@ -109,6 +117,14 @@ pub static REPLICA_LOCAL_PROGRAM_EXEC: Lazy<Counter> = Lazy::new(|| {
     );
     register_counter!(NAME)
 });
+pub static REPLICA_CHECKSUM_MISMATCH: Lazy<Counter> = Lazy::new(|| {
+    const NAME: &str = "libsql_server_replica_checksum_mismatch";
+    describe_counter!(
+        NAME,
+        "number of times a replica detected a broken replication checksum chain and quarantined itself"
+    );
+    register_counter!(NAME)
+});
 pub static DESCRIBE_COUNT: Lazy<Counter> = Lazy::new(|| {
     const NAME: &str = "libsql_server_describe_count";
     describe_counter!(NAME, "number of calls to describe");
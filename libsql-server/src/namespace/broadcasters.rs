@@ -1,14 +1,13 @@
 use std::sync::Arc;
 
-use hashbrown::HashMap;
-use parking_lot::Mutex;
 use tokio_stream::wrappers::BroadcastStream;
 
 use crate::broadcaster::{BroadcastMsg, Broadcaster};
 
+use super::sharded_map::ShardedMap;
 use super::NamespaceName;
 
-type BroadcasterRegistryInner = Mutex<HashMap<NamespaceName, Broadcaster>>;
+type BroadcasterRegistryInner = ShardedMap<NamespaceName, Broadcaster>;
 
 #[derive(Default)]
 pub struct BroadcasterRegistry {
@@ -18,7 +17,7 @@ pub struct BroadcasterRegistry {
 impl BroadcasterRegistry {
     pub(crate) fn handle(&self, namespace: NamespaceName) -> BroadcasterHandle {
         BroadcasterHandle {
-            namespace: namespace,
+            namespace,
             registry: self.inner.clone(),
         }
     }
@@ -28,21 +27,23 @@ impl BroadcasterRegistry {
         namespace: NamespaceName,
         table: String,
     ) -> BroadcastStream<BroadcastMsg> {
-        self.inner
-            .lock()
-            .entry(namespace.clone())
-            .or_insert_with(|| Default::default())
-            .subscribe(table)
+        self.inner.with_shard(&namespace, |shard| {
+            shard
+                .entry(namespace.clone())
+                .or_insert_with(Default::default)
+                .subscribe(table)
+        })
     }
 
     pub(crate) fn unsubscribe(&self, namespace: NamespaceName, table: &String) {
-        let mut broadcasters = self.inner.lock();
-        let remove = broadcasters
-            .get(&namespace)
-            .map_or(false, |broadcaster| !broadcaster.unsubscribe(table));
-        if remove {
-            broadcasters.remove(&namespace);
-        }
+        self.inner.with_shard(&namespace, |shard| {
+            let remove = shard
+                .get(&namespace)
+                .map_or(false, |broadcaster| !broadcaster.unsubscribe(table));
+            if remove {
+                shard.remove(&namespace);
+            }
+        })
     }
 }
 
@@ -54,11 +55,11 @@ pub struct BroadcasterHandle {
 
 impl BroadcasterHandle {
     pub fn get(&self) -> Option<Broadcaster> {
-        self.registry.lock().get(&self.namespace).map(|b| b.clone())
+        self.registry.get_cloned(&self.namespace)
     }
 
     pub fn active(&self) -> bool {
-        self.registry.lock().contains_key(&self.namespace)
+        self.registry.contains_key(&self.namespace)
     }
 
     pub fn handle(&self, namespace: NamespaceName) -> BroadcasterHandle {
@@ -63,6 +63,16 @@ pub(super) async fn fork(
         }
     };
 
+    // When forking at HEAD, checkpoint the source database first so that all of its pages are
+    // folded into the main db file: that lets us try a copy-on-write clone of that file below,
+    // instead of always replaying the whole replication log into a fresh one.
+    if restore_to.is_none() {
+        from_ns
+            .checkpoint()
+            .await
+            .map_err(|e| crate::Error::Fork(ForkError::Internal(e)))?;
+    }
+
     let fork_task = ForkTask {
         base_path,
         to_namespace: to_ns.clone(),
@@ -70,6 +80,7 @@ pub(super) async fn fork(
         restore_to,
         to_config,
         store,
+        source_path: from_ns.path.clone(),
     };
 
     let ns = fork_task.fork().await?;
@@ -117,6 +128,9 @@ pub struct ForkTask {
     pub to_config: MetaStoreHandle,
     pub restore_to: Option<PointInTimeRestore>,
     pub store: NamespaceStore,
+    /// Path to the source namespace's data file, used to attempt a copy-on-write clone instead
+    /// of a full replication log replay when forking at HEAD.
+    pub source_path: Arc<Path>,
 }
 
 pub struct PointInTimeRestore {
@@ -151,7 +165,7 @@ impl ForkTask {
             Self::restore_from_backup(restore, db_path)
                 .await
                 .map_err(ForkError::Internal)?;
-        } else {
+        } else if !self.try_cow_clone(&db_path).await? {
             Self::restore_from_log_file(&self.logger, db_path).await?;
         }
 
@@ -164,6 +178,19 @@ impl ForkTask {
             .map_err(|e| ForkError::CreateNamespace(Box::new(e)))
     }
 
+    /// Tries to clone the source database's data file into `dest` using a copy-on-write
+    /// filesystem reflink, sharing pages with the parent until either side writes to them.
+    /// Returns `Ok(false)` whenever a reflink can't be made (unsupported filesystem, source
+    /// missing, ...), in which case the caller should fall back to replaying the log instead.
+    async fn try_cow_clone(&self, dest: &Path) -> Result<bool> {
+        let source = self.source_path.join("data");
+        let dest = dest.to_path_buf();
+        let cloned = BLOCKING_RT
+            .spawn_blocking(move || reflink_copy::reflink(&source, &dest).is_ok())
+            .await?;
+        Ok(cloned)
+    }
+
     /// Restores the database state from a local log file.
     async fn restore_from_log_file(
         logger: &Arc<ReplicationLogger>,
@@ -23,6 +23,7 @@ use crate::error::LoadDumpError;
 use crate::namespace::broadcasters::BroadcasterHandle;
 use crate::namespace::meta_store::MetaStoreHandle;
 use crate::namespace::replication_wal::{make_replication_wal_wrapper, ReplicationWalWrapper};
+use crate::namespace::statements::StatementsHandle;
 use crate::namespace::{
     NamespaceBottomlessDbId, NamespaceBottomlessDbIdInit, NamespaceName, ResolveNamespacePathFn,
     RestoreOption,
@@ -48,6 +49,7 @@ pub(super) async fn make_primary_connection_maker(
     join_set: &mut JoinSet<anyhow::Result<()>>,
     resolve_attach_path: ResolveNamespacePathFn,
     broadcaster: BroadcasterHandle,
+    statements: StatementsHandle,
     make_wal_manager: Arc<dyn Fn() -> InnerWalManager + Sync + Send + 'static>,
     encryption_config: Option<EncryptionConfig>,
 ) -> crate::Result<(
@@ -111,6 +113,13 @@ pub(super) async fn make_primary_connection_maker(
         DEFAULT_AUTO_CHECKPOINT
     };
 
+    // A namespace can override the server-wide fsync batching window with its own bounded loss
+    // window, for cache/analytics namespaces that would rather bound how much they can lose than
+    // fsync the replication log on every commit.
+    let log_sync_interval = db_config
+        .relaxed_durability_sync_interval
+        .or(primary_config.log_sync_interval);
+
     let logger = Arc::new(ReplicationLogger::open(
         &db_path,
         primary_config.max_log_size,
@@ -120,6 +129,7 @@ pub(super) async fn make_primary_connection_maker(
         primary_config.scripted_backup.clone(),
         name.clone(),
         encryption_config.clone(),
+        log_sync_interval,
     )?);
 
     tracing::debug!("sending stats");
@@ -164,6 +174,7 @@ pub(super) async fn make_primary_connection_maker(
             wal_wrapper.clone(),
             stats.clone(),
             broadcaster,
+            statements,
             meta_store_handle.clone(),
             base_config.extensions.clone(),
             base_config.max_response_size,
@@ -212,6 +223,10 @@ pub(super) async fn make_primary_connection_maker(
 
     join_set.spawn(run_periodic_compactions(logger.clone()));
 
+    if let Some(log_sync_interval) = log_sync_interval {
+        join_set.spawn(run_periodic_log_sync(logger.clone(), log_sync_interval));
+    }
+
     tracing::debug!("Done making primary connection");
 
     Ok((connection_maker, wal_wrapper, stats))
@@ -272,6 +287,28 @@ async fn init_bottomless_replicator(
     Ok((replicator, did_recover))
 }
 
+/// Ensures batched commits are flushed to disk even during a lull in writes, so the durability
+/// latency budget configured for the log (`log_sync_interval`) is an upper bound, not just a
+/// best-effort amortization while traffic is steady.
+async fn run_periodic_log_sync(
+    logger: Arc<ReplicationLogger>,
+    sync_interval: Duration,
+) -> anyhow::Result<()> {
+    let mut interval = tokio::time::interval(sync_interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        let handle = BLOCKING_RT.spawn_blocking(enclose! {(logger) move || {
+            logger.force_sync()
+        }});
+        handle
+            .await
+            .expect("log sync task crashed")
+            .context("replication log sync failed")?;
+    }
+}
+
 async fn run_periodic_compactions(logger: Arc<ReplicationLogger>) -> anyhow::Result<()> {
     // calling `ReplicationLogger::maybe_compact()` is cheap if the compaction does not actually
     // take place, so we can afford to poll it very often for simplicity
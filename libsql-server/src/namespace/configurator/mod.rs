@@ -14,6 +14,7 @@ use crate::StatsSender;
 
 use super::broadcasters::BroadcasterHandle;
 use super::meta_store::MetaStoreHandle;
+use super::statements::StatementsHandle;
 use super::{
     Namespace, NamespaceBottomlessDbIdInit, NamespaceName, NamespaceStore, ResetCb,
     ResolveNamespacePathFn, RestoreOption,
@@ -32,6 +33,9 @@ pub use schema::SchemaConfigurator;
 #[derive(Clone, Debug)]
 pub struct BaseNamespaceConfig {
     pub(crate) base_path: Arc<Path>,
+    /// The server-wide, checksum-verified candidate set built by `DbConfig::validate_extensions`.
+    /// Which of these a given namespace actually loads is opted into per-namespace via
+    /// `DatabaseConfig::extensions`.
     pub(crate) extensions: Arc<[PathBuf]>,
     pub(crate) stats_sender: StatsSender,
     pub(crate) max_response_size: u64,
@@ -50,6 +54,11 @@ pub struct PrimaryConfig {
     pub(crate) bottomless_replication: Option<bottomless::replicator::Options>,
     pub(crate) scripted_backup: Option<ScriptBackupManager>,
     pub(crate) checkpoint_interval: Option<Duration>,
+    /// Shared across every namespace in the process, so that at most
+    /// `DbConfig::max_concurrent_checkpoints` namespace checkpoints run concurrently.
+    pub(crate) checkpoint_semaphore: Arc<Semaphore>,
+    /// See `DbConfig::log_sync_interval`.
+    pub(crate) log_sync_interval: Option<Duration>,
 }
 
 pub type DynConfigurator = dyn ConfigureNamespace + Send + Sync + 'static;
@@ -119,6 +128,7 @@ pub trait ConfigureNamespace {
         resolve_attach_path: ResolveNamespacePathFn,
         store: NamespaceStore,
         broadcaster: BroadcasterHandle,
+        statements: StatementsHandle,
     ) -> Pin<Box<dyn Future<Output = crate::Result<Namespace>> + Send + 'a>>;
 
     fn cleanup<'a>(
@@ -138,4 +148,19 @@ pub trait ConfigureNamespace {
         timestamp: Option<NaiveDateTime>,
         store: NamespaceStore,
     ) -> Pin<Box<dyn Future<Output = crate::Result<Namespace>> + Send + 'a>>;
+
+    /// Move the on-disk data for `namespace` so it lives under `to` instead. The meta store
+    /// update and in-memory cache eviction are handled by the caller; this only knows how to
+    /// relocate whatever this configurator keeps on disk. Namespace kinds that don't support
+    /// renaming (currently replicas) return an error.
+    fn rename<'a>(
+        &'a self,
+        namespace: &'a NamespaceName,
+        to: NamespaceName,
+    ) -> Pin<Box<dyn Future<Output = crate::Result<()>> + Send + 'a>> {
+        let _ = to;
+        Box::pin(std::future::ready(Err(crate::Error::NamespaceRenameError(
+            format!("namespace `{namespace}` cannot be renamed: renaming is only supported on the primary"),
+        ))))
+    }
 }
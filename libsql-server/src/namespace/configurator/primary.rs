@@ -14,6 +14,7 @@ use crate::database::{Database, PrimaryDatabase};
 use crate::namespace::broadcasters::BroadcasterHandle;
 use crate::namespace::configurator::helpers::make_primary_connection_maker;
 use crate::namespace::meta_store::MetaStoreHandle;
+use crate::namespace::statements::StatementsHandle;
 use crate::namespace::{
     Namespace, NamespaceBottomlessDbIdInit, NamespaceName, NamespaceStore, ResetCb,
     ResolveNamespacePathFn, RestoreOption,
@@ -52,6 +53,7 @@ impl PrimaryConfigurator {
         resolve_attach_path: ResolveNamespacePathFn,
         db_path: Arc<Path>,
         broadcaster: BroadcasterHandle,
+        statements: StatementsHandle,
         encryption_config: Option<EncryptionConfig>,
     ) -> crate::Result<Namespace> {
         let mut join_set = JoinSet::new();
@@ -70,6 +72,7 @@ impl PrimaryConfigurator {
             &mut join_set,
             resolve_attach_path,
             broadcaster,
+            statements,
             self.make_wal_manager.clone(),
             encryption_config,
         )
@@ -96,6 +99,7 @@ impl PrimaryConfigurator {
                 connection_maker.clone(),
                 checkpoint_interval,
                 namespace.clone(),
+                self.primary_config.checkpoint_semaphore.clone(),
             ));
         }
 
@@ -126,6 +130,7 @@ impl ConfigureNamespace for PrimaryConfigurator {
         resolve_attach_path: ResolveNamespacePathFn,
         _store: NamespaceStore,
         broadcaster: BroadcasterHandle,
+        statements: StatementsHandle,
     ) -> Pin<Box<dyn Future<Output = crate::Result<Namespace>> + Send + 'a>> {
         Box::pin(async move {
             let db_path: Arc<Path> = self.base.base_path.join("dbs").join(name.as_str()).into();
@@ -139,6 +144,7 @@ impl ConfigureNamespace for PrimaryConfigurator {
                     resolve_attach_path,
                     db_path.clone(),
                     broadcaster,
+                    statements,
                     self.base.encryption_config.clone(),
                 )
                 .await
@@ -198,4 +204,23 @@ impl ConfigureNamespace for PrimaryConfigurator {
             self.base.base_path.clone(),
         ))
     }
+
+    fn rename<'a>(
+        &'a self,
+        namespace: &'a NamespaceName,
+        to: NamespaceName,
+    ) -> Pin<Box<dyn Future<Output = crate::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            let from_path = self.base.base_path.join("dbs").join(namespace.as_str());
+            let to_path = self.base.base_path.join("dbs").join(to.as_str());
+            if from_path.try_exists()? {
+                if let Some(parent) = to_path.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                tokio::fs::rename(&from_path, &to_path).await?;
+            }
+
+            Ok(())
+        })
+    }
 }
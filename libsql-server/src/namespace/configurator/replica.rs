@@ -19,6 +19,7 @@ use crate::database::{Database, ReplicaDatabase};
 use crate::namespace::broadcasters::BroadcasterHandle;
 use crate::namespace::configurator::helpers::{make_stats, run_storage_monitor};
 use crate::namespace::meta_store::MetaStoreHandle;
+use crate::namespace::statements::StatementsHandle;
 use crate::namespace::{Namespace, NamespaceBottomlessDbIdInit, RestoreOption};
 use crate::namespace::{NamespaceName, NamespaceStore, ResetCb, ResetOp, ResolveNamespacePathFn};
 use crate::replication::replicator_client::WalImpl;
@@ -30,6 +31,9 @@ pub struct ReplicaConfigurator {
     base: BaseNamespaceConfig,
     channel: Channel,
     uri: Uri,
+    /// Run as a warm standby: keep replicating, but block reads/writes on namespaces that
+    /// haven't been promoted yet (`epoch == 0`). See [`Self::setup`].
+    standby: bool,
     make_wal_manager: Arc<dyn Fn() -> InnerWalManager + Sync + Send + 'static>,
 }
 
@@ -38,12 +42,14 @@ impl ReplicaConfigurator {
         base: BaseNamespaceConfig,
         channel: Channel,
         uri: Uri,
+        standby: bool,
         make_wal_manager: Arc<dyn Fn() -> InnerWalManager + Sync + Send + 'static>,
     ) -> Self {
         Self {
             base,
             channel,
             uri,
+            standby,
             make_wal_manager,
         }
     }
@@ -60,6 +66,7 @@ impl ConfigureNamespace for ReplicaConfigurator {
         resolve_attach_path: ResolveNamespacePathFn,
         store: NamespaceStore,
         broadcaster: BroadcasterHandle,
+        statements: StatementsHandle,
     ) -> Pin<Box<dyn Future<Output = crate::Result<Namespace>> + Send + 'a>> {
         Box::pin(async move {
             tracing::debug!("creating replica namespace");
@@ -104,6 +111,7 @@ impl ConfigureNamespace for ReplicaConfigurator {
                             resolve_attach_path,
                             store,
                             broadcaster,
+                            statements,
                         )
                         .await;
                 }
@@ -113,6 +121,18 @@ impl ConfigureNamespace for ReplicaConfigurator {
 
             tracing::debug!("done performing handshake");
 
+            // A standby keeps applying frames like a regular replica, but refuses to serve
+            // traffic until it's explicitly promoted. We only gate namespaces that have never
+            // been promoted (epoch == 0), so a restart doesn't re-block a namespace that was
+            // already promoted in a previous run.
+            if self.standby && meta_store_handle.get().epoch == 0 {
+                let mut config = (*meta_store_handle.get()).clone();
+                config.block_reads = true;
+                config.block_writes = true;
+                config.block_reason = Some("standby: awaiting promotion".into());
+                meta_store_handle.store(config).await?;
+            }
+
             let primary_current_replicatio_index =
                 replicator.client_mut().primary_replication_index;
 
@@ -135,6 +155,12 @@ impl ConfigureNamespace for ReplicaConfigurator {
                             // (reset)(ResetOp::Destroy(namespace.clone()));
                             // Err(err)?;
                         }
+                        e @ Error::Injector(libsql_replication::injector::Error::ChecksumMismatch { .. }) => {
+                            tracing::error!("replication checksum chain diverged from the primary, quarantining and reseting replica: {e}");
+                            crate::metrics::REPLICA_CHECKSUM_MISMATCH.increment(1);
+                            (reset)(ResetOp::Reset(namespace.clone()));
+                            Err(e)?;
+                        },
                         e @ Error::Injector(_) => {
                             tracing::error!("potential corruption detected while replicating, reseting  replica: {e}");
                             (reset)(ResetOp::Reset(namespace.clone()));
@@ -210,6 +236,7 @@ impl ConfigureNamespace for ReplicaConfigurator {
                 PassthroughWalWrapper,
                 stats.clone(),
                 broadcaster,
+                statements,
                 meta_store_handle.clone(),
                 self.base.extensions.clone(),
                 self.base.max_response_size,
@@ -8,6 +8,7 @@ use crate::connection::connection_manager::InnerWalManager;
 use crate::database::{Database, SchemaDatabase};
 use crate::namespace::broadcasters::BroadcasterHandle;
 use crate::namespace::meta_store::MetaStoreHandle;
+use crate::namespace::statements::StatementsHandle;
 use crate::namespace::{
     Namespace, NamespaceName, NamespaceStore, ResetCb, ResolveNamespacePathFn, RestoreOption,
 };
@@ -49,6 +50,7 @@ impl ConfigureNamespace for SchemaConfigurator {
         resolve_attach_path: ResolveNamespacePathFn,
         _store: NamespaceStore,
         broadcaster: BroadcasterHandle,
+        statements: StatementsHandle,
     ) -> std::pin::Pin<Box<dyn Future<Output = crate::Result<Namespace>> + Send + 'a>> {
         Box::pin(async move {
             let mut join_set = JoinSet::new();
@@ -67,6 +69,7 @@ impl ConfigureNamespace for SchemaConfigurator {
                 &mut join_set,
                 resolve_attach_path,
                 broadcaster,
+                statements.clone(),
                 self.make_wal_manager.clone(),
                 self.base.encryption_config.clone(),
             )
@@ -84,6 +87,7 @@ impl ConfigureNamespace for SchemaConfigurator {
                         .logger()
                         .new_frame_notifier
                         .subscribe(),
+                    statements,
                 )),
                 name: name.clone(),
                 tasks: join_set,
@@ -0,0 +1,35 @@
+use super::sharded_map::ShardedMap;
+use super::NamespaceName;
+
+/// Where a namespace deletion stands. The tombstone ([`DeletionStatus::InProgress`]) is recorded
+/// as soon as [`super::store::NamespaceStore::destroy`] has cut the namespace off from new
+/// traffic, before the (potentially slow) on-disk and bottomless cleanup has run.
+#[derive(Debug, Clone)]
+pub enum DeletionStatus {
+    InProgress,
+    Completed,
+    Failed { error: String },
+}
+
+/// Tracks the status of namespace deletions that are cleaning up in the background.
+///
+/// Entries are kept around after completion so that a caller who missed the window can still find
+/// out how a deletion went. They aren't pruned, so a namespace that's repeatedly created and
+/// deleted only ever occupies one entry (each new deletion overwrites the last), but this isn't
+/// meant as a long-term audit log.
+#[derive(Default)]
+pub(crate) struct DeletionRegistry {
+    inner: ShardedMap<NamespaceName, DeletionStatus>,
+}
+
+impl DeletionRegistry {
+    pub(crate) fn set(&self, namespace: NamespaceName, status: DeletionStatus) {
+        self.inner.with_shard(&namespace, |shard| {
+            shard.insert(namespace.clone(), status);
+        });
+    }
+
+    pub(crate) fn get(&self, namespace: &NamespaceName) -> Option<DeletionStatus> {
+        self.inner.get_cloned(namespace)
+    }
+}
@@ -14,6 +14,7 @@ use libsql_sys::wal::{
 };
 use parking_lot::Mutex;
 use prost::Message;
+use rusqlite::OptionalExtension;
 use tokio::sync::oneshot;
 use tokio::sync::{
     mpsc,
@@ -75,6 +76,11 @@ struct MetaStoreInner {
     conn: tokio::sync::Mutex<MetaStoreConnection>,
     wal_manager: MetaStoreWalManager,
     db_kind: DatabaseKind,
+    /// Namespaces that have a persisted config, populated cheaply (namespace name only, no
+    /// config decoding) at startup. `configs` is filled lazily from this index the first time
+    /// each namespace's handle is requested, so startup cost no longer grows with the number of
+    /// namespaces on disk.
+    known_namespaces: Mutex<std::collections::HashSet<NamespaceName>>,
 }
 
 fn setup_connection(conn: &rusqlite::Connection) -> Result<()> {
@@ -119,6 +125,8 @@ pub async fn metastore_connection_maker(
                 use_compression: CompressionKind::None,
                 encryption_config: None,
                 aws_endpoint: Some(config.bucket_endpoint),
+                aws_endpoint_secondary: None,
+                health_check_interval: std::time::Duration::from_secs(10),
                 access_key_id: Some(config.access_key_id),
                 secret_access_key: Some(config.secret_access_key),
                 session_token: config.session_token,
@@ -127,6 +135,7 @@ pub async fn metastore_connection_maker(
                 bucket_name: config.bucket_name,
                 max_frames_per_batch: 10_000,
                 max_batch_interval: config.backup_interval,
+                rpo_target: None,
                 s3_max_parallelism: 32,
                 s3_max_retries: 10,
                 skip_snapshot: false,
@@ -193,6 +202,7 @@ impl MetaStoreInner {
             conn: conn.into(),
             wal_manager,
             db_kind,
+            known_namespaces: Default::default(),
         };
 
         if config.allow_recover_from_fs {
@@ -246,49 +256,26 @@ impl MetaStoreInner {
         Ok(())
     }
 
+    /// Indexes which namespaces have a persisted config, without decoding any of them. Actual
+    /// configs are loaded into `configs` lazily, on the first call to [`MetaStore::handle`] for
+    /// that namespace, so startup time doesn't scale with the number of namespaces on disk.
     #[tracing::instrument(skip(self))]
     fn restore(&mut self) -> Result<()> {
         tracing::info!("restoring meta store");
 
-        let mut stmt = self
-            .conn
-            .get_mut()
-            .prepare("SELECT namespace, config FROM namespace_configs")?;
+        let mut stmt = self.conn.get_mut().prepare("SELECT namespace FROM namespace_configs")?;
 
-        let rows = stmt.query(())?.mapped(|r| {
-            let ns = r.get::<_, String>(0)?;
-            let config = r.get::<_, Vec<u8>>(1)?;
-
-            Ok((ns, config))
-        });
+        let rows = stmt.query(())?.mapped(|r| r.get::<_, String>(0));
 
+        let known = self.known_namespaces.get_mut();
         for row in rows {
             match row {
-                Ok((k, v)) => {
-                    let ns = match NamespaceName::from_string(k) {
-                        Ok(ns) => ns,
-                        Err(e) => {
-                            tracing::warn!("unable to convert namespace name: {}", e);
-                            continue;
-                        }
-                    };
-
-                    let config = match metadata::DatabaseConfig::decode(&v[..]) {
-                        Ok(c) => Arc::new(DatabaseConfig::from(&c)),
-                        Err(e) => {
-                            tracing::warn!("unable to convert config: {}", e);
-                            continue;
-                        }
-                    };
-
-                    // We don't store the version in the sqlitedb due to the session token
-                    // changed each time we start the primary, this will cause the replica to
-                    // handshake again and get the latest config.
-                    let (tx, _) = watch::channel(InnerConfig { version: 0, config });
-
-                    self.configs.get_mut().insert(ns, tx);
-                }
-
+                Ok(k) => match NamespaceName::from_string(k) {
+                    Ok(ns) => {
+                        known.insert(ns);
+                    }
+                    Err(e) => tracing::warn!("unable to convert namespace name: {}", e),
+                },
                 Err(e) => {
                     tracing::error!("meta store restore failed: {}", e);
 
@@ -297,10 +284,37 @@ impl MetaStoreInner {
             }
         }
 
-        tracing::info!("meta store restore completed");
+        tracing::info!("meta store restore completed, {} known namespaces", known.len());
 
         Ok(())
     }
+
+    /// Loads a single namespace's persisted config from the database, decoding it lazily.
+    fn load_config(&self, namespace: &NamespaceName) -> Result<Option<Arc<DatabaseConfig>>> {
+        let conn = self.conn.blocking_lock();
+        Self::load_config_with_conn(&conn, namespace)
+    }
+
+    /// Same as [`Self::load_config`], but for callers that already hold the connection lock.
+    fn load_config_with_conn(
+        conn: &MetaStoreConnection,
+        namespace: &NamespaceName,
+    ) -> Result<Option<Arc<DatabaseConfig>>> {
+        let mut stmt =
+            conn.prepare_cached("SELECT config FROM namespace_configs WHERE namespace = ?")?;
+        let config = stmt
+            .query_row([namespace.as_str()], |r| r.get::<_, Vec<u8>>(0))
+            .optional()?;
+
+        match config {
+            Some(bytes) => {
+                let config = metadata::DatabaseConfig::decode(&bytes[..])
+                    .map_err(|e| Error::from(anyhow::anyhow!("unable to decode config: {e}")))?;
+                Ok(Some(Arc::new(DatabaseConfig::from(&config))))
+            }
+            None => Ok(None),
+        }
+    }
 }
 
 /// Handles config change updates by inserting them into the database and in-memory
@@ -491,12 +505,34 @@ impl MetaStore {
         let change_tx = self.changes_tx.clone();
 
         let mut configs = self.inner.configs.lock().await;
-        let sender = configs.entry(namespace.clone()).or_insert_with(|| {
-            // TODO(lucio): if no entry exists we need to ensure we send the update to
-            // the bg channel.
-            let (tx, _) = watch::channel(InnerConfig::default());
-            tx
-        });
+        let sender = match configs.entry(namespace.clone()) {
+            std::collections::hash_map::Entry::Occupied(e) => e.into_mut(),
+            std::collections::hash_map::Entry::Vacant(e) => {
+                // Not cached yet: if we know this namespace has a persisted config, load it from
+                // the metastore db now instead of eagerly at startup. Otherwise this is a
+                // brand-new namespace, which defaults until its config is stored.
+                let known = self.inner.known_namespaces.lock().contains(&namespace);
+                let config = if known {
+                    let inner = self.inner.clone();
+                    let namespace = namespace.clone();
+                    tokio::task::spawn_blocking(move || inner.load_config(&namespace))
+                        .await
+                        .unwrap()
+                        .ok()
+                        .flatten()
+                } else {
+                    None
+                };
+
+                // TODO(lucio): if no entry exists we need to ensure we send the update to
+                // the bg channel.
+                let (tx, _) = watch::channel(match config {
+                    Some(config) => InnerConfig { version: 0, config },
+                    None => InnerConfig::default(),
+                });
+                e.insert(tx)
+            }
+        };
 
         let rx = sender.subscribe();
 
@@ -520,16 +556,23 @@ impl MetaStore {
         let mut conn = self.inner.conn.blocking_lock();
 
         let mut configs = self.inner.configs.blocking_lock();
-        let r = if let Some(sender) = configs.get(&namespace) {
+        // The config may not be cached yet if it was never loaded by a `handle()` call in this
+        // process; fall back to reading it straight from the db in that case.
+        let cached = configs.get(&namespace).map(|sender| sender.borrow().config.clone());
+        let config = match cached {
+            Some(config) => Some(config),
+            None => MetaStoreInner::load_config_with_conn(&conn, &namespace)?,
+        };
+
+        let r = if let Some(config) = config {
             tracing::debug!("removed namespace `{}` from meta store", namespace);
-            let config = sender.borrow().clone();
             let tx = conn.transaction()?;
-            if config.config.is_shared_schema {
+            if config.is_shared_schema {
                 if crate::schema::db::schema_has_linked_dbs(&tx, &namespace)? {
                     return Err(crate::Error::HasLinkedDbs(namespace.clone()));
                 }
             }
-            if let Some(ref shared_schema) = config.config.shared_schema_name {
+            if let Some(ref shared_schema) = config.shared_schema_name {
                 if crate::schema::db::has_pending_migration_jobs(&tx, shared_schema)? {
                     return Err(crate::Error::PendingMigrationOnSchema(
                         shared_schema.clone(),
@@ -546,12 +589,13 @@ impl MetaStore {
                 [namespace.as_str()],
             )?;
             tx.commit()?;
-            Ok(Some(config.config))
+            Ok(Some(config))
         } else {
             tracing::trace!("namespace `{}` not found in meta store", namespace);
             Ok(None)
         };
         configs.remove(&namespace);
+        self.inner.known_namespaces.lock().remove(&namespace);
         r
     }
 
@@ -560,6 +604,19 @@ impl MetaStore {
     // here to check if a namespace exists. Preferably the former.
     pub async fn exists(&self, namespace: &NamespaceName) -> bool {
         self.inner.configs.lock().await.contains_key(namespace)
+            || self.inner.known_namespaces.lock().contains(namespace)
+    }
+
+    /// Returns every namespace that has a persisted config, in no particular order. Used to
+    /// enumerate namespaces for bulk operations (e.g. exporting a config bundle) without loading
+    /// any of them.
+    pub fn all_namespaces(&self) -> Vec<NamespaceName> {
+        self.inner
+            .known_namespaces
+            .lock()
+            .iter()
+            .cloned()
+            .collect()
     }
 
     pub(crate) async fn shutdown(&self) -> crate::Result<()> {
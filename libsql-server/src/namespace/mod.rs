@@ -20,12 +20,17 @@ pub use self::store::NamespaceStore;
 
 pub mod broadcasters;
 pub(crate) mod configurator;
+mod deletion;
 pub mod meta_store;
 mod name;
 pub mod replication_wal;
 mod schema_lock;
+mod sharded_map;
+pub mod statements;
 mod store;
 
+pub use self::deletion::DeletionStatus;
+
 pub type ResetCb = Box<dyn Fn(ResetOp) + Send + Sync + 'static>;
 pub type ResolveNamespacePathFn =
     Arc<dyn Fn(&NamespaceName) -> crate::Result<Arc<Path>> + Sync + Send + 'static>;
@@ -79,6 +84,12 @@ impl Namespace {
     }
 
     async fn checkpoint(&self) -> anyhow::Result<()> {
+        // make sure every commit up to this point is durable before we let sqlite fold them
+        // into the main db file: otherwise a crash right after checkpointing could lose commits
+        // that were still sitting in the replication log's batching window.
+        if let Some(logger) = self.db.logger() {
+            logger.force_sync()?;
+        }
         let conn = self.db.connection_maker().create().await?;
         conn.vacuum_if_needed().await?;
         conn.checkpoint().await?;
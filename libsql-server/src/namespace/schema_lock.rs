@@ -1,13 +1,11 @@
 use std::sync::Arc;
 
-use hashbrown::{hash_map::Entry, HashMap};
-use parking_lot::Mutex;
-
+use super::sharded_map::ShardedMap;
 use super::NamespaceName;
 
 #[derive(Default)]
 pub struct SchemaLocksRegistry {
-    locks: Arc<Mutex<HashMap<NamespaceName, Arc<tokio::sync::RwLock<()>>>>>,
+    locks: Arc<ShardedMap<NamespaceName, Arc<tokio::sync::RwLock<()>>>>,
 }
 
 #[allow(dead_code)]
@@ -19,31 +17,22 @@ enum SchemaLockKind {
 pub struct SchemaLock {
     schema: NamespaceName,
     _guard: Option<SchemaLockKind>,
-    locks: Arc<Mutex<HashMap<NamespaceName, Arc<tokio::sync::RwLock<()>>>>>,
+    locks: Arc<ShardedMap<NamespaceName, Arc<tokio::sync::RwLock<()>>>>,
 }
 
 impl Drop for SchemaLock {
     fn drop(&mut self) {
-        let mut locks = self.locks.lock();
-        match locks.entry(self.schema.clone()) {
-            Entry::Occupied(entry) => {
-                // there's only two ref left: the maps, and ours
-                if Arc::strong_count(entry.get()) == 2 {
-                    entry.remove();
-                }
-            }
-            Entry::Vacant(_) => unreachable!("lock entry removed while we still hold a lock to it"),
-        }
+        // there's only two ref left once we drop ours: the map's, and the one we're holding
+        self.locks
+            .remove_if(&self.schema, |lock| Arc::strong_count(lock) == 2);
     }
 }
 
 impl SchemaLocksRegistry {
     pub async fn acquire_shared(&self, schema: NamespaceName) -> SchemaLock {
-        let lock = {
-            let mut lock = self.locks.lock();
-            let lock = lock.entry(schema.clone()).or_default();
-            lock.clone()
-        };
+        let lock = self
+            .locks
+            .get_or_insert_with(schema.clone(), Default::default);
         let guard = lock.read_owned().await;
         SchemaLock {
             schema,
@@ -53,11 +42,9 @@ impl SchemaLocksRegistry {
     }
 
     pub async fn acquire_exlusive(&self, schema: NamespaceName) -> SchemaLock {
-        let lock = {
-            let mut lock = self.locks.lock();
-            let lock = lock.entry(schema.clone()).or_default();
-            lock.clone()
-        };
+        let lock = self
+            .locks
+            .get_or_insert_with(schema.clone(), Default::default);
         let guard = lock.write_owned().await;
         SchemaLock {
             schema,
@@ -77,20 +64,20 @@ mod test {
 
         let lock1 = locks.acquire_shared("schema".into()).await;
         let lock2 = locks.acquire_shared("schema".into()).await;
-        assert_eq!(locks.locks.lock().len(), 1);
+        assert_eq!(locks.locks.len(), 1);
 
         drop(lock1);
-        assert_eq!(locks.locks.lock().len(), 1);
+        assert_eq!(locks.locks.len(), 1);
 
         drop(lock2);
-        assert!(locks.locks.lock().is_empty());
+        assert_eq!(locks.locks.len(), 0);
 
         let lock1 = locks.acquire_exlusive("schema1".into()).await;
         let lock2 = locks.acquire_exlusive("schema2".into()).await;
-        assert_eq!(locks.locks.lock().len(), 2);
+        assert_eq!(locks.locks.len(), 2);
         drop(lock1);
         drop(lock2);
 
-        assert!(locks.locks.lock().is_empty());
+        assert_eq!(locks.locks.len(), 0);
     }
 }
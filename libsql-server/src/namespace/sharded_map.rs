@@ -0,0 +1,108 @@
+use std::hash::{Hash, Hasher};
+
+use hashbrown::HashMap;
+use parking_lot::Mutex;
+
+/// A hash map split across a fixed number of independently-locked shards.
+///
+/// Registries keyed by [`super::NamespaceName`] (schema locks, broadcasters, ...) are read and
+/// written from every connection of every namespace. With a single `Mutex<HashMap<..>>`, tens of
+/// thousands of simultaneously hot namespaces end up serialized on that one lock even though they
+/// touch disjoint keys. Routing each key to one of a fixed set of shards by hash keeps the
+/// critical section the same (a plain `HashMap` op) while letting unrelated namespaces make
+/// progress concurrently.
+#[derive(Debug)]
+pub(crate) struct ShardedMap<K, V> {
+    shards: Box<[Mutex<HashMap<K, V>>]>,
+}
+
+const DEFAULT_SHARDS: usize = 32;
+
+impl<K, V> Default for ShardedMap<K, V> {
+    fn default() -> Self {
+        Self::with_shards(DEFAULT_SHARDS)
+    }
+}
+
+impl<K, V> ShardedMap<K, V> {
+    fn with_shards(n: usize) -> Self {
+        Self {
+            shards: std::iter::repeat_with(|| Mutex::new(HashMap::new()))
+                .take(n.max(1))
+                .collect(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> ShardedMap<K, V> {
+    fn shard(&self, key: &K) -> &Mutex<HashMap<K, V>> {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        key.hash(&mut hasher);
+        let idx = (hasher.finish() as usize) % self.shards.len();
+        &self.shards[idx]
+    }
+
+    /// Returns the value for `key`, inserting `default()` first if it isn't present yet.
+    pub(crate) fn get_or_insert_with(&self, key: K, default: impl FnOnce() -> V) -> V
+    where
+        V: Clone,
+    {
+        self.shard(&key)
+            .lock()
+            .entry(key)
+            .or_insert_with(default)
+            .clone()
+    }
+
+    /// Removes `key` if `should_remove` returns `true` for its current value.
+    pub(crate) fn remove_if(&self, key: &K, should_remove: impl FnOnce(&V) -> bool) {
+        let shard = self.shard(key);
+        let mut guard = shard.lock();
+        if guard.get(key).map_or(false, should_remove) {
+            guard.remove(key);
+        }
+    }
+
+    pub(crate) fn get_cloned(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.shard(key).lock().get(key).cloned()
+    }
+
+    pub(crate) fn contains_key(&self, key: &K) -> bool {
+        self.shard(key).lock().contains_key(key)
+    }
+
+    pub(crate) fn remove(&self, key: &K) -> Option<V> {
+        self.shard(key).lock().remove(key)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.shards.iter().map(|s| s.lock().len()).sum()
+    }
+
+    /// Runs `f` against the shard owning `key`, for callers that need a compound
+    /// read-modify-write (entry-or-insert followed by a mutation, conditional removal, ...)
+    /// under a single critical section.
+    pub(crate) fn with_shard<R>(&self, key: &K, f: impl FnOnce(&mut HashMap<K, V>) -> R) -> R {
+        f(&mut *self.shard(key).lock())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sharded_map_routes_and_counts() {
+        let map: ShardedMap<String, u32> = ShardedMap::default();
+        for i in 0..100 {
+            map.get_or_insert_with(format!("ns{i}"), || i);
+        }
+        assert_eq!(map.len(), 100);
+        assert_eq!(map.get_cloned(&"ns42".to_string()), Some(42));
+        assert_eq!(map.remove(&"ns42".to_string()), Some(42));
+        assert_eq!(map.len(), 99);
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use hashbrown::HashMap;
+use parking_lot::Mutex;
+
+use super::sharded_map::ShardedMap;
+use super::NamespaceName;
+
+/// A statement pre-registered via the admin API (see `http::admin::statements`). Tokens carrying
+/// a `stmts` claim (see [`crate::auth::Authorized::statements`]) may only execute a registered
+/// statement whose id is in that claim, and whose SQL matches this one verbatim -- turning sqld
+/// into a safe backend for direct-from-browser access with untrusted, narrowly-scoped tokens.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RegisteredStatement {
+    pub sql: String,
+}
+
+#[derive(Debug, Default)]
+struct NamespaceStatements {
+    by_id: Mutex<HashMap<String, RegisteredStatement>>,
+}
+
+type StatementRegistryInner = ShardedMap<NamespaceName, NamespaceStatements>;
+
+#[derive(Default)]
+pub struct StatementRegistry {
+    inner: Arc<StatementRegistryInner>,
+}
+
+impl StatementRegistry {
+    pub(crate) fn handle(&self, namespace: NamespaceName) -> StatementsHandle {
+        StatementsHandle {
+            namespace,
+            registry: self.inner.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct StatementsHandle {
+    namespace: NamespaceName,
+    registry: Arc<StatementRegistryInner>,
+}
+
+impl StatementsHandle {
+    pub fn register(&self, id: String, sql: String) {
+        self.registry.with_shard(&self.namespace, |shard| {
+            shard
+                .entry(self.namespace.clone())
+                .or_insert_with(Default::default)
+                .by_id
+                .lock()
+                .insert(id, RegisteredStatement { sql });
+        })
+    }
+
+    pub fn remove(&self, id: &str) -> Option<RegisteredStatement> {
+        self.registry.with_shard(&self.namespace, |shard| {
+            shard.get(&self.namespace)?.by_id.lock().remove(id)
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Option<RegisteredStatement> {
+        self.registry.with_shard(&self.namespace, |shard| {
+            shard.get(&self.namespace)?.by_id.lock().get(id).cloned()
+        })
+    }
+
+    pub fn list(&self) -> Vec<(String, RegisteredStatement)> {
+        self.registry.with_shard(&self.namespace, |shard| {
+            shard
+                .get(&self.namespace)
+                .map(|ns| {
+                    ns.by_id
+                        .lock()
+                        .iter()
+                        .map(|(id, stmt)| (id.clone(), stmt.clone()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns whether `sql`, verbatim, is the currently registered statement for `id` in this
+    /// namespace.
+    pub fn is_registered(&self, id: &str, sql: &str) -> bool {
+        self.get(id).is_some_and(|stmt| stmt.sql == sql)
+    }
+}
@@ -13,6 +13,7 @@ use tokio_stream::wrappers::BroadcastStream;
 use crate::auth::Authenticated;
 use crate::broadcaster::BroadcastMsg;
 use crate::connection::config::DatabaseConfig;
+use crate::connection::Connection as _;
 use crate::database::DatabaseKind;
 use crate::error::Error;
 use crate::metrics::NAMESPACE_LOAD_LATENCY;
@@ -21,10 +22,16 @@ use crate::stats::Stats;
 
 use super::broadcasters::{BroadcasterHandle, BroadcasterRegistry};
 use super::configurator::{DynConfigurator, NamespaceConfigurators};
+use super::deletion::{DeletionRegistry, DeletionStatus};
 use super::meta_store::{MetaStore, MetaStoreHandle};
 use super::schema_lock::SchemaLocksRegistry;
+use super::statements::{StatementRegistry, StatementsHandle};
 use super::{Namespace, ResetCb, ResetOp, ResolveNamespacePathFn, RestoreOption};
 
+/// How many times to retry on-disk/bottomless cleanup after a namespace has already been cut off
+/// from traffic, before giving up and recording the deletion as failed.
+const CLEANUP_RETRIES: u32 = 5;
+
 type NamespaceEntry = Arc<RwLock<Option<Namespace>>>;
 
 /// Stores and manage a set of namespaces.
@@ -48,8 +55,10 @@ pub struct NamespaceStoreInner {
     snapshot_at_shutdown: bool,
     schema_locks: SchemaLocksRegistry,
     broadcasters: BroadcasterRegistry,
+    statements: StatementRegistry,
     configurators: NamespaceConfigurators,
     db_kind: DatabaseKind,
+    deletions: DeletionRegistry,
 }
 
 impl NamespaceStore {
@@ -74,9 +83,18 @@ impl NamespaceStore {
                     tracing::info!("namespace `{name}` deallocated");
                     // shutdown namespace
                     if let Some(ns) = ns.write().await.take() {
+                        let db_path = ns.path.clone();
                         if let Err(e) = ns.shutdown(snapshot_at_shutdown).await {
                             tracing::error!("error deallocating `{name}`: {e}")
                         }
+                        // Only push a final snapshot to cold storage when this namespace
+                        // actually went idle (`time_to_idle`), not when it's evicted for other
+                        // reasons (explicit removal, replacement, cache pressure).
+                        if cause == moka::notification::RemovalCause::Expired {
+                            if let Err(e) = crate::replication::snapshot::hibernate_namespace_snapshots(&db_path, &name).await {
+                                tracing::warn!("failed to push cold storage snapshot for idle namespace `{name}`: {e}");
+                            }
+                        }
                     }
                 })
             })
@@ -93,8 +111,10 @@ impl NamespaceStore {
                 snapshot_at_shutdown,
                 schema_locks: Default::default(),
                 broadcasters: Default::default(),
+                statements: Default::default(),
                 configurators,
                 db_kind,
+                deletions: Default::default(),
             }),
         })
     }
@@ -103,11 +123,23 @@ impl NamespaceStore {
         self.inner.metadata.exists(namespace).await
     }
 
+    /// Deletes a namespace.
+    ///
+    /// The namespace is removed from the metadata store and evicted from memory before this
+    /// returns, so it immediately stops accepting new traffic and a concurrent
+    /// [`NamespaceStore::create`] of the same name won't collide with leftover state. The
+    /// (potentially slow) on-disk and bottomless cleanup then runs in the background with a
+    /// tombstone recorded in [`NamespaceStore::deletion_status`], so a caller who doesn't want to
+    /// wait on cleanup can poll that instead of holding the request open.
     pub async fn destroy(&self, namespace: NamespaceName, prune_all: bool) -> crate::Result<()> {
         if self.inner.has_shutdown.load(Ordering::Relaxed) {
             return Err(Error::NamespaceStoreShutdown);
         }
 
+        self.inner
+            .deletions
+            .set(namespace.clone(), DeletionStatus::InProgress);
+
         // destroy on-disk database and backups
         let db_config = tokio::task::spawn_blocking({
             let inner = self.inner.clone();
@@ -132,14 +164,58 @@ impl NamespaceStore {
             }
         }
 
-        self.cleanup(&namespace, &db_config, prune_all, bottomless_db_id_init)
-            .await?;
+        tracing::info!("namespace `{namespace}` cut off from traffic, cleaning up in the background");
+
+        let store = self.clone();
+        tokio::spawn(async move {
+            let mut attempt = 0;
+            loop {
+                match store
+                    .cleanup(
+                        &namespace,
+                        &db_config,
+                        prune_all,
+                        bottomless_db_id_init.clone(),
+                    )
+                    .await
+                {
+                    Ok(()) => {
+                        tracing::info!("destroyed namespace: {namespace}");
+                        store.inner.deletions.set(namespace, DeletionStatus::Completed);
+                        return;
+                    }
+                    Err(e) => {
+                        attempt += 1;
+                        if attempt >= CLEANUP_RETRIES {
+                            tracing::error!(
+                                "giving up cleaning up namespace `{namespace}` after {attempt} attempts: {e}"
+                            );
+                            store.inner.deletions.set(
+                                namespace,
+                                DeletionStatus::Failed { error: e.to_string() },
+                            );
+                            return;
+                        }
 
-        tracing::info!("destroyed namespace: {namespace}");
+                        tracing::warn!(
+                            "cleanup attempt {attempt} for namespace `{namespace}` failed, retrying: {e}"
+                        );
+                        tokio::time::sleep(Duration::from_secs(1 << attempt)).await;
+                    }
+                }
+            }
+        });
 
         Ok(())
     }
 
+    /// Returns the status of a namespace deletion started with [`NamespaceStore::destroy`], or
+    /// `None` if this namespace has never been deleted (or was deleted before this node last
+    /// restarted, since the registry isn't persisted).
+    pub fn deletion_status(&self, namespace: &NamespaceName) -> Option<DeletionStatus> {
+        self.inner.deletions.get(namespace)
+    }
+
     pub async fn checkpoint(&self, namespace: NamespaceName) -> crate::Result<()> {
         let entry = self
             .inner
@@ -299,6 +375,58 @@ impl NamespaceStore {
         Ok(())
     }
 
+    /// Rename `from` to `to`: moves the namespace's on-disk data, updates the meta store, and
+    /// evicts the in-memory namespace, so it's lazily reloaded under its new name on next access.
+    pub async fn rename(&self, from: NamespaceName, to: NamespaceName) -> crate::Result<()> {
+        if self.inner.has_shutdown.load(Ordering::Relaxed) {
+            return Err(Error::NamespaceStoreShutdown);
+        }
+
+        if !self.inner.metadata.exists(&from).await {
+            return Err(Error::NamespaceDoesntExist(from.to_string()));
+        }
+        if self.inner.metadata.exists(&to).await {
+            return Err(Error::NamespaceAlreadyExist(to.to_string()));
+        }
+
+        // shut the namespace down gracefully, so its files are flushed and closed before we move
+        // them; it stays evicted afterwards and will be lazily reloaded under its new name.
+        if let Some(entry) = self.inner.store.remove(&from).await {
+            if let Some(ns) = entry.write().await.take() {
+                ns.shutdown(self.inner.snapshot_at_shutdown).await?;
+            }
+        }
+
+        let mut config = (*self
+            .inner
+            .metadata
+            .remove(from.clone())?
+            .ok_or_else(|| Error::NamespaceDoesntExist(from.to_string()))?)
+        .clone();
+
+        // preserve the bottomless key prefix the namespace was using under its old name, so its
+        // existing backups keep being found under the new name instead of being silently
+        // orphaned.
+        if config.bottomless_db_id.is_none() {
+            config.bottomless_db_id = Some(from.to_string());
+        }
+
+        self.get_configurator(&config).rename(&from, to.clone()).await?;
+
+        let handle = self.inner.metadata.handle(to.clone()).await;
+        handle
+            .store_and_maybe_flush(Some(config.into()), true)
+            .await?;
+
+        // NOTE: a replica that already has `from` loaded locally isn't told about the rename by
+        // the replication stream, since it only carries page data: it will start getting
+        // `NamespaceDoesntExist` from the primary under the old name, and needs to be restarted
+        // to pick up the new one.
+        tracing::info!("renamed namespace `{from}` to `{to}`");
+
+        Ok(())
+    }
+
     pub async fn with_authenticated<Fun, R>(
         &self,
         namespace: NamespaceName,
@@ -378,6 +506,7 @@ impl NamespaceStore {
                 self.resolve_attach_fn(),
                 self.clone(),
                 self.broadcaster(namespace.clone()),
+                self.statements(namespace.clone()),
             )
             .await?;
 
@@ -479,10 +608,21 @@ impl NamespaceStore {
         self.with(namespace, |ns| ns.stats.clone()).await
     }
 
+    pub(crate) async fn queue_stats(
+        &self,
+        namespace: NamespaceName,
+    ) -> crate::Result<Option<crate::connection::connection_manager::QueueStats>> {
+        self.with(namespace, |ns| ns.db.queue_stats()).await
+    }
+
     pub(crate) fn broadcaster(&self, namespace: NamespaceName) -> BroadcasterHandle {
         self.inner.broadcasters.handle(namespace)
     }
 
+    pub(crate) fn statements(&self, namespace: NamespaceName) -> StatementsHandle {
+        self.inner.statements.handle(namespace)
+    }
+
     pub(crate) fn subscribe(
         &self,
         namespace: NamespaceName,
@@ -495,6 +635,19 @@ impl NamespaceStore {
         self.inner.broadcasters.unsubscribe(namespace, table);
     }
 
+    pub(crate) async fn index_advisor_report(
+        &self,
+        namespace: NamespaceName,
+    ) -> crate::Result<crate::index_advisor::IndexAdvisorReport> {
+        let queries = crate::index_advisor::tracked_queries(&self.stats(namespace.clone()).await?);
+        let connection_maker = self.with(namespace, |ns| ns.db.connection_maker()).await?;
+        let conn = connection_maker.create().await?;
+        let report = tokio::task::block_in_place(|| {
+            conn.with_raw(|conn| crate::index_advisor::analyze(conn, &queries))
+        })?;
+        Ok(report)
+    }
+
     pub(crate) async fn config_store(
         &self,
         namespace: NamespaceName,
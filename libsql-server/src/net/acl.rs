@@ -0,0 +1,78 @@
+use std::net::IpAddr;
+
+use ipnet::IpNet;
+
+/// A connection-level network ACL for a single listener: an address is allowed through if it
+/// isn't covered by `deny`, and, when `allow` is non-empty, if it *is* covered by `allow`.
+/// `deny` always takes precedence over `allow`.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkAcl {
+    allow: Vec<IpNet>,
+    deny: Vec<IpNet>,
+}
+
+impl NetworkAcl {
+    pub fn new(allow: Vec<IpNet>, deny: Vec<IpNet>) -> Option<Self> {
+        if allow.is_empty() && deny.is_empty() {
+            None
+        } else {
+            Some(Self { allow, deny })
+        }
+    }
+
+    pub fn parse(allow: &[String], deny: &[String]) -> anyhow::Result<Option<Self>> {
+        let parse_list = |list: &[String]| -> anyhow::Result<Vec<IpNet>> {
+            list.iter()
+                .map(|s| {
+                    s.parse::<IpNet>()
+                        .or_else(|_| s.parse::<IpAddr>().map(IpNet::from))
+                        .map_err(|_| anyhow::anyhow!("`{s}` is not a valid IP address or CIDR block"))
+                })
+                .collect()
+        };
+
+        Ok(Self::new(parse_list(allow)?, parse_list(deny)?))
+    }
+
+    pub fn is_allowed(&self, addr: IpAddr) -> bool {
+        if self.deny.iter().any(|net| net.contains(&addr)) {
+            return false;
+        }
+
+        self.allow.is_empty() || self.allow.iter().any(|net| net.contains(&addr))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn deny_takes_precedence_over_allow() {
+        let acl = NetworkAcl::parse(
+            &["10.0.0.0/8".to_string()],
+            &["10.0.0.1".to_string()],
+        )
+        .unwrap()
+        .unwrap();
+
+        assert!(!acl.is_allowed("10.0.0.1".parse().unwrap()));
+        assert!(acl.is_allowed("10.0.0.2".parse().unwrap()));
+        assert!(!acl.is_allowed("192.168.0.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn empty_allow_list_allows_everything_not_denied() {
+        let acl = NetworkAcl::parse(&[], &["10.0.0.1".to_string()])
+            .unwrap()
+            .unwrap();
+
+        assert!(!acl.is_allowed("10.0.0.1".parse().unwrap()));
+        assert!(acl.is_allowed("8.8.8.8".parse().unwrap()));
+    }
+
+    #[test]
+    fn no_rules_returns_none() {
+        assert!(NetworkAcl::parse(&[], &[]).unwrap().is_none());
+    }
+}
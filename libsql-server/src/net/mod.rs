@@ -0,0 +1,266 @@
+use std::error::Error as StdError;
+use std::io::Error as IoError;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use hyper::client::connect::Connection;
+use hyper::server::accept::Accept as HyperAccept;
+use hyper::Uri;
+use hyper_rustls::acceptor::TlsStream;
+use pin_project_lite::pin_project;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tonic::transport::server::{Connected, TcpConnectInfo};
+use tower::Service;
+
+pub use acl::NetworkAcl;
+
+mod acl;
+mod proxy_protocol;
+
+pub trait Connector:
+    Service<Uri, Response = Self::Conn, Future = Self::Fut, Error = Self::Err>
+    + Send
+    + Sync
+    + 'static
+    + Clone
+{
+    type Conn: Unpin + Send + 'static + AsyncRead + AsyncWrite + Connection;
+    type Fut: Send + 'static + Unpin;
+    type Err: Into<Box<dyn StdError + Send + Sync>> + Send + Sync;
+}
+
+impl<T> Connector for T
+where
+    T: Service<Uri> + Send + Sync + 'static + Clone,
+    T::Response: Unpin + Send + 'static + AsyncRead + AsyncWrite + Connection,
+    T::Future: Send + 'static + Unpin,
+    T::Error: Into<Box<dyn StdError + Send + Sync>> + Send + Sync,
+{
+    type Conn = Self::Response;
+    type Fut = Self::Future;
+    type Err = Self::Error;
+}
+
+pub trait Conn: AsyncRead + AsyncWrite + Unpin + Send + 'static {
+    fn connect_info(&self) -> TcpConnectInfo;
+}
+
+pub trait Accept:
+    HyperAccept<Conn = Self::Connection, Error = IoError> + Unpin + Send + 'static
+{
+    type Connection: Conn;
+}
+
+/// Time allowed for a client to complete the PROXY protocol handshake before its connection is
+/// dropped, so a client that opens a connection and never sends (or never finishes sending) a
+/// header can't tie up a `pending` slot indefinitely.
+const PROXY_PROTOCOL_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct AddrIncoming {
+    listener: tokio::net::TcpListener,
+    acl: Option<Arc<NetworkAcl>>,
+    proxy_protocol: bool,
+    /// Connections accepted while `proxy_protocol` is enabled: the PROXY header has to be read
+    /// off the wire before the connection can be handed to hyper, so those reads are driven here
+    /// instead of blocking the whole listener. `None` means the connection failed its handshake
+    /// (bad header or timeout) and was already logged and dropped: unlike a fatal listener error,
+    /// one client sending garbage must not bring down `Accept` for every other client.
+    pending: FuturesUnordered<BoxFuture<'static, Option<AddrStream>>>,
+}
+
+impl AddrIncoming {
+    pub fn new(listener: tokio::net::TcpListener) -> Self {
+        Self {
+            listener,
+            acl: None,
+            proxy_protocol: false,
+            pending: FuturesUnordered::new(),
+        }
+    }
+
+    /// Rejects connections from addresses not allowed by `acl` before they're ever handed off to
+    /// a protocol handler.
+    pub fn with_acl(mut self, acl: Option<Arc<NetworkAcl>>) -> Self {
+        self.acl = acl;
+        self
+    }
+
+    /// Expects every incoming connection to start with a PROXY protocol v2 header, and uses the
+    /// client address it carries in place of the TCP peer address.
+    pub fn with_proxy_protocol(mut self, enabled: bool) -> Self {
+        self.proxy_protocol = enabled;
+        self
+    }
+}
+
+impl HyperAccept for AddrIncoming {
+    type Conn = AddrStream;
+    type Error = IoError;
+
+    fn poll_accept(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Conn, Self::Error>>> {
+        let this = self.get_mut();
+        loop {
+            if let Poll::Ready(Some(result)) = this.pending.poll_next_unpin(cx) {
+                match result {
+                    Some(stream) => return Poll::Ready(Some(Ok(stream))),
+                    // The handshake for this connection failed or timed out and was already
+                    // logged; drop just this connection and keep accepting others.
+                    None => continue,
+                }
+            }
+
+            match this.listener.poll_accept(cx) {
+                Poll::Ready(Ok((stream, remote_addr))) => {
+                    if let Some(acl) = &this.acl {
+                        if !acl.is_allowed(remote_addr.ip()) {
+                            tracing::warn!(
+                                "rejected connection from {remote_addr}: blocked by network ACL"
+                            );
+                            continue;
+                        }
+                    }
+
+                    // disable naggle algorithm
+                    if let Err(e) = stream.set_nodelay(true) {
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    let local_addr = match stream.local_addr() {
+                        Ok(addr) => addr,
+                        Err(e) => return Poll::Ready(Some(Err(e))),
+                    };
+
+                    if this.proxy_protocol {
+                        this.pending.push(Box::pin(async move {
+                            let mut stream = stream;
+                            let header = match tokio::time::timeout(
+                                PROXY_PROTOCOL_HANDSHAKE_TIMEOUT,
+                                proxy_protocol::read_header(&mut stream),
+                            )
+                            .await
+                            {
+                                Ok(Ok(header)) => header,
+                                Ok(Err(e)) => {
+                                    tracing::warn!(
+                                        "rejected connection from {remote_addr}: invalid PROXY protocol header: {e}"
+                                    );
+                                    return None;
+                                }
+                                Err(_) => {
+                                    tracing::warn!(
+                                        "rejected connection from {remote_addr}: PROXY protocol handshake timed out"
+                                    );
+                                    return None;
+                                }
+                            };
+                            Some(AddrStream {
+                                stream,
+                                local_addr,
+                                remote_addr: header.unwrap_or(remote_addr),
+                            })
+                        }));
+                        continue;
+                    }
+
+                    return Poll::Ready(Some(Ok(AddrStream {
+                        stream,
+                        local_addr,
+                        remote_addr,
+                    })));
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+pin_project! {
+    pub struct AddrStream<S = tokio::net::TcpStream> {
+        #[pin]
+        pub stream: S,
+        pub remote_addr: SocketAddr,
+        pub local_addr: SocketAddr,
+    }
+}
+
+impl Accept for AddrIncoming {
+    type Connection = AddrStream;
+}
+
+impl<T> Conn for AddrStream<T>
+where
+    T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    fn connect_info(&self) -> TcpConnectInfo {
+        TcpConnectInfo {
+            local_addr: Some(self.local_addr),
+            remote_addr: Some(self.remote_addr),
+        }
+    }
+}
+
+impl<C: Conn> Conn for TlsStream<C> {
+    fn connect_info(&self) -> TcpConnectInfo {
+        self.io().unwrap().connect_info()
+    }
+}
+
+impl<S> AsyncRead for AddrStream<S>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        self.project().stream.poll_read(cx, buf)
+    }
+}
+
+impl<S> AsyncWrite for AddrStream<S>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, std::io::Error>> {
+        self.project().stream.poll_write(cx, buf)
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<(), std::io::Error>> {
+        self.project().stream.poll_flush(cx)
+    }
+
+    fn poll_shutdown(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<(), std::io::Error>> {
+        self.project().stream.poll_shutdown(cx)
+    }
+}
+
+impl<S> Connected for AddrStream<S> {
+    type ConnectInfo = TcpConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        TcpConnectInfo {
+            local_addr: Some(self.local_addr),
+            remote_addr: Some(self.remote_addr),
+        }
+    }
+}
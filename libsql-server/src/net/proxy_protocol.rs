@@ -0,0 +1,99 @@
+//! Minimal PROXY protocol v2 (binary) header parsing, used to recover the real client address
+//! when sqld sits behind a TCP load balancer that doesn't preserve the source address.
+//!
+//! See <https://www.haproxy.org/download/1.8/doc/proxy-protocol.txt> for the format.
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+const SIGNATURE: [u8; 12] = [
+    0x0D, 0x0A, 0x0D, 0x0A, 0x00, 0x0D, 0x0A, 0x51, 0x55, 0x49, 0x54, 0x0A,
+];
+
+/// Reads and consumes a PROXY protocol v2 header from the front of `stream`, returning the
+/// original client address it carries. Returns `Ok(None)` for a `LOCAL` header, which carries no
+/// address (e.g. health checks issued by the proxy itself).
+pub async fn read_header<S>(stream: &mut S) -> io::Result<Option<SocketAddr>>
+where
+    S: AsyncRead + Unpin,
+{
+    let mut signature = [0u8; 12];
+    stream.read_exact(&mut signature).await?;
+    if signature != SIGNATURE {
+        return Err(invalid_data("missing PROXY protocol v2 signature"));
+    }
+
+    let mut header = [0u8; 4];
+    stream.read_exact(&mut header).await?;
+    let version = header[0] >> 4;
+    if version != 2 {
+        return Err(invalid_data("unsupported PROXY protocol version"));
+    }
+    let command = header[0] & 0x0F;
+    let family = header[1] >> 4;
+    let len = u16::from_be_bytes([header[2], header[3]]) as usize;
+
+    let mut addresses = vec![0u8; len];
+    stream.read_exact(&mut addresses).await?;
+
+    // a LOCAL connection (command 0) carries no meaningful address: it's a health check or a
+    // connection made by the proxy itself.
+    if command == 0 {
+        return Ok(None);
+    }
+
+    let addr = match family {
+        // AF_INET
+        1 if addresses.len() >= 12 => {
+            let ip = Ipv4Addr::new(addresses[0], addresses[1], addresses[2], addresses[3]);
+            let port = u16::from_be_bytes([addresses[8], addresses[9]]);
+            SocketAddr::new(IpAddr::V4(ip), port)
+        }
+        // AF_INET6
+        2 if addresses.len() >= 36 => {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(&addresses[0..16]);
+            let port = u16::from_be_bytes([addresses[32], addresses[33]]);
+            SocketAddr::new(IpAddr::V6(Ipv6Addr::from(octets)), port)
+        }
+        _ => return Err(invalid_data("unsupported PROXY protocol address family")),
+    };
+
+    Ok(Some(addr))
+}
+
+fn invalid_data(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn header_v4(src: (u8, u8, u8, u8), src_port: u16) -> Vec<u8> {
+        let mut buf = SIGNATURE.to_vec();
+        buf.push(0x21); // version 2, command PROXY
+        buf.push(0x11); // AF_INET, STREAM
+        buf.extend_from_slice(&12u16.to_be_bytes());
+        buf.extend_from_slice(&[src.0, src.1, src.2, src.3]);
+        buf.extend_from_slice(&[127, 0, 0, 1]);
+        buf.extend_from_slice(&src_port.to_be_bytes());
+        buf.extend_from_slice(&8080u16.to_be_bytes());
+        buf
+    }
+
+    #[tokio::test]
+    async fn parses_v4_header() {
+        let mut buf = header_v4((203, 0, 113, 1), 51000).as_slice().to_vec();
+        let addr = read_header(&mut buf.as_slice()).await.unwrap().unwrap();
+        assert_eq!(addr, "203.0.113.1:51000".parse().unwrap());
+    }
+
+    #[tokio::test]
+    async fn rejects_bad_signature() {
+        let mut buf = vec![0u8; 16];
+        assert!(read_header(&mut buf.as_slice()).await.is_err());
+    }
+}
@@ -46,6 +46,34 @@ impl TryFrom<rusqlite::types::ValueRef<'_>> for Value {
     }
 }
 
+/// Serializes a borrowed [`rusqlite::types::ValueRef`] directly into the same wire format that
+/// `Value`'s derived `Serialize` produces, without first copying text/blob bytes into an owned
+/// `Value`. Useful on proxy paths that serialize a row as it streams out of sqlite, where the
+/// intermediate allocation would otherwise happen once per cell.
+pub struct ValueRefSer<'a>(pub ValueRef<'a>);
+
+impl<'a> Serialize for ValueRefSer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::Error;
+
+        match self.0 {
+            ValueRef::Null => serializer.serialize_unit_variant("Value", 0, "Null"),
+            ValueRef::Integer(i) => serializer.serialize_newtype_variant("Value", 1, "Integer", &i),
+            ValueRef::Real(x) => serializer.serialize_newtype_variant("Value", 2, "Real", &x),
+            ValueRef::Text(s) => serializer.serialize_newtype_variant(
+                "Value",
+                3,
+                "Text",
+                std::str::from_utf8(s).map_err(S::Error::custom)?,
+            ),
+            ValueRef::Blob(b) => serializer.serialize_newtype_variant("Value", 4, "Blob", b),
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Query {
     pub stmt: Statement,
@@ -2,11 +2,34 @@ use std::borrow::Cow;
 
 use anyhow::Result;
 use fallible_iterator::FallibleIterator;
-use sqlite3_parser::ast::{Cmd, Expr, Id, PragmaBody, QualifiedName, Stmt};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use sqlite3_parser::ast::{
+    Cmd, Expr, FromClause, Id, InsertBody, OneSelect, PragmaBody, QualifiedName, Select,
+    SelectTable, Stmt, Upsert, UpsertDo,
+};
 use sqlite3_parser::lexer::sql::{Parser, ParserError};
 
 use crate::namespace::NamespaceName;
 
+/// Matches a well-known `/* key=value,key2=value2 */` query tag comment, as set by
+/// `libsql::Connection::set_query_tag` on the client. Captures the comma-separated `key=value`
+/// list inside, without the comment delimiters.
+static QUERY_TAG_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"/\*\s*([A-Za-z0-9_]+=[^*,]+(?:,[A-Za-z0-9_]+=[^*,]+){0,7})\s*\*/").unwrap());
+
+/// Caps how much of an extracted query tag we keep, so a client can't blow up the size of
+/// whatever it gets indexed into (slow-query logs, statement stats) with an oversized comment.
+const MAX_QUERY_TAG_LEN: usize = 128;
+
+/// Extracts a query tag comment from `sql`, if present. When a statement contains more than one
+/// matching comment, the last one wins, since that's where a tag appended by the client driver
+/// would end up relative to any comments already in the user's SQL.
+pub fn extract_query_tag(sql: &str) -> Option<String> {
+    let inner = QUERY_TAG_RE.captures_iter(sql).last()?.get(1)?.as_str();
+    Some(inner.trim().chars().take(MAX_QUERY_TAG_LEN).collect())
+}
+
 /// A group of statements to be executed together.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Statement {
@@ -17,6 +40,12 @@ pub struct Statement {
     pub is_insert: bool,
     // Optional id and alias associated with the statement (used for attach/detach)
     pub attach_info: Option<(String, String)>,
+    /// For an INSERT (including an UPSERT), the columns it may write. `None` for every other
+    /// statement kind.
+    pub written_columns: Option<WrittenColumns>,
+    /// For a `SELECT` with a `WITH` clause, the dependency graph between its CTEs and its main
+    /// query. `None` if the statement isn't a `SELECT`, or has no `WITH` clause.
+    pub cte_graph: Option<CteGraph>,
 }
 
 impl Default for Statement {
@@ -146,65 +175,22 @@ impl StmtKind {
 
     fn pragma_kind(name: &QualifiedName, body: Option<&PragmaBody>) -> Option<Self> {
         let name = name.name.0.as_str();
-        match to_ascii_lower(name).as_ref() {
-            // always ok to be served by primary or replicas - pure readonly pragmas
-            "table_list" | "index_list" | "table_info" | "table_xinfo" | "index_info" | "index_xinfo"
-            | "pragma_list" | "compile_options" | "database_list" | "function_list"
-            | "module_list" => Some(Self::Read),
-            // special case for `encoding` - it's effectively readonly for connections
-            // that already created a database, which is always the case for sqld
-            "encoding" => Some(Self::Read),
-            "schema_version" if body.is_none() => Some(Self::Read),
-            // always ok to be served by primary
-            "defer_foreign_keys" | "foreign_keys" | "foreign_key_list" | "foreign_key_check" | "collation_list"
-            | "data_version" | "freelist_count" | "integrity_check" | "legacy_file_format"
-            | "page_count" | "quick_check" | "stats" | "user_version" => Some(Self::Write),
-            // ok to be served by primary without args
-            "analysis_limit"
-            | "application_id"
-            | "auto_vacuum"
-            | "automatic_index"
-            | "busy_timeout"
-            | "cache_size"
-            | "cache_spill"
-            | "cell_size_check"
-            | "checkpoint_fullfsync"
-            | "fullfsync"
-            | "hard_heap_limit"
-            | "journal_mode"
-            | "journal_size_limit"
-            | "legacy_alter_table"
-            | "locking_mode"
-            | "max_page_count"
-            | "mmap_size"
-            | "page_size"
-            | "query_only"
-            | "read_uncommitted"
-            | "recursive_triggers"
-            | "reverse_unordered_selects"
-            | "secure_delete"
-            | "soft_heap_limit"
-            | "synchronous"
-            | "temp_store"
-            | "threads"
-            | "trusted_schema"
-            | "wal_autocheckpoint" => {
-                match body {
-                    Some(_) => None,
-                    None => Some(Self::Write),
-                }
-            }
-            // changes the state of the connection, and can't be allowed rn:
-            "case_sensitive_like" | "ignore_check_constraints" | "incremental_vacuum"
-                // TODO: check if optimize can be safely performed
-                | "optimize"
-                | "parser_trace"
-                | "shrink_memory"
-                | "wal_checkpoint" => None,
-            _ => {
-                tracing::debug!("Unknown pragma: {name}");
-                None
+        let Some(pragma) = KnownPragma::from_name(to_ascii_lower(name).as_ref()) else {
+            tracing::debug!("Unknown pragma: {name}");
+            return None;
+        };
+        match pragma.access() {
+            PragmaAccess::Read => Some(Self::Read),
+            PragmaAccess::ReadIfNoArgs => match body {
+                Some(_) => None,
+                None => Some(Self::Read),
+            },
+            PragmaAccess::Write => Some(Self::Write),
+            PragmaAccess::WriteIfNoArgs => match body {
+                Some(_) => None,
+                None => Some(Self::Write),
             },
+            PragmaAccess::Disallowed => None,
         }
     }
 
@@ -241,6 +227,372 @@ fn to_ascii_lower(s: &str) -> Cow<str> {
     }
 }
 
+/// A PRAGMA recognized by [`StmtKind::pragma_kind`], modeled as a typed enum instead of matching
+/// on the pragma's (lowercased) name string. Lets the namespace-level statement allowlist reason
+/// about which pragma was issued without re-implementing this name matching itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KnownPragma {
+    TableList,
+    IndexList,
+    TableInfo,
+    TableXinfo,
+    IndexInfo,
+    IndexXinfo,
+    PragmaList,
+    CompileOptions,
+    DatabaseList,
+    FunctionList,
+    ModuleList,
+    Encoding,
+    SchemaVersion,
+    DeferForeignKeys,
+    ForeignKeys,
+    ForeignKeyList,
+    ForeignKeyCheck,
+    CollationList,
+    DataVersion,
+    FreelistCount,
+    IntegrityCheck,
+    LegacyFileFormat,
+    PageCount,
+    QuickCheck,
+    Stats,
+    UserVersion,
+    AnalysisLimit,
+    ApplicationId,
+    AutoVacuum,
+    AutomaticIndex,
+    BusyTimeout,
+    CacheSize,
+    CacheSpill,
+    CellSizeCheck,
+    CheckpointFullfsync,
+    Fullfsync,
+    HardHeapLimit,
+    JournalMode,
+    JournalSizeLimit,
+    LegacyAlterTable,
+    LockingMode,
+    MaxPageCount,
+    MmapSize,
+    PageSize,
+    QueryOnly,
+    ReadUncommitted,
+    RecursiveTriggers,
+    ReverseUnorderedSelects,
+    SecureDelete,
+    SoftHeapLimit,
+    Synchronous,
+    TempStore,
+    Threads,
+    TrustedSchema,
+    WalAutocheckpoint,
+    CaseSensitiveLike,
+    IgnoreCheckConstraints,
+    IncrementalVacuum,
+    Optimize,
+    ParserTrace,
+    ShrinkMemory,
+    WalCheckpoint,
+}
+
+impl KnownPragma {
+    /// Looks up a pragma by its (already-lowercased) SQL name, e.g. `"journal_mode"`.
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "table_list" => Self::TableList,
+            "index_list" => Self::IndexList,
+            "table_info" => Self::TableInfo,
+            "table_xinfo" => Self::TableXinfo,
+            "index_info" => Self::IndexInfo,
+            "index_xinfo" => Self::IndexXinfo,
+            "pragma_list" => Self::PragmaList,
+            "compile_options" => Self::CompileOptions,
+            "database_list" => Self::DatabaseList,
+            "function_list" => Self::FunctionList,
+            "module_list" => Self::ModuleList,
+            "encoding" => Self::Encoding,
+            "schema_version" => Self::SchemaVersion,
+            "defer_foreign_keys" => Self::DeferForeignKeys,
+            "foreign_keys" => Self::ForeignKeys,
+            "foreign_key_list" => Self::ForeignKeyList,
+            "foreign_key_check" => Self::ForeignKeyCheck,
+            "collation_list" => Self::CollationList,
+            "data_version" => Self::DataVersion,
+            "freelist_count" => Self::FreelistCount,
+            "integrity_check" => Self::IntegrityCheck,
+            "legacy_file_format" => Self::LegacyFileFormat,
+            "page_count" => Self::PageCount,
+            "quick_check" => Self::QuickCheck,
+            "stats" => Self::Stats,
+            "user_version" => Self::UserVersion,
+            "analysis_limit" => Self::AnalysisLimit,
+            "application_id" => Self::ApplicationId,
+            "auto_vacuum" => Self::AutoVacuum,
+            "automatic_index" => Self::AutomaticIndex,
+            "busy_timeout" => Self::BusyTimeout,
+            "cache_size" => Self::CacheSize,
+            "cache_spill" => Self::CacheSpill,
+            "cell_size_check" => Self::CellSizeCheck,
+            "checkpoint_fullfsync" => Self::CheckpointFullfsync,
+            "fullfsync" => Self::Fullfsync,
+            "hard_heap_limit" => Self::HardHeapLimit,
+            "journal_mode" => Self::JournalMode,
+            "journal_size_limit" => Self::JournalSizeLimit,
+            "legacy_alter_table" => Self::LegacyAlterTable,
+            "locking_mode" => Self::LockingMode,
+            "max_page_count" => Self::MaxPageCount,
+            "mmap_size" => Self::MmapSize,
+            "page_size" => Self::PageSize,
+            "query_only" => Self::QueryOnly,
+            "read_uncommitted" => Self::ReadUncommitted,
+            "recursive_triggers" => Self::RecursiveTriggers,
+            "reverse_unordered_selects" => Self::ReverseUnorderedSelects,
+            "secure_delete" => Self::SecureDelete,
+            "soft_heap_limit" => Self::SoftHeapLimit,
+            "synchronous" => Self::Synchronous,
+            "temp_store" => Self::TempStore,
+            "threads" => Self::Threads,
+            "trusted_schema" => Self::TrustedSchema,
+            "wal_autocheckpoint" => Self::WalAutocheckpoint,
+            "case_sensitive_like" => Self::CaseSensitiveLike,
+            "ignore_check_constraints" => Self::IgnoreCheckConstraints,
+            "incremental_vacuum" => Self::IncrementalVacuum,
+            "optimize" => Self::Optimize,
+            "parser_trace" => Self::ParserTrace,
+            "shrink_memory" => Self::ShrinkMemory,
+            "wal_checkpoint" => Self::WalCheckpoint,
+            _ => return None,
+        })
+    }
+
+    /// How this pragma should be routed between a primary and its replicas.
+    fn access(self) -> PragmaAccess {
+        use KnownPragma::*;
+        match self {
+            TableList | IndexList | TableInfo | TableXinfo | IndexInfo | IndexXinfo | PragmaList
+            | CompileOptions | DatabaseList | FunctionList | ModuleList
+            // special case for `encoding` - it's effectively readonly for connections
+            // that already created a database, which is always the case for sqld
+            | Encoding => PragmaAccess::Read,
+            SchemaVersion => PragmaAccess::ReadIfNoArgs,
+            DeferForeignKeys | ForeignKeys | ForeignKeyList | ForeignKeyCheck | CollationList
+            | DataVersion | FreelistCount | IntegrityCheck | LegacyFileFormat | PageCount
+            | QuickCheck | Stats | UserVersion => PragmaAccess::Write,
+            AnalysisLimit | ApplicationId | AutoVacuum | AutomaticIndex | BusyTimeout | CacheSize
+            | CacheSpill | CellSizeCheck | CheckpointFullfsync | Fullfsync | HardHeapLimit
+            | JournalMode | JournalSizeLimit | LegacyAlterTable | LockingMode | MaxPageCount
+            | MmapSize | PageSize | QueryOnly | ReadUncommitted | RecursiveTriggers
+            | ReverseUnorderedSelects | SecureDelete | SoftHeapLimit | Synchronous | TempStore
+            | Threads | TrustedSchema | WalAutocheckpoint => PragmaAccess::WriteIfNoArgs,
+            // changes the state of the connection, and can't be allowed rn:
+            CaseSensitiveLike | IgnoreCheckConstraints | IncrementalVacuum
+                // TODO: check if optimize can be safely performed
+                | Optimize | ParserTrace | ShrinkMemory | WalCheckpoint => PragmaAccess::Disallowed,
+        }
+    }
+}
+
+/// Whether a pragma can be routed to a read replica, must always go to the primary, must go to
+/// the primary only when it's being read (no argument given), must go to the primary only when
+/// it's being written (an argument given), or can't be routed at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PragmaAccess {
+    Read,
+    ReadIfNoArgs,
+    Write,
+    WriteIfNoArgs,
+    Disallowed,
+}
+
+/// The columns a single INSERT statement may write, including any `ON CONFLICT DO UPDATE`
+/// clauses. Needed by callers that must know a write's column-level blast radius without
+/// re-walking the AST themselves, e.g. a CDC subscription filtering by column, or an
+/// authorization scope that only grants writes to specific columns.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WrittenColumns {
+    /// Columns targeted by the INSERT's own column list, or `None` if the statement lists no
+    /// explicit columns, meaning every column of the table may be written.
+    pub inserted: Option<Vec<String>>,
+    /// Columns additionally targeted by `DO UPDATE SET ...` clauses, across every `ON CONFLICT`
+    /// clause chained onto the upsert.
+    pub upserted: Vec<String>,
+}
+
+impl WrittenColumns {
+    fn from_insert(columns: &Option<Vec<sqlite3_parser::ast::Name>>, upsert: &Option<Upsert>) -> Self {
+        let mut upserted = Vec::new();
+        let mut next = upsert.as_ref();
+        while let Some(upsert) = next {
+            if let UpsertDo::Set { sets, .. } = &upsert.do_clause {
+                upserted.extend(sets.iter().flat_map(|set| {
+                    set.col_names.iter().map(|name| name.0.clone())
+                }));
+            }
+            next = upsert.next.as_deref();
+        }
+
+        Self {
+            inserted: columns
+                .as_ref()
+                .map(|cols| cols.iter().map(|name| name.0.clone()).collect()),
+            upserted,
+        }
+    }
+
+    /// Every column name this statement may write to, deduplicated. Doesn't attempt to expand
+    /// [`WrittenColumns::inserted`] being `None` into the table's full column list — check
+    /// [`WrittenColumns::writes_all_columns`] for that case first.
+    pub fn column_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .inserted
+            .iter()
+            .flatten()
+            .chain(self.upserted.iter())
+            .map(String::as_str)
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Whether the INSERT's column list was omitted, meaning it writes every column of the
+    /// table (in schema order) rather than a known subset.
+    pub fn writes_all_columns(&self) -> bool {
+        self.inserted.is_none()
+    }
+}
+
+/// The dependency graph between a statement's CTEs (`WITH ... AS (...)`) and its main query,
+/// built by walking every `FROM` clause reachable from the main query and each CTE's own
+/// `SELECT`. Lets the server estimate a statement's complexity from its CTE fan-out, and a
+/// query-rewriting pass decide which CTEs are referenced only once and so can be inlined instead
+/// of materialized.
+///
+/// Only tracks references made through `FROM` clauses (including subqueries and compound
+/// selects); a CTE referenced solely inside a scalar subquery expression such as
+/// `WHERE x IN (SELECT ...)` is not currently detected.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct CteGraph {
+    /// CTE names, in the order they appear in the `WITH` clause.
+    pub ctes: Vec<String>,
+    /// `(from, to)` edges: `from` references `to` in its body, where `from` is either a CTE
+    /// name or [`CteGraph::MAIN_QUERY`] for the statement's own body.
+    pub edges: Vec<(String, String)>,
+    /// CTE names that reference themselves, directly or through another CTE in the same `WITH`
+    /// clause, i.e. that require `WITH RECURSIVE` to be valid.
+    pub recursive: Vec<String>,
+}
+
+impl CteGraph {
+    /// Sentinel node standing in for the statement's own body in [`CteGraph::edges`].
+    pub const MAIN_QUERY: &'static str = "";
+
+    /// Builds the dependency graph for `select`'s `WITH` clause, if it has one.
+    fn build(select: &Select) -> Option<Self> {
+        let with = select.with.as_ref()?;
+        let ctes: Vec<String> = with.ctes.iter().map(|cte| cte.tbl_name.0.clone()).collect();
+
+        let mut edges = Vec::new();
+        for cte in &with.ctes {
+            let mut referenced = Vec::new();
+            collect_referenced_tables(&cte.select, &mut referenced);
+            for table in referenced {
+                if ctes.iter().any(|name| name.eq_ignore_ascii_case(&table)) {
+                    edges.push((cte.tbl_name.0.clone(), table));
+                }
+            }
+        }
+
+        let mut referenced_by_main = Vec::new();
+        collect_referenced_tables(select, &mut referenced_by_main);
+        for table in referenced_by_main {
+            if ctes.iter().any(|name| name.eq_ignore_ascii_case(&table)) {
+                edges.push((Self::MAIN_QUERY.to_string(), table));
+            }
+        }
+
+        let recursive = ctes
+            .iter()
+            .filter(|name| path_exists(&edges, name, name))
+            .cloned()
+            .collect();
+
+        Some(Self {
+            ctes,
+            edges,
+            recursive,
+        })
+    }
+
+    /// CTEs referenced from exactly one place (a CTE or the main query) — candidates for
+    /// inlining, since duplicating their body wouldn't multiply the work it does.
+    pub fn singly_referenced(&self) -> Vec<&str> {
+        self.ctes
+            .iter()
+            .filter(|name| self.edges.iter().filter(|(_, to)| to == name).count() == 1)
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// Whether `edges` contains a path from `from` to `to`, via depth-first search.
+fn path_exists(edges: &[(String, String)], from: &str, to: &str) -> bool {
+    fn visit<'a>(edges: &'a [(String, String)], node: &str, to: &str, seen: &mut Vec<&'a str>) -> bool {
+        edges
+            .iter()
+            .filter(|(f, _)| f == node)
+            .any(|(_, next)| {
+                if next == to {
+                    return true;
+                }
+                if seen.contains(&next.as_str()) {
+                    return false;
+                }
+                seen.push(next);
+                visit(edges, next, to, seen)
+            })
+    }
+    let mut seen = Vec::new();
+    visit(edges, from, to, &mut seen)
+}
+
+/// Collects every table name referenced in `select`'s `FROM` clauses, including subqueries and
+/// compound (`UNION`/`INTERSECT`/`EXCEPT`) branches, but not scalar subquery expressions.
+fn collect_referenced_tables(select: &Select, out: &mut Vec<String>) {
+    if let OneSelect::Select { from: Some(from), .. } = &select.body.select {
+        collect_from_clause(from, out);
+    }
+    if let Some(compounds) = &select.body.compounds {
+        for compound in compounds {
+            if let OneSelect::Select { from: Some(from), .. } = &compound.select {
+                collect_from_clause(from, out);
+            }
+        }
+    }
+}
+
+fn collect_from_clause(from: &FromClause, out: &mut Vec<String>) {
+    if let Some(table) = &from.select {
+        collect_select_table(table, out);
+    }
+    if let Some(joins) = &from.joins {
+        for join in joins {
+            collect_select_table(&join.table, out);
+        }
+    }
+}
+
+fn collect_select_table(table: &SelectTable, out: &mut Vec<String>) {
+    match table {
+        SelectTable::Table(name, ..) | SelectTable::TableCall(name, ..) => {
+            out.push(name.name.0.clone());
+        }
+        SelectTable::Select(select, _) => collect_referenced_tables(select, out),
+        SelectTable::Sub(from, _) => collect_from_clause(from, out),
+    }
+}
+
 /// The state of a transaction for a series of statement
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum TxnStatus {
@@ -280,6 +632,8 @@ impl Statement {
             is_iud: false,
             is_insert: false,
             attach_info: None,
+            written_columns: None,
+            cte_graph: None,
         }
     }
 
@@ -303,6 +657,8 @@ impl Statement {
                         is_iud: false,
                         is_insert: false,
                         attach_info: None,
+                        written_columns: None,
+                        cte_graph: None,
                     });
                 }
             }
@@ -322,12 +678,35 @@ impl Statement {
                 _ => None,
             };
 
+            let written_columns = match &c {
+                Cmd::Stmt(Stmt::Insert {
+                    columns,
+                    body: InsertBody::Select(_, upsert),
+                    ..
+                }) => Some(WrittenColumns::from_insert(columns, upsert)),
+                Cmd::Stmt(Stmt::Insert {
+                    columns,
+                    body: InsertBody::DefaultValues,
+                    ..
+                }) => Some(WrittenColumns::from_insert(columns, &None)),
+                _ => None,
+            };
+
+            let cte_graph = match &c {
+                Cmd::Stmt(Stmt::Select(select)) | Cmd::Explain(Stmt::Select(select)) => {
+                    CteGraph::build(select)
+                }
+                _ => None,
+            };
+
             Ok(Statement {
                 stmt: stmt_orig.to_string(),
                 kind,
                 is_iud,
                 is_insert,
                 attach_info,
+                written_columns,
+                cte_graph,
             })
         }
         // The parser needs to be boxed because it's large, and you don't want it on the stack.
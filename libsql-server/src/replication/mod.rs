@@ -1,7 +1,8 @@
 pub mod primary;
 pub mod replicator_client;
 pub mod script_backup_manager;
-mod snapshot;
+pub(crate) mod snapshot;
+mod snapshot_archive;
 pub mod snapshot_store;
 
 use crc::Crc;
@@ -84,6 +84,15 @@ pub struct LogFile {
     /// Encryption layer
     encryption: Option<FrameEncryptor>,
     encryption_buf: BytesMut,
+
+    /// Durability latency budget: commits within this long of the last sync are batched
+    /// together and fsynced as a group instead of one fsync per commit. `None` syncs on every
+    /// commit.
+    sync_interval: Option<Duration>,
+    last_synced_at: Instant,
+    /// Set when commits have been written since the last sync, so that callers that need a
+    /// strong durability guarantee (compaction, checkpoint, shutdown) know to force one.
+    needs_sync: bool,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -142,6 +151,9 @@ impl LogFile {
             commited_checksum: 0,
             encryption,
             encryption_buf,
+            sync_interval: None,
+            last_synced_at: Instant::now(),
+            needs_sync: false,
         };
 
         if file_end == 0 {
@@ -181,10 +193,35 @@ impl LogFile {
         self.uncommitted_frame_count = 0;
         self.commited_checksum = self.uncommitted_checksum;
         self.write_header()?;
+        self.needs_sync = true;
+
+        match self.sync_interval {
+            Some(interval) if self.last_synced_at.elapsed() < interval => {
+                // Still within the current batching window: leave the fsync for whichever
+                // commit closes the window (or an explicit `sync()` call), instead of paying it
+                // for every commit.
+            }
+            _ => self.sync()?,
+        }
 
         Ok(())
     }
 
+    /// Sets the durability latency budget used to batch fsyncs across commits. See
+    /// [`LogFile::sync_interval`].
+    pub fn set_sync_interval(&mut self, sync_interval: Option<Duration>) {
+        self.sync_interval = sync_interval;
+    }
+
+    /// Fsyncs the log file, making every commit written so far durable, and resets the batching
+    /// window.
+    pub fn sync(&mut self) -> anyhow::Result<()> {
+        self.file.sync_data()?;
+        self.needs_sync = false;
+        self.last_synced_at = Instant::now();
+        Ok(())
+    }
+
     pub(crate) fn rollback(&mut self) {
         self.uncommitted_frame_count = 0;
         self.uncommitted_checksum = self.commited_checksum;
@@ -426,10 +463,13 @@ impl LogFile {
     fn reset(self) -> anyhow::Result<Self> {
         let max_log_frame_count = self.max_log_frame_count;
         let max_log_duration = self.max_log_duration;
+        let sync_interval = self.sync_interval;
         // truncate file
         self.file.set_len(0)?;
         let encryption = self.encryption;
-        Self::new(self.file, max_log_frame_count, max_log_duration, encryption)
+        let mut new = Self::new(self.file, max_log_frame_count, max_log_duration, encryption)?;
+        new.set_sync_interval(sync_interval);
+        Ok(new)
     }
 
     pub fn set_encryptor(&mut self, encryption: Option<FrameEncryptor>) -> Option<FrameEncryptor> {
@@ -551,6 +591,7 @@ pub struct ReplicationLogger {
     pub closed_signal: watch::Sender<bool>,
     pub auto_checkpoint: u32,
     encryptor: Option<FrameEncryptor>,
+    namespace: NamespaceName,
 }
 
 impl ReplicationLogger {
@@ -563,6 +604,7 @@ impl ReplicationLogger {
         scripted_backup: Option<ScriptBackupManager>,
         namespace: NamespaceName,
         encryption_config: Option<EncryptionConfig>,
+        log_sync_interval: Option<Duration>,
     ) -> anyhow::Result<Self> {
         let log_path = db_path.join("wallog");
         let data_path = db_path.join("data");
@@ -577,7 +619,8 @@ impl ReplicationLogger {
 
         let max_log_frame_count = max_log_size * 1_000_000 / LogFile::FRAME_SIZE as u64;
         let encryption = encryption_config.clone().map(FrameEncryptor::new);
-        let log_file = LogFile::new(file, max_log_frame_count, max_log_duration, encryption)?;
+        let mut log_file = LogFile::new(file, max_log_frame_count, max_log_duration, encryption)?;
+        log_file.set_sync_interval(log_sync_interval);
         let header = log_file.header();
 
         let should_recover = if dirty {
@@ -664,7 +707,7 @@ impl ReplicationLogger {
                 &db_path,
                 Uuid::from_u128(log_file.header.log_id.get()),
                 scripted_backup,
-                namespace,
+                namespace.clone(),
             )?,
             log_file: RwLock::new(log_file),
             db_path,
@@ -674,6 +717,7 @@ impl ReplicationLogger {
             // we keep the last 100 commit transaction timestamps
             commit_timestamp_cache: moka::sync::Cache::new(*REPLICATION_LATENCY_CACHE_SIZE),
             encryptor,
+            namespace,
         })
     }
 
@@ -777,7 +821,7 @@ impl ReplicationLogger {
     }
 
     pub async fn get_snapshot_file(&self, from: FrameNo) -> anyhow::Result<Option<SnapshotFile>> {
-        find_snapshot_file(&self.db_path, from, self.encryptor.clone()).await
+        find_snapshot_file(&self.db_path, from, self.encryptor.clone(), &self.namespace).await
     }
 
     pub fn get_frame(&self, frame_no: FrameNo) -> Result<Frame, LogReadError> {
@@ -807,6 +851,18 @@ impl ReplicationLogger {
         Ok(true)
     }
 
+    /// Forces a sync of whatever commits are still batched in the current durability window.
+    /// Called periodically so that a lull in traffic doesn't leave the last commits of a burst
+    /// unsynced indefinitely, and before operations (checkpoint, shutdown) that require every
+    /// committed frame to be durable.
+    pub fn force_sync(&self) -> anyhow::Result<()> {
+        let mut log_file = self.log_file.write();
+        if log_file.needs_sync {
+            log_file.sync()?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn compactor(&self) -> &LogCompactor {
         &self.compactor
     }
@@ -898,6 +954,7 @@ mod test {
             None,
             "test".into(),
             None,
+            None,
         )
         .unwrap();
 
@@ -936,6 +993,7 @@ mod test {
             None,
             "test".into(),
             None,
+            None,
         )
         .unwrap();
         let log_file = logger.log_file.write();
@@ -955,6 +1013,7 @@ mod test {
             None,
             "test".into(),
             None,
+            None,
         )
         .unwrap();
         let entry = WalPage {
@@ -1011,6 +1070,91 @@ mod test {
         assert_eq!(log_file.frames_iter().unwrap().count(), 6);
     }
 
+    #[test]
+    fn log_file_no_sync_interval_syncs_every_commit() {
+        let f = tempfile::tempfile().unwrap();
+        let mut log_file = LogFile::new(f, 100, None, None).unwrap();
+
+        log_file
+            .push_page(&WalPage {
+                page_no: 0,
+                size_after: 5,
+                data: Bytes::from_static(&[1; 4096]),
+            })
+            .unwrap();
+        log_file.commit().unwrap();
+
+        // With no sync_interval configured, every commit fsyncs immediately, so nothing is
+        // ever left owing a sync.
+        assert!(!log_file.needs_sync);
+    }
+
+    #[test]
+    fn log_file_sync_interval_batches_commits() {
+        let f = tempfile::tempfile().unwrap();
+        let mut log_file = LogFile::new(f, 100, None, None).unwrap();
+        log_file.set_sync_interval(Some(Duration::from_secs(3600)));
+
+        log_file
+            .push_page(&WalPage {
+                page_no: 0,
+                size_after: 5,
+                data: Bytes::from_static(&[1; 4096]),
+            })
+            .unwrap();
+        log_file.commit().unwrap();
+        // Within the batching window, the commit is durable in the page cache but the fsync is
+        // deferred until the window closes or force_sync() is called.
+        assert!(log_file.needs_sync);
+        let last_synced_at = log_file.last_synced_at;
+
+        log_file
+            .push_page(&WalPage {
+                page_no: 1,
+                size_after: 5,
+                data: Bytes::from_static(&[1; 4096]),
+            })
+            .unwrap();
+        log_file.commit().unwrap();
+        // A second commit landing in the same window still owes exactly one fsync, not two.
+        assert!(log_file.needs_sync);
+        assert_eq!(log_file.last_synced_at, last_synced_at);
+
+        log_file.sync().unwrap();
+        assert!(!log_file.needs_sync);
+    }
+
+    #[tokio::test]
+    async fn replication_logger_force_sync_before_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let logger = ReplicationLogger::open(
+            dir.path(),
+            0,
+            None,
+            false,
+            DEFAULT_AUTO_CHECKPOINT,
+            None,
+            "test".into(),
+            None,
+            Some(Duration::from_secs(3600)),
+        )
+        .unwrap();
+
+        let frames = (0..5)
+            .map(|i| WalPage {
+                page_no: i,
+                size_after: 0,
+                data: Bytes::from(vec![i as _; 4096]),
+            })
+            .collect::<Vec<_>>();
+        logger.write_pages(&frames).unwrap();
+        logger.commit().unwrap();
+
+        assert!(logger.log_file.read().needs_sync);
+        logger.force_sync().unwrap();
+        assert!(!logger.log_file.read().needs_sync);
+    }
+
     #[tokio::test]
     #[cfg(feature = "encryption")]
     async fn log_with_encryption() {
@@ -1024,6 +1168,7 @@ mod test {
             None,
             "test".into(),
             None,
+            None,
         )
         .unwrap();
 
@@ -1077,6 +1222,7 @@ mod test {
                 None,
                 "test".into(),
                 None,
+                None,
             )
             .unwrap(),
         );
@@ -148,6 +148,7 @@ mod test {
                 None,
                 "test".into(),
                 None,
+                None,
             )
             .unwrap(),
         );
@@ -184,6 +184,22 @@ impl ReplicatorClient for Client {
                     .map_err(|e| Status::new(Code::Internal, e.to_string()))?;
             }
 
+            let last_seen_epoch = self.meta_store_handle.get().epoch;
+            let incoming_epoch = config.epoch.unwrap_or(0);
+            if incoming_epoch < last_seen_epoch {
+                // We've already seen a higher epoch than this primary is reporting, meaning
+                // it was demoted (e.g. a standby was promoted in its place) and is still trying
+                // to serve as primary. Refuse to replicate from it so its late writes can't be
+                // mistaken for current and cause a split-brain.
+                return Err(Error::Fatal(
+                    format!(
+                        "primary reported epoch {incoming_epoch}, but we've already seen epoch \
+                         {last_seen_epoch}: this primary appears to have been demoted"
+                    )
+                    .into(),
+                ));
+            }
+
             self.meta_store_handle
                 .store(DatabaseConfig::from(config))
                 .await
@@ -22,6 +22,7 @@ use crate::replication::primary::logger::LogFileHeader;
 
 use super::primary::logger::{FrameEncryptor, LogFile};
 use super::script_backup_manager::ScriptBackupManager;
+use super::snapshot_archive;
 use super::FrameNo;
 
 /// This is the ratio of the space required to store snapshot vs size of the actual database.
@@ -63,6 +64,7 @@ pub async fn find_snapshot_file(
     db_path: &Path,
     frame_no: FrameNo,
     encryptor: Option<FrameEncryptor>,
+    namespace: &NamespaceName,
 ) -> anyhow::Result<Option<SnapshotFile>> {
     let snapshot_dir_path = snapshot_dir_path(db_path);
     let snapshots = snapshot_list(db_path);
@@ -74,15 +76,171 @@ pub async fn find_snapshot_file(
         // we're looking for the frame right after the last applied frame on the replica
         if (start_frame_no..=end_frame_no).contains(&frame_no) {
             let snapshot_path = snapshot_dir_path.join(&name);
+            verify_local_snapshot_or_redownload(namespace, &name, &snapshot_path).await?;
             tracing::debug!("found snapshot for frame {frame_no} at {snapshot_path:?}");
             let snapshot_file = SnapshotFile::open(&snapshot_path, encryptor).await?;
             return Ok(Some(snapshot_file));
         }
     }
 
+    // the snapshot covering this frame may have been spilled to object storage to keep local
+    // disk usage bounded; fetch it back if so.
+    if let Some(archive) = snapshot_archive::ARCHIVE.as_ref() {
+        if let Some(name) = find_archived_snapshot_name(db_path, frame_no).await? {
+            let snapshot_path = snapshot_dir_path.join(&name);
+            if archive.download(namespace, &name, &snapshot_path).await? {
+                tracing::debug!(
+                    "restored snapshot for frame {frame_no} from the archive at {snapshot_path:?}"
+                );
+                let snapshot_file = SnapshotFile::open(&snapshot_path, encryptor).await?;
+                return Ok(Some(snapshot_file));
+            }
+        }
+    }
+
     Ok(None)
 }
 
+/// Prefers the local copy of a snapshot, but if it's also recorded in the archive, confirms it
+/// still matches the archived checksum before trusting it; a namespace that hibernated and woke
+/// back up may have had its local disk replaced or restored from a stale volume snapshot in the
+/// meantime. Falls back to re-downloading from the archive on a mismatch, and is a no-op when no
+/// archive is configured or the snapshot was never archived.
+async fn verify_local_snapshot_or_redownload(
+    namespace: &NamespaceName,
+    snapshot_name: &str,
+    local_path: &Path,
+) -> anyhow::Result<()> {
+    let Some(archive) = snapshot_archive::ARCHIVE.as_ref() else {
+        return Ok(());
+    };
+    let Some(expected) = archive.checksum(namespace, snapshot_name).await? else {
+        return Ok(());
+    };
+
+    let actual = {
+        let local_path = local_path.to_path_buf();
+        tokio::task::spawn_blocking(move || sha256::try_digest(local_path.as_path()))
+            .await
+            .context("failed to join checksum task")?
+            .context("failed to checksum local snapshot")?
+    };
+
+    if actual != expected {
+        tracing::warn!(
+            "local snapshot `{snapshot_name}` failed checksum verification against the archive, re-downloading"
+        );
+        archive.download(namespace, snapshot_name, local_path).await?;
+    }
+
+    Ok(())
+}
+
+/// Line-delimited index of snapshots that were uploaded to the archive and removed from local
+/// disk, so `find_snapshot_file` knows what to ask the archive for. Each line is a snapshot file
+/// name, so `parse_snapshot_name` applies to it just like a local `.snap` file.
+fn archived_snapshot_index_path(db_path: &Path) -> PathBuf {
+    snapshot_dir_path(db_path).join("archived.index")
+}
+
+async fn find_archived_snapshot_name(
+    db_path: &Path,
+    frame_no: FrameNo,
+) -> anyhow::Result<Option<String>> {
+    let index_path = archived_snapshot_index_path(db_path);
+    let Ok(content) = tokio::fs::read_to_string(&index_path).await else {
+        return Ok(None);
+    };
+
+    for name in content.lines() {
+        let Some((_, start_frame_no, end_frame_no)) = parse_snapshot_name(name) else {
+            continue;
+        };
+        if (start_frame_no..=end_frame_no).contains(&frame_no) {
+            return Ok(Some(name.to_string()));
+        }
+    }
+
+    Ok(None)
+}
+
+async fn record_archived_snapshot(db_path: &Path, snapshot_name: &str) -> anyhow::Result<()> {
+    use tokio::io::AsyncWriteExt as _;
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(archived_snapshot_index_path(db_path))
+        .await?;
+    file.write_all(format!("{snapshot_name}\n").as_bytes())
+        .await?;
+
+    Ok(())
+}
+
+/// Spill snapshots beyond the local retention window to the archive and delete them locally,
+/// recording them in the archived index so they can still be served to lagging replicas. A no-op
+/// when no archive is configured.
+async fn archive_old_snapshots(db_path: &Path, namespace: &NamespaceName) -> anyhow::Result<()> {
+    archive_snapshots_beyond(db_path, namespace, *snapshot_archive::LOCAL_SNAPSHOT_RETENTION).await
+}
+
+/// Push every remaining local snapshot to the archive and remove it from disk, freeing up as much
+/// space as possible for a namespace that just went idle. A no-op when no archive is configured.
+///
+/// This only spills the compacted snapshot files (see [`archive_old_snapshots`]); the namespace's
+/// still-open replication log is left alone, since it may still hold frames a lagging replica
+/// hasn't caught up on yet.
+pub async fn hibernate_namespace_snapshots(
+    db_path: &Path,
+    namespace: &NamespaceName,
+) -> anyhow::Result<()> {
+    archive_snapshots_beyond(db_path, namespace, 0).await
+}
+
+async fn archive_snapshots_beyond(
+    db_path: &Path,
+    namespace: &NamespaceName,
+    retention: usize,
+) -> anyhow::Result<()> {
+    let Some(archive) = snapshot_archive::ARCHIVE.as_ref() else {
+        return Ok(());
+    };
+
+    let snapshot_dir_path = snapshot_dir_path(db_path);
+    let mut by_age = Vec::new();
+    let snapshots = snapshot_list(db_path);
+    tokio::pin!(snapshots);
+    while let Some(name) = snapshots.next().await.transpose()? {
+        let Some((_, start_frame_no, _)) = parse_snapshot_name(&name) else {
+            continue;
+        };
+        by_age.push((start_frame_no, name));
+    }
+    by_age.sort_unstable_by_key(|(start_frame_no, _)| *start_frame_no);
+
+    if by_age.len() <= retention {
+        return Ok(());
+    }
+
+    for (_, name) in &by_age[..by_age.len() - retention] {
+        let path = snapshot_dir_path.join(name);
+        if let Err(e) = archive.upload(namespace, name, &path).await {
+            tracing::warn!("failed to archive snapshot `{name}`, keeping it on local disk: {e}");
+            continue;
+        }
+        if let Err(e) = record_archived_snapshot(db_path, name).await {
+            tracing::warn!("failed to record archived snapshot `{name}` in the local index: {e}");
+            continue;
+        }
+        if let Err(e) = tokio::fs::remove_file(&path).await {
+            tracing::warn!("failed to remove locally-archived snapshot `{name}`: {e}");
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Clone, Debug)]
 pub struct LogCompactor {
     sender: mpsc::Sender<(LogFile, PathBuf)>,
@@ -98,7 +256,15 @@ async fn compact(
     namespace: NamespaceName,
 ) -> anyhow::Result<()> {
     let before = Instant::now();
-    match perform_compaction(db_path, to_compact_file, log_id, namespace, scripted_backup).await {
+    match perform_compaction(
+        db_path,
+        to_compact_file,
+        log_id,
+        namespace.clone(),
+        scripted_backup,
+    )
+    .await
+    {
         Ok((snapshot_name, snapshot_frame_count, size_after)) => {
             tracing::info!(
                 "snapshot `{snapshot_name}` successfully created, in {:?}",
@@ -112,6 +278,10 @@ async fn compact(
                 bail!("failed to register snapshot with snapshot merger: {e}");
             }
 
+            if let Err(e) = archive_old_snapshots(db_path, &namespace).await {
+                tracing::warn!("failed to archive old snapshots: {e}");
+            }
+
             if let Err(e) = std::fs::remove_file(to_compact_path) {
                 bail!("failed to remove old log file `{to_compact_path:?}`: {e}",);
             }
@@ -0,0 +1,187 @@
+//! Optional spill of compacted replication snapshots to S3-compatible object storage.
+//!
+//! On a primary with many namespaces, the snapshots produced by compaction (see
+//! [`super::snapshot`]) can otherwise accumulate on local disk indefinitely: they're only deleted
+//! once merged away, and a namespace that's been stable for a while may keep old snapshots around
+//! just in case a lagging replica needs them. When an archive is configured, snapshots beyond the
+//! local retention window are uploaded here and removed from disk, while still being served to
+//! replicas on demand by downloading them back.
+//!
+//! This is unrelated to `bottomless`, which continuously streams the WAL itself to object storage
+//! for disaster recovery; this module only offloads the primary's own compacted snapshot files.
+use std::path::Path;
+
+use anyhow::{anyhow, Context as _};
+use aws_config::BehaviorVersion;
+use aws_sdk_s3::config::{Credentials, Region, SharedCredentialsProvider};
+use aws_sdk_s3::primitives::ByteStream;
+use once_cell::sync::Lazy;
+
+use crate::namespace::NamespaceName;
+
+/// Number of snapshots kept on local disk per namespace before older ones are spilled to the
+/// archive. Only takes effect when the archive is configured.
+pub static LOCAL_SNAPSHOT_RETENTION: Lazy<usize> = Lazy::new(|| {
+    std::env::var("SQLD_SNAPSHOT_ARCHIVE_LOCAL_RETENTION")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(8)
+});
+
+/// The configured archive, or `None` if `SQLD_SNAPSHOT_ARCHIVE_BUCKET` isn't set, in which case
+/// snapshots are kept on local disk indefinitely, as before this feature existed.
+pub static ARCHIVE: Lazy<Option<SnapshotArchive>> = Lazy::new(|| match SnapshotArchive::from_env() {
+    Ok(archive) => archive,
+    Err(e) => {
+        tracing::error!("invalid snapshot archive configuration, spilling to object storage is disabled: {e}");
+        None
+    }
+});
+
+#[derive(Clone)]
+pub struct SnapshotArchive {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+}
+
+impl SnapshotArchive {
+    fn from_env() -> anyhow::Result<Option<Self>> {
+        let Ok(bucket) = std::env::var("SQLD_SNAPSHOT_ARCHIVE_BUCKET") else {
+            return Ok(None);
+        };
+        let region = std::env::var("SQLD_SNAPSHOT_ARCHIVE_AWS_DEFAULT_REGION")
+            .map_err(|_| anyhow!("SQLD_SNAPSHOT_ARCHIVE_AWS_DEFAULT_REGION was not set"))?;
+        let access_key_id = std::env::var("SQLD_SNAPSHOT_ARCHIVE_AWS_ACCESS_KEY_ID")
+            .map_err(|_| anyhow!("SQLD_SNAPSHOT_ARCHIVE_AWS_ACCESS_KEY_ID was not set"))?;
+        let secret_access_key = std::env::var("SQLD_SNAPSHOT_ARCHIVE_AWS_SECRET_ACCESS_KEY")
+            .map_err(|_| anyhow!("SQLD_SNAPSHOT_ARCHIVE_AWS_SECRET_ACCESS_KEY was not set"))?;
+        let endpoint = std::env::var("SQLD_SNAPSHOT_ARCHIVE_ENDPOINT").ok();
+
+        let mut loader = aws_config::SdkConfig::builder();
+        if let Some(endpoint) = endpoint.as_deref() {
+            loader = loader.endpoint_url(endpoint);
+        }
+        let conf = loader
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new(region))
+            .credentials_provider(SharedCredentialsProvider::new(Credentials::new(
+                access_key_id,
+                secret_access_key,
+                None,
+                None,
+                "Static",
+            )))
+            .build();
+
+        let s3_config = aws_sdk_s3::config::Builder::from(&conf)
+            .force_path_style(true)
+            .build();
+
+        Ok(Some(Self {
+            client: aws_sdk_s3::Client::from_conf(s3_config),
+            bucket,
+        }))
+    }
+
+    fn key(namespace: &NamespaceName, snapshot_name: &str) -> String {
+        format!("{namespace}/{snapshot_name}")
+    }
+
+    /// Upload the snapshot file at `path` under `snapshot_name`, so it can later be fetched back
+    /// by a replica that needs a segment older than what the primary keeps locally. The file's
+    /// sha256 is recorded alongside it, so a caller that still has a local copy can later confirm
+    /// it wasn't corrupted or replaced without downloading it again (see [`Self::checksum`]).
+    pub async fn upload(
+        &self,
+        namespace: &NamespaceName,
+        snapshot_name: &str,
+        path: &Path,
+    ) -> anyhow::Result<()> {
+        let checksum = {
+            let path = path.to_path_buf();
+            tokio::task::spawn_blocking(move || sha256::try_digest(path.as_path()))
+                .await
+                .context("failed to join checksum task")?
+                .context("failed to checksum snapshot before upload")?
+        };
+        let body = ByteStream::from_path(path)
+            .await
+            .context("failed to open snapshot file for upload to the archive")?;
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(Self::key(namespace, snapshot_name))
+            .metadata("sha256", checksum)
+            .body(body)
+            .send()
+            .await
+            .context("failed to upload snapshot to the archive")?;
+
+        Ok(())
+    }
+
+    /// Returns the sha256 checksum recorded for `snapshot_name` when it was archived, or `None`
+    /// if it isn't archived.
+    pub async fn checksum(
+        &self,
+        namespace: &NamespaceName,
+        snapshot_name: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let resp = self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(Self::key(namespace, snapshot_name))
+            .send()
+            .await;
+
+        let output = match resp {
+            Ok(output) => output,
+            Err(aws_sdk_s3::error::SdkError::ServiceError(e)) if e.err().is_not_found() => {
+                return Ok(None)
+            }
+            Err(e) => return Err(e).context("failed to fetch snapshot metadata from the archive"),
+        };
+
+        Ok(output
+            .metadata()
+            .and_then(|metadata| metadata.get("sha256"))
+            .cloned())
+    }
+
+    /// Download a previously-archived snapshot to `dest`. Returns `Ok(false)`, rather than an
+    /// error, when the snapshot isn't archived, since that's the common case of a replica asking
+    /// for a frame range that simply doesn't exist anywhere, local or remote.
+    pub async fn download(
+        &self,
+        namespace: &NamespaceName,
+        snapshot_name: &str,
+        dest: &Path,
+    ) -> anyhow::Result<bool> {
+        let resp = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(Self::key(namespace, snapshot_name))
+            .send()
+            .await;
+
+        let output = match resp {
+            Ok(output) => output,
+            Err(aws_sdk_s3::error::SdkError::ServiceError(e)) if e.err().is_no_such_key() => {
+                return Ok(false)
+            }
+            Err(e) => return Err(e).context("failed to download snapshot from the archive"),
+        };
+
+        let mut body = output.body.into_async_read();
+        let mut file = tokio::fs::File::create(dest)
+            .await
+            .context("failed to create local file for archived snapshot")?;
+        tokio::io::copy(&mut body, &mut file)
+            .await
+            .context("failed to write archived snapshot to local disk")?;
+
+        Ok(true)
+    }
+}
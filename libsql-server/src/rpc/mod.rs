@@ -21,8 +21,20 @@ use crate::utils::services::idle_shutdown::IdleShutdownKicker;
 pub mod proxy;
 pub mod replica_proxy;
 pub mod replication;
+pub mod status;
 pub mod streaming_exec;
 
+/// Builds the internal RPC server's gRPC reflection service, so tools like `grpcurl` can call
+/// `Proxy`/`ReplicationLog` without a local copy of the `.proto` files.
+fn reflection_service(
+) -> anyhow::Result<tonic_reflection::server::ServerReflectionServer<impl tonic_reflection::server::ServerReflection>>
+{
+    let service = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(libsql_replication::rpc::FILE_DESCRIPTOR_SET)
+        .build()?;
+    Ok(service)
+}
+
 pub async fn run_rpc_server<A: crate::net::Accept>(
     proxy_service: ProxyService,
     acceptor: A,
@@ -66,6 +78,7 @@ pub async fn run_rpc_server<A: crate::net::Accept>(
             .layer(&option_layer(idle_shutdown_layer))
             .add_service(ProxyServer::new(proxy_service))
             .add_service(ReplicationLogServer::new(service))
+            .add_service(reflection_service()?)
             .into_router();
 
         let svc = ServiceBuilder::new()
@@ -91,6 +104,7 @@ pub async fn run_rpc_server<A: crate::net::Accept>(
             .layer(&option_layer(idle_shutdown_layer))
             .add_service(proxy)
             .add_service(replication)
+            .add_service(reflection_service()?)
             .into_router();
 
         let svc = ServiceBuilder::new()
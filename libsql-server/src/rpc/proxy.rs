@@ -455,10 +455,10 @@ impl QueryResultBuilder for ExecuteResultsBuilder {
     }
 
     fn add_row_value(&mut self, v: ValueRef) -> Result<(), QueryResultBuilderError> {
-        let data = bincode::serialize(
-            &crate::query::Value::try_from(v).map_err(QueryResultBuilderError::from_any)?,
-        )
-        .map_err(QueryResultBuilderError::from_any)?;
+        // Serialize straight from the borrowed `ValueRef` instead of going through an owned
+        // `Value`, avoiding a text/blob copy per cell.
+        let data = bincode::serialize(&crate::query::ValueRefSer(v))
+            .map_err(QueryResultBuilderError::from_any)?;
 
         if data.len() as u64 + self.current_step_size + self.current_size > self.max_size {
             return Err(QueryResultBuilderError::ResponseTooLarge(self.max_size));
@@ -587,7 +587,13 @@ impl Proxy for ProxyService {
             .await
             .map_err(|e| {
                 if let crate::error::Error::NamespaceDoesntExist(_) = e {
-                    tonic::Status::failed_precondition(NAMESPACE_DOESNT_EXIST)
+                    crate::rpc::status::namespace_error(
+                        tonic::Code::FailedPrecondition,
+                        NAMESPACE_DOESNT_EXIST,
+                        ctx.namespace(),
+                        "namespace does not exist",
+                        None,
+                    )
                 } else {
                     tonic::Status::internal(e.to_string())
                 }
@@ -619,7 +625,13 @@ impl Proxy for ProxyService {
             .await
             .map_err(|e| {
                 if let crate::error::Error::NamespaceDoesntExist(_) = e {
-                    tonic::Status::failed_precondition(NAMESPACE_DOESNT_EXIST)
+                    crate::rpc::status::namespace_error(
+                        tonic::Code::FailedPrecondition,
+                        NAMESPACE_DOESNT_EXIST,
+                        ctx.namespace(),
+                        "namespace does not exist",
+                        None,
+                    )
                 } else {
                     tonic::Status::internal(e.to_string())
                 }
@@ -693,7 +705,13 @@ impl Proxy for ProxyService {
             .await
             .map_err(|e| {
                 if let crate::error::Error::NamespaceDoesntExist(_) = e {
-                    tonic::Status::failed_precondition(NAMESPACE_DOESNT_EXIST)
+                    crate::rpc::status::namespace_error(
+                        tonic::Code::FailedPrecondition,
+                        NAMESPACE_DOESNT_EXIST,
+                        ctx.namespace(),
+                        "namespace does not exist",
+                        None,
+                    )
                 } else {
                     tonic::Status::internal(e.to_string())
                 }
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::net::SocketAddr;
 use std::pin::Pin;
 use std::sync::{Arc, RwLock};
@@ -21,7 +21,7 @@ use tonic::Status;
 use uuid::Uuid;
 
 use crate::auth::Auth;
-use crate::connection::config::DatabaseConfig;
+use crate::connection::config::{DatabaseConfig, ReplicaPriority};
 use crate::namespace::{NamespaceName, NamespaceStore};
 use crate::replication::primary::frame_stream::FrameStream;
 use crate::replication::{LogReadError, ReplicationLogger};
@@ -44,6 +44,10 @@ pub struct ReplicationLogService {
     //deprecated:
     generation_id: Uuid,
     replicas_with_hello: RwLock<HashSet<(SocketAddr, NamespaceName)>>,
+
+    // number of replicas currently streaming frames for each namespace, used to enforce
+    // `DatabaseConfig::max_replicas`
+    replica_counts: Arc<RwLock<HashMap<NamespaceName, usize>>>,
 }
 
 pub const MAX_FRAMES_PER_BATCH: usize = 1024;
@@ -67,10 +71,46 @@ impl ReplicationLogService {
             collect_stats,
             generation_id: Uuid::new_v4(),
             replicas_with_hello: Default::default(),
+            replica_counts: Default::default(),
             service_internal,
         }
     }
 
+    /// Reserves a fan-out slot for a new replica of `namespace`, enforcing
+    /// [`DatabaseConfig::max_replicas`]. Replicas with [`ReplicaPriority::Priority`] bypass the
+    /// limit so that operationally important connections always get through. The returned guard
+    /// releases the slot (if one was taken) when the replica's stream is dropped.
+    fn try_reserve_replica_slot(
+        &self,
+        namespace: &NamespaceName,
+        config: &DatabaseConfig,
+    ) -> Result<ReplicaSlotGuard, Status> {
+        if config.replica_priority == ReplicaPriority::Priority {
+            return Ok(ReplicaSlotGuard {
+                namespace: namespace.clone(),
+                counts: self.replica_counts.clone(),
+                reserved: false,
+            });
+        }
+
+        if let Some(max_replicas) = config.max_replicas {
+            let mut counts = self.replica_counts.write().unwrap();
+            let count = counts.entry(namespace.clone()).or_insert(0);
+            if *count >= max_replicas as usize {
+                return Err(Status::resource_exhausted(format!(
+                    "namespace `{namespace}` already has the maximum of {max_replicas} connected replicas"
+                )));
+            }
+            *count += 1;
+        }
+
+        Ok(ReplicaSlotGuard {
+            namespace: namespace.clone(),
+            counts: self.replica_counts.clone(),
+            reserved: true,
+        })
+    }
+
     async fn authenticate<T>(
         &self,
         req: &tonic::Request<T>,
@@ -202,9 +242,31 @@ fn map_frame_stream_output(
     }
 }
 
+struct ReplicaSlotGuard {
+    namespace: NamespaceName,
+    counts: Arc<RwLock<HashMap<NamespaceName, usize>>>,
+    reserved: bool,
+}
+
+impl Drop for ReplicaSlotGuard {
+    fn drop(&mut self) {
+        if !self.reserved {
+            return;
+        }
+        let mut counts = self.counts.write().unwrap();
+        if let Some(count) = counts.get_mut(&self.namespace) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                counts.remove(&self.namespace);
+            }
+        }
+    }
+}
+
 pub struct StreamGuard<S> {
     s: S,
     idle_shutdown_layer: Option<IdleShutdownKicker>,
+    replica_slot: Option<ReplicaSlotGuard>,
 }
 
 impl<S> StreamGuard<S> {
@@ -215,8 +277,14 @@ impl<S> StreamGuard<S> {
         Self {
             s,
             idle_shutdown_layer,
+            replica_slot: None,
         }
     }
+
+    fn with_replica_slot(mut self, replica_slot: ReplicaSlotGuard) -> Self {
+        self.replica_slot = Some(replica_slot);
+        self
+    }
 }
 
 impl<S> Drop for StreamGuard<S> {
@@ -254,8 +322,10 @@ impl ReplicationLog for ReplicationLogService {
 
         self.authenticate(&req, namespace.clone()).await?;
 
-        let (logger, _, _, stats, config_changed) =
-            self.logger_from_namespace(namespace, &req, true).await?;
+        let (logger, config, _, stats, config_changed) =
+            self.logger_from_namespace(namespace.clone(), &req, true).await?;
+
+        let replica_slot = self.try_reserve_replica_slot(&namespace, &config)?;
 
         let stats = if self.collect_stats {
             Some(stats)
@@ -270,6 +340,7 @@ impl ReplicationLog for ReplicationLogService {
                 .map_err(|e| Status::internal(e.to_string()))?,
             self.idle_shutdown_layer.clone(),
         )
+        .with_replica_slot(replica_slot)
         .map(map_frame_stream_output);
 
         // if only tokio_stream had futures::Stream::take_until...
@@ -301,7 +372,11 @@ impl ReplicationLog for ReplicationLogService {
         let namespace = super::super::extract_namespace(self.disable_namespaces, &req)?;
         self.authenticate(&req, namespace.clone()).await?;
 
-        let (logger, _, _, stats, _) = self.logger_from_namespace(namespace, &req, true).await?;
+        let (logger, config, _, stats, _) = self
+            .logger_from_namespace(namespace.clone(), &req, true)
+            .await?;
+
+        let replica_slot = self.try_reserve_replica_slot(&namespace, &config)?;
 
         let stats = if self.collect_stats {
             Some(stats)
@@ -322,6 +397,7 @@ impl ReplicationLog for ReplicationLogService {
             .map_err(|e| Status::internal(e.to_string()))?,
             self.idle_shutdown_layer.clone(),
         )
+        .with_replica_slot(replica_slot)
         .map(map_frame_stream_output)
         .collect::<Result<Vec<_>, _>>()
         .await?;
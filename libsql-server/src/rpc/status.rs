@@ -0,0 +1,31 @@
+//! Helpers for attaching structured `google.rpc.Status` details to gRPC errors, on top of the
+//! plain `tonic::Status::new(code, message)` used elsewhere. Rich details let well-behaved
+//! clients (e.g. our own replica/write-proxy clients) branch on a stable reason string and a
+//! retry hint instead of pattern-matching the message text.
+
+use std::time::Duration;
+
+use tonic::{Code, Status};
+use tonic_types::{ErrorDetails, StatusExt};
+
+use crate::namespace::NamespaceName;
+
+/// Builds a [`Status`] carrying an [`ErrorDetails`] `ErrorInfo` that names the namespace the
+/// request was for, plus an optional `RetryInfo` telling the caller how long to back off before
+/// retrying. `reason` should be a short, stable, upper-snake-case identifier (e.g.
+/// `NAMESPACE_DOESNT_EXIST`) that callers can match on.
+pub fn namespace_error(
+    code: Code,
+    reason: &str,
+    namespace: &NamespaceName,
+    message: impl Into<String>,
+    retry_after: Option<Duration>,
+) -> Status {
+    let mut details = ErrorDetails::new();
+    details.set_error_info(reason, "libsql.sqld", [("namespace", namespace.to_string())]);
+    if let Some(retry_after) = retry_after {
+        details.set_retry_info(Some(retry_after));
+    }
+
+    Status::with_error_details(code, message, details)
+}
@@ -948,6 +948,8 @@ mod test {
             bottomless_replication: None,
             scripted_backup: None,
             checkpoint_interval: None,
+            checkpoint_semaphore: Arc::new(Semaphore::new(10_000)),
+            log_sync_interval: None,
         };
 
         let make_wal_manager = Arc::new(|| Sqlite3WalManager::default());
@@ -6,7 +6,7 @@ use chrono::{DateTime, DurationRound, Utc};
 use hdrhistogram::Histogram;
 use metrics::{counter, gauge, histogram, increment_counter};
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, VecDeque};
 use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc;
 use tokio::task::JoinSet;
@@ -23,15 +23,18 @@ pub struct TopQuery {
     pub rows_written: u64,
     pub rows_read: u64,
     pub query: String,
+    /// The client-supplied query tag (see `libsql::Connection::set_query_tag`), if any.
+    pub tag: Option<String>,
 }
 
 impl TopQuery {
-    fn new(query: String, rows_read: u64, rows_written: u64) -> Self {
+    fn new(query: String, rows_read: u64, rows_written: u64, tag: Option<String>) -> Self {
         Self {
             weight: rows_read + rows_written,
             rows_read,
             rows_written,
             query,
+            tag,
         }
     }
 }
@@ -42,15 +45,18 @@ pub struct SlowestQuery {
     pub query: String,
     pub rows_written: u64,
     pub rows_read: u64,
+    /// The client-supplied query tag (see `libsql::Connection::set_query_tag`), if any.
+    pub tag: Option<String>,
 }
 
 impl SlowestQuery {
-    fn new(query: String, elapsed_ms: u64, rows_read: u64, rows_written: u64) -> Self {
+    fn new(query: String, elapsed_ms: u64, rows_read: u64, rows_written: u64, tag: Option<String>) -> Self {
         Self {
             elapsed_ms,
             query,
             rows_read,
             rows_written,
+            tag,
         }
     }
 }
@@ -188,6 +194,81 @@ impl QueriesStats {
     }
 }
 
+/// A single point in a namespace's [`StatsHistory`].
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct StatsSample {
+    /// Unix timestamp, in seconds, the sample was taken at.
+    pub timestamp: i64,
+    pub rows_read: u64,
+    pub rows_written: u64,
+    pub storage_bytes_used: u64,
+}
+
+/// How often [`Stats`] samples itself into its [`StatsHistory`].
+const HISTORY_SAMPLE_INTERVAL: Duration = Duration::from_secs(300);
+/// Number of samples kept at full (`HISTORY_SAMPLE_INTERVAL`) resolution - 24h at the default
+/// interval - before they get folded into `downsampled`.
+const HISTORY_RAW_CAPACITY: usize = 288;
+/// Width, in seconds, of a downsampled bucket.
+const HISTORY_BUCKET_SECS: i64 = 3600;
+/// Number of downsampled buckets kept - 30 days at the default bucket width - before the oldest
+/// is dropped.
+const HISTORY_DOWNSAMPLED_CAPACITY: usize = 720;
+
+/// A per-namespace time series of [`StatsSample`]s, so admin dashboards have enough history for
+/// basic usage graphs without needing to scrape `/metrics` externally. Recent samples are kept at
+/// full resolution; once a sample ages out of that window it's folded into an hourly bucket
+/// instead, keeping memory (and the serialized size of `stats.json`) bounded regardless of uptime.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StatsHistory {
+    raw: VecDeque<StatsSample>,
+    downsampled: VecDeque<StatsSample>,
+}
+
+impl StatsHistory {
+    fn record(&mut self, sample: StatsSample) {
+        if self.raw.len() >= HISTORY_RAW_CAPACITY {
+            if let Some(evicted) = self.raw.pop_front() {
+                self.downsample(evicted);
+            }
+        }
+        self.raw.push_back(sample);
+    }
+
+    fn downsample(&mut self, sample: StatsSample) {
+        let bucket_start = sample.timestamp - sample.timestamp.rem_euclid(HISTORY_BUCKET_SECS);
+        match self.downsampled.back_mut() {
+            // Still in the same bucket as the last downsampled sample: keep the most recent
+            // reading for it, since these are cumulative counters rather than deltas.
+            Some(last) if last.timestamp == bucket_start => {
+                *last = StatsSample {
+                    timestamp: bucket_start,
+                    ..sample
+                };
+            }
+            _ => {
+                if self.downsampled.len() >= HISTORY_DOWNSAMPLED_CAPACITY {
+                    self.downsampled.pop_front();
+                }
+                self.downsampled.push_back(StatsSample {
+                    timestamp: bucket_start,
+                    ..sample
+                });
+            }
+        }
+    }
+
+    /// Samples, oldest first, with a timestamp in `[from, to]`.
+    fn range(&self, from: i64, to: i64) -> Vec<StatsSample> {
+        self.downsampled
+            .iter()
+            .chain(self.raw.iter())
+            .filter(|s| s.timestamp >= from && s.timestamp <= to)
+            .copied()
+            .collect()
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct StatsUpdateMessage {
     pub sql: String,
@@ -235,6 +316,8 @@ pub struct Stats {
     query_latency: AtomicU64,
     #[serde(skip)]
     queries: Arc<RwLock<Option<QueriesStats>>>,
+    #[serde(default)]
+    history: Arc<RwLock<StatsHistory>>,
 }
 
 impl Stats {
@@ -269,9 +352,15 @@ impl Stats {
 
         join_set.spawn(spawn_stats_thread(Arc::downgrade(&this), update_receiver));
 
+        join_set.spawn(spawn_stats_history_thread(Arc::downgrade(&this)));
+
         Ok(this)
     }
 
+    pub fn namespace(&self) -> &NamespaceName {
+        &self.namespace
+    }
+
     pub fn send(&self, msg: StatsUpdateMessage) {
         if let Some(sender) = &self.sender {
             let _ = sender.blocking_send(msg);
@@ -291,6 +380,7 @@ impl Stats {
             rows_read
         };
         let weight = rows_read + rows_written;
+        let tag = crate::query_analysis::extract_query_tag(&sql);
 
         histogram!("libsql_server_statement_execution_time", elapsed);
         histogram!("libsql_server_statement_mem_used_bytes", mem_used as f64);
@@ -303,10 +393,11 @@ impl Stats {
             };
 
             tracing::info!(
-                "high read ({}) or write ({}) query: {}",
+                "high read ({}) or write ({}) query: {} tag={}",
                 rows_read,
                 rows_written,
-                sql
+                sql,
+                tag.as_deref().unwrap_or("")
             );
         }
 
@@ -322,6 +413,7 @@ impl Stats {
                 sql.clone(),
                 rows_read,
                 rows_written,
+                tag.clone(),
             ));
         }
         if self.qualifies_as_slowest_query(elapsed_ms) {
@@ -330,6 +422,7 @@ impl Stats {
                 elapsed_ms,
                 rows_read,
                 rows_written,
+                tag,
             ));
         }
 
@@ -431,10 +524,11 @@ impl Stats {
     fn add_top_query(&self, query: TopQuery) {
         let mut top_queries = self.top_queries.write().unwrap();
         tracing::debug!(
-            "top query: {},{}:{}",
+            "top query: {},{}:{} tag={}",
             query.rows_read,
             query.rows_written,
-            query.query
+            query.query,
+            query.tag.as_deref().unwrap_or("")
         );
         top_queries.insert(query);
         if top_queries.len() > 10 {
@@ -459,7 +553,12 @@ impl Stats {
 
     fn add_slowest_query(&self, query: SlowestQuery) {
         let mut slowest_queries = self.slowest_queries.write().unwrap();
-        tracing::debug!("slowest query: {}: {}", query.elapsed_ms, query.query);
+        tracing::debug!(
+            "slowest query: {}: {} tag={}",
+            query.elapsed_ms,
+            query.query,
+            query.tag.as_deref().unwrap_or("")
+        );
         slowest_queries.insert(query);
         if slowest_queries.len() > 10 {
             slowest_queries.pop_first();
@@ -496,6 +595,20 @@ impl Stats {
     pub(crate) fn id(&self) -> Option<Uuid> {
         self.id
     }
+
+    fn record_history_sample(&self) {
+        self.history.write().unwrap().record(StatsSample {
+            timestamp: Utc::now().timestamp(),
+            rows_read: self.rows_read(),
+            rows_written: self.rows_written(),
+            storage_bytes_used: self.storage_bytes_used(),
+        });
+    }
+
+    /// Samples, oldest first, with a timestamp in `[from, to]` (unix seconds).
+    pub(crate) fn history_range(&self, from: i64, to: i64) -> Vec<StatsSample> {
+        self.history.read().unwrap().range(from, to)
+    }
 }
 
 async fn spawn_stats_thread(
@@ -510,6 +623,16 @@ async fn spawn_stats_thread(
     }
 }
 
+async fn spawn_stats_history_thread(stats: Weak<Stats>) -> anyhow::Result<()> {
+    loop {
+        tokio::time::sleep(HISTORY_SAMPLE_INTERVAL).await;
+        match stats.upgrade() {
+            Some(stats) => stats.record_history_sample(),
+            None => return Ok(()),
+        }
+    }
+}
+
 async fn spawn_stats_persist_thread(stats: Weak<Stats>, path: PathBuf) -> anyhow::Result<()> {
     loop {
         if let Err(e) = try_persist_stats(stats.clone(), &path).await {
@@ -91,6 +91,7 @@ async fn configure_server(
             hard_heap_limit_mb: None,
             max_response_size: 10000000 * 4096,
             max_total_response_size: 10000000 * 4096,
+            max_request_size: 10000000 * 4096,
             snapshot_exec: None,
             checkpoint_interval: Some(Duration::from_secs(3)),
             snapshot_at_shutdown: false,
@@ -108,6 +109,7 @@ async fn configure_server(
             self_url: None,
             primary_url: None,
             auth_strategy: Auth::new(Disabled::new()),
+            ..Default::default()
         },
         path: path.into().into(),
         disable_default_namespace: false,
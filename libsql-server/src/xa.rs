@@ -0,0 +1,110 @@
+use crate::connection::{Connection as _, MakeConnection as _};
+use crate::database::Connection;
+use crate::namespace::{NamespaceName, NamespaceStore};
+
+/// A batch of raw SQL statements to run against a single namespace as part of a
+/// [`TwoPhaseWrite`].
+#[derive(Debug, serde::Deserialize)]
+pub struct XaNamespaceWrite {
+    pub namespace: NamespaceName,
+    pub statements: Vec<String>,
+}
+
+/// A cross-namespace write batch, applied by [`TwoPhaseCoordinator::execute`].
+///
+/// This is an experimental, best-effort mechanism: sqld has no distributed transaction
+/// log spanning the independent WALs of each namespace, so this is *not* a real two-phase
+/// commit. All namespaces are prepared (their statements are executed inside a
+/// `BEGIN IMMEDIATE` transaction, uncommitted) before any of them are committed, which
+/// narrows the window for a partial failure, but a crash between the first and the last
+/// per-namespace `COMMIT` can still leave the batch only partially applied.
+#[derive(Debug, serde::Deserialize)]
+pub struct TwoPhaseWrite {
+    pub writes: Vec<XaNamespaceWrite>,
+}
+
+/// Coordinates [`TwoPhaseWrite`] batches across namespaces.
+pub struct TwoPhaseCoordinator {
+    store: NamespaceStore,
+}
+
+impl TwoPhaseCoordinator {
+    pub fn new(store: NamespaceStore) -> Self {
+        Self { store }
+    }
+
+    /// Prepares every namespace's statements under a `BEGIN IMMEDIATE` transaction, and
+    /// only commits them once all of them have prepared successfully. If any namespace
+    /// fails to prepare, every namespace already prepared is rolled back and the error is
+    /// returned; nothing is left observably committed in that case.
+    pub async fn execute(&self, batch: TwoPhaseWrite) -> crate::Result<()> {
+        let mut prepared = Vec::with_capacity(batch.writes.len());
+
+        for write in batch.writes {
+            match self.prepare(&write).await {
+                Ok(conn) => prepared.push((write.namespace, conn)),
+                Err(e) => {
+                    for (namespace, conn) in prepared {
+                        if let Err(rollback_err) = conn.with_raw(|conn| conn.execute_batch("ROLLBACK"))
+                        {
+                            tracing::error!(
+                                "failed to roll back prepared xa write on `{namespace}`: {rollback_err}"
+                            );
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        let mut failed = Vec::new();
+        for (namespace, conn) in prepared {
+            if let Err(e) = conn.with_raw(|conn| conn.execute_batch("COMMIT")) {
+                // Some namespaces may already be durably committed at this point, so we
+                // can't roll the whole batch back anymore: log and move on to the rest.
+                tracing::error!("failed to commit prepared xa write on `{namespace}`: {e}");
+                failed.push(namespace);
+            }
+        }
+
+        if !failed.is_empty() {
+            return Err(crate::Error::XaError(format!(
+                "failed to commit on namespace(s): {}",
+                failed
+                    .iter()
+                    .map(|n| n.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn prepare(&self, write: &XaNamespaceWrite) -> crate::Result<Connection> {
+        let conn_maker = self
+            .store
+            .with(write.namespace.clone(), |ns| ns.db.connection_maker())
+            .await?;
+        let conn = conn_maker.create().await?;
+
+        conn.with_raw(|conn| conn.execute_batch("BEGIN IMMEDIATE"))
+            .map_err(|e| {
+                crate::Error::XaError(format!(
+                    "failed to begin transaction on `{}`: {e}",
+                    write.namespace
+                ))
+            })?;
+
+        for stmt in &write.statements {
+            conn.with_raw(|conn| conn.execute_batch(stmt)).map_err(|e| {
+                crate::Error::XaError(format!(
+                    "failed to prepare statement on `{}`: {e}",
+                    write.namespace
+                ))
+            })?;
+        }
+
+        Ok(conn)
+    }
+}
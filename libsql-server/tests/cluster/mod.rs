@@ -71,6 +71,7 @@ pub fn make_cluster(sim: &mut Sim, num_replica: usize, disable_namespaces: bool)
                         remote_url: "http://primary:4567".into(),
                         connector: TurmoilConnector,
                         tls_config: None,
+                        standby: false,
                     }),
                     disable_namespaces,
                     disable_default_namespace: !disable_namespaces,
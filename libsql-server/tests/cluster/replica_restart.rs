@@ -74,6 +74,7 @@ fn replica_restart() {
                             remote_url: "http://primary:4567".into(),
                             connector: TurmoilConnector,
                             tls_config: None,
+                            standby: false,
                         }),
                         ..Default::default()
                     }
@@ -250,6 +251,7 @@ fn primary_regenerate_log_no_replica_restart() {
                             remote_url: "http://primary:4567".into(),
                             connector: TurmoilConnector,
                             tls_config: None,
+                            standby: false,
                         }),
                         ..Default::default()
                     }
@@ -432,6 +434,7 @@ fn primary_regenerate_log_with_replica_restart() {
                             remote_url: "http://primary:4567".into(),
                             connector: TurmoilConnector,
                             tls_config: None,
+                            standby: false,
                         }),
                         ..Default::default()
                     }
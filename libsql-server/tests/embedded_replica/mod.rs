@@ -1441,6 +1441,7 @@ fn replicate_auth() {
                         remote_url: "http://primary:4567".into(),
                         connector: TurmoilConnector,
                         tls_config: None,
+                        standby: false,
                     }),
                     ..Default::default()
                 };
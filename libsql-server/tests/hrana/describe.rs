@@ -0,0 +1,198 @@
+//! Exercises the `describe` request over a raw WebSocket connection, including describing a
+//! statement against a table that only exists inside the stream's still-open transaction.
+
+use std::time::Duration;
+
+use futures::SinkExt as _;
+use serde::{Deserialize, Serialize};
+use tokio_stream::StreamExt;
+use tokio_tungstenite::{client_async, tungstenite::client::IntoClientRequest, tungstenite};
+use turmoil::net::TcpStream;
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMsg {
+    Hello {
+        jwt: Option<String>,
+    },
+    Request {
+        request_id: i32,
+        request: Request,
+    },
+}
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request {
+    OpenStream {
+        stream_id: i32,
+    },
+    Execute {
+        stream_id: i32,
+        stmt: Stmt,
+    },
+    Describe {
+        stream_id: i32,
+        sql: Option<String>,
+    },
+}
+
+#[derive(Serialize, Debug, Default)]
+struct Stmt {
+    sql: Option<String>,
+    want_rows: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMsg {
+    HelloOk {},
+    ResponseOk {
+        request_id: i32,
+        response: Response,
+    },
+    ResponseError {
+        request_id: i32,
+        error: serde_json::Value,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Response {
+    OpenStream {},
+    Execute {
+        result: serde_json::Value,
+    },
+    Describe {
+        result: DescribeResult,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+struct DescribeResult {
+    params: Vec<DescribeParam>,
+    cols: Vec<DescribeCol>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DescribeParam {
+    #[allow(dead_code)]
+    name: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DescribeCol {
+    name: String,
+}
+
+#[test]
+fn describe_within_open_transaction() {
+    let mut sim = turmoil::Builder::new()
+        .simulation_duration(Duration::from_secs(1000))
+        .build();
+    sim.host("primary", super::make_standalone_server);
+
+    sim.client("client", async move {
+        let req = "ws://primary:8080".into_client_request().unwrap();
+        let conn = TcpStream::connect("primary:8080").await.unwrap();
+        let (mut ws, _) = client_async(req, conn).await.unwrap();
+
+        let mut request_id = 0;
+
+        let hello = serde_json::to_string(&ClientMsg::Hello { jwt: None }).unwrap();
+        ws.send(tungstenite::Message::Text(hello)).await.unwrap();
+        let Some(tungstenite::Message::Text(msg)) = ws.try_next().await.unwrap() else {
+            panic!("expected hello response");
+        };
+        assert!(matches!(
+            serde_json::from_str::<ServerMsg>(&msg).unwrap(),
+            ServerMsg::HelloOk {}
+        ));
+
+        async fn roundtrip(
+            ws: &mut (impl futures::Sink<tungstenite::Message, Error = tungstenite::Error>
+                  + futures::Stream<Item = Result<tungstenite::Message, tungstenite::Error>>
+                  + Unpin),
+            request_id: &mut i32,
+            request: Request,
+        ) -> Response {
+            *request_id += 1;
+            let msg = ClientMsg::Request {
+                request_id: *request_id,
+                request,
+            };
+            ws.send(tungstenite::Message::Text(serde_json::to_string(&msg).unwrap()))
+                .await
+                .unwrap();
+            let Some(tungstenite::Message::Text(msg)) = ws.try_next().await.unwrap() else {
+                panic!("expected response");
+            };
+            match serde_json::from_str::<ServerMsg>(&msg).unwrap() {
+                ServerMsg::ResponseOk {
+                    request_id: got_id,
+                    response,
+                } => {
+                    assert_eq!(got_id, *request_id);
+                    response
+                }
+                ServerMsg::ResponseError { error, .. } => panic!("request failed: {error:?}"),
+                other => panic!("unexpected message: {other:?}"),
+            }
+        }
+
+        roundtrip(&mut ws, &mut request_id, Request::OpenStream { stream_id: 0 }).await;
+
+        roundtrip(
+            &mut ws,
+            &mut request_id,
+            Request::Execute {
+                stream_id: 0,
+                stmt: Stmt {
+                    sql: Some("BEGIN".into()),
+                    want_rows: Some(false),
+                },
+            },
+        )
+        .await;
+
+        roundtrip(
+            &mut ws,
+            &mut request_id,
+            Request::Execute {
+                stream_id: 0,
+                stmt: Stmt {
+                    sql: Some("CREATE TEMP TABLE describe_test (id INTEGER, name TEXT)".into()),
+                    want_rows: Some(false),
+                },
+            },
+        )
+        .await;
+
+        // describe_test only exists inside the still-open transaction on this stream. If
+        // `describe` ran against a different connection, this would fail with "no such table".
+        let response = roundtrip(
+            &mut ws,
+            &mut request_id,
+            Request::Describe {
+                stream_id: 0,
+                sql: Some("SELECT id, name FROM describe_test WHERE id = ?".into()),
+            },
+        )
+        .await;
+
+        let Response::Describe { result } = response else {
+            panic!("expected describe response, got {response:?}");
+        };
+
+        assert_eq!(result.params.len(), 1);
+        assert_eq!(
+            result.cols.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+            vec!["id", "name"]
+        );
+
+        Ok(())
+    });
+
+    sim.run().unwrap();
+}
@@ -6,6 +6,7 @@ use tempfile::tempdir;
 
 use crate::common::net::{init_tracing, SimServer, TestServer};
 mod batch;
+mod describe;
 mod transaction;
 
 async fn make_standalone_server() -> Result<(), Box<dyn std::error::Error>> {
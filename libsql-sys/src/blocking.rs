@@ -0,0 +1,102 @@
+//! An async facade over blocking sqlite connections.
+//!
+//! sqlite calls are blocking: they must not run directly on an async executor's worker threads.
+//! Client and server code has historically dealt with this by hand-rolling `spawn_blocking`
+//! calls at each call site (sometimes against the ambient tokio runtime, sometimes against a
+//! dedicated one sized for many concurrent connections), with no consistent way to give up on a
+//! call whose caller has gone away. [`BlockingPool`] and [`AsyncConnection`] centralize that
+//! pattern.
+
+use std::sync::{Arc, Mutex};
+
+use tokio::runtime::{Builder, Runtime};
+use tokio::task::JoinError;
+use tokio_util::sync::CancellationToken;
+
+/// A dedicated tokio runtime for running blocking sqlite calls off of an async executor's
+/// worker threads. Unlike spawning directly onto the ambient runtime, a dedicated pool keeps a
+/// database under heavy blocking load from starving unrelated async work in the same process.
+#[derive(Clone)]
+pub struct BlockingPool {
+    rt: Arc<Runtime>,
+}
+
+impl BlockingPool {
+    /// Builds a pool with `max_blocking_threads` worker threads. Pick this to comfortably cover
+    /// the number of connections expected to be blocked concurrently.
+    pub fn new(max_blocking_threads: usize) -> std::io::Result<Self> {
+        let rt = Builder::new_multi_thread()
+            .max_blocking_threads(max_blocking_threads)
+            .enable_all()
+            .build()?;
+        Ok(Self { rt: Arc::new(rt) })
+    }
+
+    /// Runs `f` on the pool, returning its result once it completes.
+    pub async fn spawn_blocking<F, T>(&self, f: F) -> Result<T, JoinError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.rt.spawn_blocking(f).await
+    }
+}
+
+/// Wraps a blocking connection `C`, dispatching calls against it onto a [`BlockingPool`] and
+/// racing them against a [`CancellationToken`] so a caller that's no longer interested in the
+/// result (a dropped request, a closed stream) can stop waiting on it.
+///
+/// Cancelling does not abort a call already running on the pool - sqlite gives us no safe way to
+/// interrupt a blocking FFI call from the outside - it only stops [`with_connection`] from
+/// waiting on it any further; the call still runs to completion and its result is discarded.
+///
+/// [`with_connection`]: AsyncConnection::with_connection
+pub struct AsyncConnection<C> {
+    inner: Arc<Mutex<C>>,
+    pool: BlockingPool,
+    cancellation: CancellationToken,
+}
+
+impl<C> AsyncConnection<C>
+where
+    C: Send + 'static,
+{
+    pub fn new(inner: C, pool: BlockingPool) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(inner)),
+            pool,
+            cancellation: CancellationToken::new(),
+        }
+    }
+
+    /// A token that fires when [`cancel`](Self::cancel) is called on this connection, for
+    /// callers that want to race their own work against it too.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
+
+    /// Cancels every call currently awaiting this connection via [`with_connection`].
+    ///
+    /// [`with_connection`]: AsyncConnection::with_connection
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
+    /// Runs `f` against the wrapped connection on the blocking pool. Returns `None` if this
+    /// connection is cancelled before `f` completes.
+    pub async fn with_connection<F, T>(&self, f: F) -> Option<Result<T, JoinError>>
+    where
+        F: FnOnce(&mut C) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let inner = self.inner.clone();
+        let task = self.pool.spawn_blocking(move || {
+            let mut conn = inner.lock().unwrap();
+            f(&mut conn)
+        });
+        tokio::select! {
+            _ = self.cancellation.cancelled() => None,
+            result = task => Some(result),
+        }
+    }
+}
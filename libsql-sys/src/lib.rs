@@ -61,6 +61,8 @@ pub mod ffi {
     }
 }
 
+#[cfg(feature = "async")]
+pub mod blocking;
 #[cfg(feature = "api")]
 pub mod connection;
 pub mod error;
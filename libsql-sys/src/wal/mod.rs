@@ -1,3 +1,42 @@
+//! A safe, public abstraction over sqlite's virtual WAL interface (`sqlite3_wal_methods`).
+//!
+//! Implementing [`WalManager`] and [`Wal`] lets a third party plug a custom write-ahead log into
+//! sqlite - for example one backed by an object store, or one that streams frames to a replica -
+//! without writing any `unsafe` FFI code: every pointer sqlite would otherwise hand over raw is
+//! wrapped in a sealed type ([`Sqlite3Db`], [`Sqlite3File`], [`Vfs`], [`PageHeaders`]) that only
+//! exposes safe methods. [`Sqlite3Wal`]/[`Sqlite3WalManager`] are the implementation backed by
+//! sqlite's own default WAL, useful as a reference and as the inner layer of a [`WrapWal`]
+//! decorator (see [`wrapper`]).
+//!
+//! # Lifecycle
+//!
+//! A [`WalManager`] is asked to [`open`](WalManager::open) a [`Wal`] once per database connection
+//! that needs one; the returned `Wal` is then driven by sqlite roughly in this order, for as long
+//! as the connection is alive:
+//!
+//! 1. [`begin_read_txn`](Wal::begin_read_txn) starts a read transaction; sqlite then calls
+//!    [`find_frame`](Wal::find_frame) and [`read_frame`](Wal::read_frame)/
+//!    [`read_frame_raw`](Wal::read_frame_raw) to resolve pages against the WAL, falling back to
+//!    the main database file for pages the WAL doesn't have. [`end_read_txn`](Wal::end_read_txn)
+//!    closes it.
+//! 2. [`begin_write_txn`](Wal::begin_write_txn) starts a write transaction. Pages modified during
+//!    the transaction are appended with [`insert_frames`](Wal::insert_frames), which may be called
+//!    several times before a commit; the call that commits returns the number of frames written
+//!    for that transaction. A rolled-back transaction is unwound with [`undo`](Wal::undo), and
+//!    [`savepoint`](Wal::savepoint)/[`savepoint_undo`](Wal::savepoint_undo) bound partial rollback
+//!    within it. [`end_write_txn`](Wal::end_write_txn) releases the write lock.
+//! 3. [`checkpoint`](Wal::checkpoint) may be called (independently of any read/write transaction
+//!    above) to fold WAL frames back into the main database file.
+//! 4. [`close`](WalManager::close) is called once the connection is done with the WAL, followed by
+//!    [`destroy`](WalManager::destroy) on the manager itself when it is no longer needed by any
+//!    connection.
+//!
+//! Implementors are not required to perform I/O synchronously with each call - e.g. a
+//! network-backed WAL may buffer frames from [`insert_frames`](Wal::insert_frames) and flush them
+//! out of band - but must make freshly inserted frames visible to subsequent
+//! [`find_frame`](Wal::find_frame)/[`read_frame`](Wal::read_frame) calls within the same
+//! connection, since sqlite relies on read-your-writes within a transaction.
+
 use std::ffi::{c_int, CStr};
 use std::num::NonZeroU32;
 
@@ -16,11 +55,17 @@ pub use ffi::make_wal_manager;
 
 use self::wrapper::{WalWrapper, WrapWal};
 
+/// Factory for [`Wal`] instances, one per database connection. See the [module-level
+/// documentation](self) for the lifecycle of the `Wal` it produces.
 pub trait WalManager {
     type Wal: Wal;
 
+    /// Whether the WAL-index (`-shm` file) should be backed by shared memory. Implementations
+    /// that don't use sqlite's default shared-memory WAL-index (e.g. because frames aren't kept
+    /// in a local mmap-able file at all) should return `false`.
     fn use_shared_memory(&self) -> bool;
 
+    /// Opens the WAL for `db_path`, called once per connection before any other `Wal` method.
     fn open(
         &self,
         vfs: &mut Vfs,
@@ -30,6 +75,8 @@ pub trait WalManager {
         db_path: &CStr,
     ) -> Result<Self::Wal>;
 
+    /// Closes `wal`, called once the connection is done with it. `scratch`, when provided, is a
+    /// buffer implementations may use for a final checkpoint instead of allocating their own.
     fn close(
         &self,
         wal: &mut Self::Wal,
@@ -38,9 +85,12 @@ pub trait WalManager {
         scratch: Option<&mut [u8]>,
     ) -> Result<()>;
 
+    /// Removes any on-disk WAL state for `db_path` (e.g. when a database is being deleted).
     fn destroy_log(&self, vfs: &mut Vfs, db_path: &CStr) -> Result<()>;
+    /// Whether a WAL log already exists for `db_path`.
     fn log_exists(&self, vfs: &mut Vfs, db_path: &CStr) -> Result<bool>;
 
+    /// Tears down the manager itself, once it is no longer needed by any connection.
     fn destroy(self)
     where
         Self: Sized;
@@ -137,6 +187,7 @@ impl PageHeaders {
     }
 }
 
+/// Called when a WAL operation would otherwise block on a lock held by another connection.
 pub trait BusyHandler {
     // Handle busy, and returns whether a retry should be performed
     fn handle_busy(&mut self) -> bool;
@@ -151,6 +202,7 @@ where
     }
 }
 
+/// Notified of each page rolled back by [`Wal::undo`].
 pub trait UndoHandler {
     fn handle_undo(&mut self, page_no: u32) -> Result<()>;
 }
@@ -164,6 +216,7 @@ pub enum CheckpointMode {
     Truncate = SQLITE_CHECKPOINT_TRUNCATE,
 }
 
+/// Notified of each frame backfilled into the main database file during [`Wal::checkpoint`].
 pub trait CheckpointCallback {
     fn frame(
         &mut self,
@@ -175,11 +228,14 @@ pub trait CheckpointCallback {
     fn finish(&mut self) -> Result<()>;
 }
 
+/// A WAL instance bound to a single database connection. See the [module-level
+/// documentation](self) for the order in which these methods are called.
 pub trait Wal {
     /// Set the WAL limit in pages
     fn limit(&mut self, size: i64);
     /// start a read transaction. Returns whether the in-memory page cache should be invalidated.
     fn begin_read_txn(&mut self) -> Result<bool>;
+    /// end the read transaction started by [`begin_read_txn`](Self::begin_read_txn).
     fn end_read_txn(&mut self);
 
     /// locate the frame containing page `page_no`
@@ -189,16 +245,26 @@ pub trait Wal {
     /// reads frame `frame_no` including its frame header into buffer.
     fn read_frame_raw(&mut self, frame_no: NonZeroU32, buffer: &mut [u8]) -> Result<()>;
 
+    /// Size of the database, in pages, as of the current read or write transaction.
     fn db_size(&self) -> u32;
 
+    /// start a write transaction.
     fn begin_write_txn(&mut self) -> Result<()>;
+    /// end the write transaction started by [`begin_write_txn`](Self::begin_write_txn).
     fn end_write_txn(&mut self) -> Result<()>;
 
+    /// Roll back the current write transaction, undoing frames inserted since it began.
+    /// `handler`, when provided, is notified of each page number being rolled back.
     fn undo<U: UndoHandler>(&mut self, handler: Option<&mut U>) -> Result<()>;
 
+    /// Record enough state to later undo back to this point in the write transaction with
+    /// [`savepoint_undo`](Self::savepoint_undo).
     fn savepoint(&mut self, rollback_data: &mut [u32]);
+    /// Roll back to a point previously recorded with [`savepoint`](Self::savepoint).
     fn savepoint_undo(&mut self, rollback_data: &mut [u32]) -> Result<()>;
 
+    /// Number of valid frames in the WAL. `locked` indicates whether the caller already holds
+    /// the WAL write lock.
     fn frame_count(&self, locked: i32) -> Result<u32>;
 
     /// Insert frames in the wal. On commit, returns the number of inserted frames for that
@@ -226,8 +292,12 @@ pub trait Wal {
         backfilled: Option<&mut i32>,
     ) -> Result<()>;
 
+    /// Enter (`op > 0`), leave (`op == 0`), or query (`op < 0`) exclusive locking mode.
     fn exclusive_mode(&mut self, op: c_int) -> Result<()>;
+    /// Whether this WAL keeps its working state in heap memory rather than relying on sqlite's
+    /// page cache, which changes how sqlite accounts for memory pressure.
     fn uses_heap_memory(&self) -> bool;
+    /// Update the `sqlite3` handle this WAL reports errors and interrupts against.
     fn set_db(&mut self, db: &mut Sqlite3Db);
 
     /// Return the value to pass to a sqlite3_wal_hook callback, the
@@ -236,5 +306,6 @@ pub trait Wal {
     /// the last call, then return 0.
     fn callback(&self) -> i32;
 
+    /// Number of frames currently in the WAL, including ones already checkpointed.
     fn frames_in_wal(&self) -> u32;
 }
@@ -0,0 +1,76 @@
+//! A synchronous facade over [`Connection`], providing the primitives a synchronous ORM backend
+//! (diesel, sea-orm's sync executor, ...) needs to drive libsql without dealing with our async
+//! API directly: statement execution, `last_insert_rowid`/`changes`, and savepoints for nested
+//! transactions.
+//!
+//! This crate doesn't ship `diesel::Connection` or `sea_orm::DatabaseConnection` impls itself —
+//! those live in their own backend crates — but everything they'd need to call into is here.
+
+use crate::params::IntoParams;
+use crate::{Connection, Result, Rows};
+
+/// A blocking wrapper around [`Connection`]. Must be constructed from within a Tokio runtime:
+/// every method blocks the calling thread on that runtime to drive the underlying async call.
+pub struct BlockingConnection {
+    conn: Connection,
+    rt: tokio::runtime::Handle,
+}
+
+impl BlockingConnection {
+    pub fn new(conn: Connection) -> Self {
+        Self {
+            conn,
+            rt: tokio::runtime::Handle::current(),
+        }
+    }
+
+    pub fn execute(&self, sql: &str, params: impl IntoParams) -> Result<u64> {
+        self.rt.block_on(self.conn.execute(sql, params))
+    }
+
+    pub fn query(&self, sql: &str, params: impl IntoParams) -> Result<Rows> {
+        self.rt.block_on(self.conn.query(sql, params))
+    }
+
+    /// The rowid of the last row inserted by this connection, as set by SQLite's
+    /// `last_insert_rowid()`. ORM backends use this to populate auto-increment primary keys
+    /// after an insert.
+    pub fn last_insert_rowid(&self) -> i64 {
+        self.conn.last_insert_rowid()
+    }
+
+    pub fn changes(&self) -> u64 {
+        self.conn.changes()
+    }
+
+    /// Opens a new savepoint. ORM backends typically use savepoints (rather than `BEGIN`) once a
+    /// transaction is already open, to support nested `transaction()` calls.
+    pub fn savepoint<'a>(&'a self, name: &str) -> Result<Savepoint<'a>> {
+        self.execute(&format!("SAVEPOINT {name}"), ())?;
+        Ok(Savepoint {
+            conn: self,
+            name: name.to_owned(),
+        })
+    }
+}
+
+/// A named savepoint opened on a [`BlockingConnection`]. Dropping it without calling
+/// [`Savepoint::release`] or [`Savepoint::rollback`] leaves it open, same as a bare
+/// `SAVEPOINT`/`RELEASE` pair in raw SQL.
+pub struct Savepoint<'a> {
+    conn: &'a BlockingConnection,
+    name: String,
+}
+
+impl<'a> Savepoint<'a> {
+    pub fn release(self) -> Result<()> {
+        self.conn.execute(&format!("RELEASE {}", self.name), ())?;
+        Ok(())
+    }
+
+    pub fn rollback(self) -> Result<()> {
+        self.conn
+            .execute(&format!("ROLLBACK TO {}", self.name), ())?;
+        Ok(())
+    }
+}
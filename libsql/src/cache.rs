@@ -0,0 +1,125 @@
+//! A simple read-through cache for read-heavy workloads on a remote or embedded replica
+//! connection, avoiding a round-trip for queries that can tolerate a short staleness window.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{Connection, Error, Result, Value};
+
+struct CacheEntry {
+    columns: Arc<Vec<Option<String>>>,
+    rows: Vec<Vec<Value>>,
+    inserted_at: Instant,
+}
+
+/// A row materialized out of a [`ReadThroughCache`]. Unlike [`crate::Row`] this is fully
+/// owned and detached from any connection or statement.
+#[derive(Debug, Clone)]
+pub struct CachedRow {
+    columns: Arc<Vec<Option<String>>>,
+    values: Vec<Value>,
+}
+
+impl CachedRow {
+    /// Number of columns in this row.
+    pub fn column_count(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Name of the column at `idx`, if known.
+    pub fn column_name(&self, idx: usize) -> Option<&str> {
+        self.columns.get(idx).and_then(|c| c.as_deref())
+    }
+
+    /// Value of the column at `idx`.
+    pub fn get_value(&self, idx: usize) -> Result<Value> {
+        self.values.get(idx).cloned().ok_or(Error::InvalidColumnIndex)
+    }
+}
+
+/// Wraps a [`Connection`] with an in-memory cache of query results, keyed by SQL text.
+/// Entries expire after a configurable TTL, and can be invalidated eagerly after a write so
+/// that read-your-writes semantics are preserved for callers that route their writes through
+/// the same cache.
+///
+/// Only parameter-less queries are supported, since caching by `(sql, params)` would require
+/// parameters to be hashable/comparable, which [`crate::params::Params`] does not guarantee.
+pub struct ReadThroughCache {
+    conn: Connection,
+    ttl: Duration,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl ReadThroughCache {
+    /// Wrap `conn`, caching results for up to `ttl`.
+    pub fn new(conn: Connection, ttl: Duration) -> Self {
+        Self {
+            conn,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run `sql`, returning the cached result if one is present and not older than the
+    /// configured TTL, otherwise querying the underlying connection and caching the result.
+    pub async fn query_cached(&self, sql: &str) -> Result<Vec<CachedRow>> {
+        if let Some(entry) = self.entries.lock().unwrap().get(sql) {
+            if entry.inserted_at.elapsed() < self.ttl {
+                return Ok(Self::materialize(entry));
+            }
+        }
+
+        let mut rows = self.conn.query(sql, ()).await?;
+        let columns = Arc::new(
+            (0..rows.column_count())
+                .map(|i| rows.column_name(i).map(str::to_string))
+                .collect::<Vec<_>>(),
+        );
+
+        let mut materialized = Vec::new();
+        while let Some(row) = rows.next().await? {
+            let values = (0..row.column_count())
+                .map(|i| row.get_value(i))
+                .collect::<Result<Vec<_>>>()?;
+            materialized.push(values);
+        }
+
+        let entry = CacheEntry {
+            columns,
+            rows: materialized,
+            inserted_at: Instant::now(),
+        };
+        let result = Self::materialize(&entry);
+        self.entries.lock().unwrap().insert(sql.to_string(), entry);
+        Ok(result)
+    }
+
+    /// Execute `sql` against the underlying connection and drop any cached entry for it.
+    pub async fn execute(&self, sql: &str, params: impl crate::params::IntoParams) -> Result<u64> {
+        let affected = self.conn.execute(sql, params).await?;
+        self.invalidate(sql);
+        Ok(affected)
+    }
+
+    /// Drop the cached entry for `sql`, if any.
+    pub fn invalidate(&self, sql: &str) {
+        self.entries.lock().unwrap().remove(sql);
+    }
+
+    /// Drop every cached entry.
+    pub fn invalidate_all(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    fn materialize(entry: &CacheEntry) -> Vec<CachedRow> {
+        entry
+            .rows
+            .iter()
+            .map(|values| CachedRow {
+                columns: entry.columns.clone(),
+                values: values.clone(),
+            })
+            .collect()
+    }
+}
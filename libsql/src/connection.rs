@@ -1,17 +1,35 @@
+use std::borrow::Cow;
 use std::collections::VecDeque;
 use std::fmt;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use crate::auth::{AuthContext, Authorization};
+use crate::function::{
+    Aggregate, AggregateFactory, Context, FunctionFlags, WindowAggregate, WindowAggregateFactory,
+};
 use crate::params::{IntoParams, Params};
 use crate::rows::Rows;
+use crate::schema::SchemaCache;
 use crate::statement::Statement;
 use crate::transaction::Transaction;
-use crate::{Result, TransactionBehavior};
+use crate::{Result, TransactionBehavior, Value};
 
 pub type AuthHook = Arc<dyn Fn(&AuthContext) -> Authorization>;
+pub type ScalarFn = Arc<dyn Fn(&Context) -> Result<Value> + Send + Sync>;
+pub type CollationFn = Arc<dyn Fn(&str, &str) -> std::cmp::Ordering + Send + Sync>;
+pub type UpdateHookFn = Arc<dyn Fn(UpdateHookAction, &str, &str, i64) + Send + Sync>;
+pub type CommitHookFn = Arc<dyn Fn() -> bool + Send + Sync>;
+pub type RollbackHookFn = Arc<dyn Fn() + Send + Sync>;
+
+/// The kind of row-level change reported to an [`update_hook`](Connection::update_hook) callback.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpdateHookAction {
+    Insert,
+    Update,
+    Delete,
+}
 
 #[async_trait::async_trait]
 pub(crate) trait Conn {
@@ -50,6 +68,65 @@ pub(crate) trait Conn {
     fn authorizer(&self, _hook: Option<AuthHook>) -> Result<()> {
         Err(crate::Error::AuthorizerNotSupported)
     }
+
+    fn create_scalar_function(
+        &self,
+        _name: &str,
+        _n_args: i32,
+        _flags: FunctionFlags,
+        _func: ScalarFn,
+    ) -> Result<()> {
+        Err(crate::Error::CreateScalarFunctionNotSupported)
+    }
+
+    fn create_aggregate_function(
+        &self,
+        _name: &str,
+        _n_args: i32,
+        _flags: FunctionFlags,
+        _factory: AggregateFactory,
+    ) -> Result<()> {
+        Err(crate::Error::CreateAggregateFunctionNotSupported)
+    }
+
+    fn create_window_function(
+        &self,
+        _name: &str,
+        _n_args: i32,
+        _flags: FunctionFlags,
+        _factory: WindowAggregateFactory,
+    ) -> Result<()> {
+        Err(crate::Error::CreateWindowFunctionNotSupported)
+    }
+
+    fn create_collation(&self, _name: &str, _compare: CollationFn) -> Result<()> {
+        Err(crate::Error::CreateCollationNotSupported)
+    }
+
+    fn update_hook(&self, _hook: Option<UpdateHookFn>) -> Result<()> {
+        Err(crate::Error::UpdateHookNotSupported)
+    }
+
+    fn commit_hook(&self, _hook: Option<CommitHookFn>) -> Result<()> {
+        Err(crate::Error::CommitHookNotSupported)
+    }
+
+    fn rollback_hook(&self, _hook: Option<RollbackHookFn>) -> Result<()> {
+        Err(crate::Error::RollbackHookNotSupported)
+    }
+}
+
+/// The outcome of a single statement within a batch: how many rows it affected and what
+/// [`Connection::last_insert_rowid`] was immediately after it ran.
+///
+/// Both fields are only tracked for `local` (in-process) connections today; batches run through
+/// `remote`/`sync`/`replication` connections report `None` for each statement. A failing
+/// statement still aborts the whole batch (as it always has) rather than being recorded here, so
+/// every entry in [`BatchRows::step_results`] corresponds to a statement that actually ran.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BatchStepResult {
+    pub rows_affected: Option<u64>,
+    pub last_insert_rowid: Option<i64>,
 }
 
 /// A set of rows returned from `execute_batch`/`execute_transactional_batch`. It is essentially
@@ -61,6 +138,7 @@ pub(crate) trait Conn {
 /// instead as this is optimized better for memory usage.
 pub struct BatchRows {
     inner: VecDeque<Option<Rows>>,
+    results: Vec<BatchStepResult>,
     skip_last_amt: usize,
 }
 
@@ -69,22 +147,37 @@ impl BatchRows {
     pub(crate) fn empty() -> Self {
         Self {
             inner: VecDeque::new(),
+            results: Vec::new(),
             skip_last_amt: 0,
         }
     }
 
     #[cfg(any(feature = "hrana", feature = "core"))]
     pub(crate) fn new(rows: Vec<Option<Rows>>) -> Self {
+        let results = vec![BatchStepResult::default(); rows.len()];
         Self {
             inner: rows.into(),
+            results,
+            skip_last_amt: 0,
+        }
+    }
+
+    #[cfg(feature = "core")]
+    pub(crate) fn new_with_results(rows: Vec<Option<Rows>>, results: Vec<BatchStepResult>) -> Self {
+        debug_assert_eq!(rows.len(), results.len());
+        Self {
+            inner: rows.into(),
+            results,
             skip_last_amt: 0,
         }
     }
 
     #[cfg(feature = "hrana")]
     pub(crate) fn new_skip_last(rows: Vec<Option<Rows>>, skip_last_amt: usize) -> Self {
+        let results = vec![BatchStepResult::default(); rows.len()];
         Self {
             inner: rows.into(),
+            results,
             skip_last_amt,
         }
     }
@@ -99,6 +192,12 @@ impl BatchRows {
 
         self.inner.pop_front()
     }
+
+    /// Per-statement [`BatchStepResult`]s for every statement that ran, in the same order as the
+    /// original SQL.
+    pub fn step_results(&self) -> &[BatchStepResult] {
+        &self.results
+    }
 }
 
 impl fmt::Debug for BatchRows {
@@ -107,13 +206,100 @@ impl fmt::Debug for BatchRows {
     }
 }
 
+/// Total rows affected across every statement in a batch, for trace reporting.
+fn batch_rows_affected(result: &Result<BatchRows>) -> Option<u64> {
+    let batch = result.as_ref().ok()?;
+    Some(
+        batch
+            .step_results()
+            .iter()
+            .filter_map(|r| r.rows_affected)
+            .sum(),
+    )
+}
+
+/// The [`Rows`] produced by each statement of a [`Connection::query_batch`] call, in the order
+/// the statements appear in the original SQL.
+pub struct ResultSets {
+    inner: BatchRows,
+}
+
+impl ResultSets {
+    /// Get the next statement's rows. Returns `None` once every statement has been consumed; the
+    /// inner `None` means that particular statement didn't produce any rows (e.g. it wasn't a
+    /// query).
+    pub fn next_result_set(&mut self) -> Option<Option<Rows>> {
+        self.inner.next_stmt_row()
+    }
+}
+
 /// A connection to some libsql database, this can be a remote one or a local one.
 #[derive(Clone)]
 pub struct Connection {
     pub(crate) conn: Arc<dyn Conn + Send + Sync>,
+    pub(crate) schema_cache: Arc<SchemaCache>,
+    pub(crate) query_tag: Arc<RwLock<Option<Arc<str>>>>,
+}
+
+/// A cloneable handle, obtained from [`Connection::interrupt_handle`], that can abort a
+/// connection's currently running statement from another thread or task -- e.g. to enforce a
+/// timeout on a long analytical query without dropping the whole connection. Local connections
+/// call `sqlite3_interrupt`; remote (Hrana) connections currently treat this as a no-op, the same
+/// as [`Connection::interrupt`]. This is a fire-and-forget signal: it doesn't wait for the
+/// interrupted statement to actually stop, and it outlives the [`Connection`] it was obtained
+/// from.
+#[derive(Clone)]
+pub struct InterruptHandle {
+    conn: Arc<dyn Conn + Send + Sync>,
+}
+
+impl InterruptHandle {
+    /// Aborts the connection's currently running statement, if any.
+    pub fn interrupt(&self) -> Result<()> {
+        self.conn.interrupt()
+    }
 }
 
 impl Connection {
+    /// Runs `fut`, the guts of a statement-shaped operation named `op` on `sql`, wrapped in a
+    /// `tracing` span when the `tracing` feature is enabled, and always reporting the outcome to
+    /// [`crate::trace::set_trace_callback`]'s raw callback. `rows` extracts the row count to
+    /// report from the operation's result, once it's available.
+    async fn traced<T>(
+        &self,
+        op: &'static str,
+        sql: &str,
+        rows: impl FnOnce(&Result<T>) -> Option<u64>,
+        fut: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let start = std::time::Instant::now();
+        #[cfg(feature = "tracing")]
+        let span = crate::trace::statement_span(op, sql);
+        #[cfg(feature = "tracing")]
+        let result = {
+            use tracing::Instrument as _;
+            fut.instrument(span.clone()).await
+        };
+        #[cfg(not(feature = "tracing"))]
+        let result = fut.await;
+
+        let rows = rows(&result);
+        let duration = start.elapsed();
+        #[cfg(feature = "tracing")]
+        {
+            span.record("rows", rows);
+            span.record("duration_ms", duration.as_millis() as u64);
+        }
+
+        crate::trace::invoke_trace_callback(crate::trace::TraceEvent {
+            op,
+            sql,
+            duration,
+            rows,
+        });
+        result
+    }
+
     /// Execute sql query provided some type that implements [`IntoParams`] returning
     /// on success the number of rows that were changed.
     ///
@@ -129,8 +315,22 @@ impl Connection {
     ///
     /// For more info on how to pass params check [`IntoParams`]'s docs.
     pub async fn execute(&self, sql: &str, params: impl IntoParams) -> Result<u64> {
-        tracing::trace!("executing `{}`", sql);
-        self.conn.execute(sql, params.into_params()?).await
+        let params = params.into_params()?;
+        let sql = self.tagged_sql(sql);
+        if crate::trace::should_log_statement() {
+            tracing::trace!(
+                "executing `{}` params={}",
+                crate::trace::traced_sql(&sql),
+                crate::trace::traced_params(&params)
+            );
+        }
+        self.traced(
+            "execute",
+            &sql,
+            |r: &Result<u64>| r.as_ref().ok().copied(),
+            self.conn.execute(&sql, params),
+        )
+        .await
     }
 
     /// Execute a batch set of statements.
@@ -140,8 +340,17 @@ impl Connection {
     /// This returns a `BatchRows` currently only the `remote`  and `local` connection supports this feature and
     /// all other connection types will return an empty set always.
     pub async fn execute_batch(&self, sql: &str) -> Result<BatchRows> {
-        tracing::trace!("executing batch `{}`", sql);
-        self.conn.execute_batch(sql).await
+        let sql = self.tagged_sql(sql);
+        if crate::trace::should_log_statement() {
+            tracing::trace!("executing batch `{}`", crate::trace::traced_sql(&sql));
+        }
+        self.traced(
+            "execute_batch",
+            &sql,
+            batch_rows_affected,
+            self.conn.execute_batch(&sql),
+        )
+        .await
     }
 
     /// Execute a batch set of statements atomically in a transaction.
@@ -151,8 +360,53 @@ impl Connection {
     /// This returns a `BatchRows` currently only the `remote` and `local` connection supports this feature and
     /// all other connection types will return an empty set always.
     pub async fn execute_transactional_batch(&self, sql: &str) -> Result<BatchRows> {
-        tracing::trace!("executing batch transactional `{}`", sql);
-        self.conn.execute_transactional_batch(sql).await
+        let sql = self.tagged_sql(sql);
+        if crate::trace::should_log_statement() {
+            tracing::trace!(
+                "executing batch transactional `{}`",
+                crate::trace::traced_sql(&sql)
+            );
+        }
+        self.traced(
+            "execute_transactional_batch",
+            &sql,
+            batch_rows_affected,
+            self.conn.execute_transactional_batch(&sql),
+        )
+        .await
+    }
+
+    /// Run a multi-statement SQL string and return the [`Rows`] produced by each statement, in
+    /// order, as a [`ResultSets`]. Like [`Connection::execute_batch`], the statements cannot take
+    /// parameters; use [`Connection::query`] for a single parameterized statement.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn run(conn: &libsql::Connection) {
+    /// let mut result_sets = conn
+    ///     .query_batch("SELECT * FROM foo; SELECT * FROM bar;")
+    ///     .await
+    ///     .unwrap();
+    /// while let Some(rows) = result_sets.next_result_set() {
+    ///     println!("{:?}", rows.is_some());
+    /// }
+    /// # }
+    /// ```
+    pub async fn query_batch(&self, sql: &str) -> Result<ResultSets> {
+        let sql = self.tagged_sql(sql);
+        if crate::trace::should_log_statement() {
+            tracing::trace!("querying batch `{}`", crate::trace::traced_sql(&sql));
+        }
+        let inner = self
+            .traced(
+                "query_batch",
+                &sql,
+                batch_rows_affected,
+                self.conn.execute_batch(&sql),
+            )
+            .await?;
+        Ok(ResultSets { inner })
     }
 
     /// Execute sql query provided some type that implements [`IntoParams`] returning
@@ -177,8 +431,12 @@ impl Connection {
 
     /// Prepares a cached statement.
     pub async fn prepare(&self, sql: &str) -> Result<Statement> {
-        tracing::trace!("preparing `{}`", sql);
-        self.conn.prepare(sql).await
+        let sql = self.tagged_sql(sql);
+        if crate::trace::should_log_statement() {
+            tracing::trace!("preparing `{}`", crate::trace::traced_sql(&sql));
+        }
+        self.traced("prepare", &sql, |_| None, self.conn.prepare(&sql))
+            .await
     }
 
     /// Begin a new transaction in `DEFERRED` mode, which is the default.
@@ -197,11 +455,107 @@ impl Connection {
         self.conn.transaction(tx_behavior).await
     }
 
+    /// Runs `f` in a [`TransactionBehavior::Deferred`] transaction, retrying the whole closure
+    /// with exponential backoff if it fails because of `SQLITE_BUSY` (another connection is
+    /// holding a conflicting lock). Commits on success, rolls back otherwise. Gives up and
+    /// returns the last error after 5 attempts.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn run(conn: &libsql::Connection) -> libsql::Result<()> {
+    /// conn.with_transaction(|tx| async move {
+    ///     tx.execute("UPDATE counters SET value = value + 1 WHERE id = 1", ())
+    ///         .await?;
+    ///     Ok(())
+    /// })
+    /// .await
+    /// # }
+    /// ```
+    pub async fn with_transaction<F, Fut, T>(&self, f: F) -> Result<T>
+    where
+        F: Fn(&Transaction) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        self.with_transaction_behavior(TransactionBehavior::Deferred, f)
+            .await
+    }
+
+    /// Like [`Connection::with_transaction`], but lets the transaction's [`TransactionBehavior`]
+    /// be specified.
+    pub async fn with_transaction_behavior<F, Fut, T>(
+        &self,
+        tx_behavior: TransactionBehavior,
+        f: F,
+    ) -> Result<T>
+    where
+        F: Fn(&Transaction) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        const MAX_ATTEMPTS: u32 = 5;
+
+        let mut attempt = 0;
+        loop {
+            let tx = self.transaction_with_behavior(tx_behavior).await?;
+            match f(&tx).await {
+                Ok(value) => {
+                    tx.commit().await?;
+                    return Ok(value);
+                }
+                Err(e) => {
+                    let _ = tx.rollback().await;
+
+                    attempt += 1;
+                    if !is_busy_error(&e) || attempt >= MAX_ATTEMPTS {
+                        return Err(e);
+                    }
+
+                    // a short, blocking backoff: `core` doesn't depend on an async runtime, so
+                    // there's no async timer available to await here, and a busy retry is rare
+                    // enough that stalling the current thread briefly is an acceptable trade-off.
+                    std::thread::sleep(std::time::Duration::from_millis(10 * (1 << attempt)));
+                }
+            }
+        }
+    }
+
+    /// Sets (or clears, with `None`) a tag appended as a trailing SQL comment to every statement
+    /// this connection runs from now on, e.g. `/* app=checkout,route=/pay */`. Intended to let a
+    /// server parse the comment back out of its statement log to attribute load per feature; the
+    /// tag itself has no effect on query execution. Applies to clones of this [`Connection`] too,
+    /// since they share the same underlying connection state.
+    ///
+    /// A literal `*/` in `tag` is stripped so it can't prematurely close the comment.
+    pub fn set_query_tag(&self, tag: impl Into<Option<String>>) {
+        let tag = tag.into().map(|tag| {
+            let cleaned = tag.replace("*/", "");
+            Arc::from(cleaned.trim())
+        });
+        *self.query_tag.write().unwrap() = tag;
+    }
+
+    /// Appends the current query tag, if any, to `sql` as a trailing comment.
+    fn tagged_sql<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        match self.query_tag.read().unwrap().as_deref() {
+            Some(tag) => Cow::Owned(format!("{sql} /* {tag} */")),
+            None => Cow::Borrowed(sql),
+        }
+    }
+
     /// Cancel ongoing operations and return at earliest opportunity.
     pub fn interrupt(&self) -> Result<()> {
         self.conn.interrupt()
     }
 
+    /// Returns a cloneable [`InterruptHandle`] that can be sent to another thread or task to
+    /// abort this connection's currently running statement. See [`InterruptHandle`] for which
+    /// connection types this actually cancels.
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        InterruptHandle {
+            conn: self.conn.clone(),
+        }
+    }
+
     pub fn busy_timeout(&self, timeout: Duration) -> Result<()> {
         self.conn.busy_timeout(timeout)
     }
@@ -266,9 +620,206 @@ impl Connection {
         self.conn.load_extension(dylib_path.as_ref(), entry_point)
     }
 
+    /// Registers (or, with `None`, clears) an authorizer callback invoked before executing each
+    /// action within a statement (e.g. reading a column, writing a table, running a pragma),
+    /// mirroring `sqlite3_set_authorizer`. Returning [`Authorization::Deny`] from the callback
+    /// aborts the statement with [`Error::SqliteFailure`](crate::Error::SqliteFailure);
+    /// [`Authorization::Ignore`] lets it continue but substitutes `NULL` for the denied read or a
+    /// no-op for the denied write.
+    ///
+    /// This is the mechanism for embedding applications to sandbox untrusted SQL passed through
+    /// the client, e.g. denying DDL or restricting which tables can be read or written.
+    ///
+    /// Only supported on local (core or embedded-replica) connections; remote connections return
+    /// [`Error::AuthorizerNotSupported`](crate::Error::AuthorizerNotSupported).
     pub fn authorizer(&self, hook: Option<AuthHook>) -> Result<()> {
         self.conn.authorizer(hook)
     }
+
+    /// Registers a custom scalar SQL function named `name`, taking `n_args` arguments (or a
+    /// variable number if negative), mirroring rusqlite's `Connection::create_scalar_function`.
+    ///
+    /// Only supported on local (core or embedded-replica) connections; remote connections return
+    /// [`Error::CreateScalarFunctionNotSupported`](crate::Error::CreateScalarFunctionNotSupported).
+    ///
+    /// ```rust,no_run
+    /// # async fn run() -> libsql::Result<()> {
+    /// use libsql::{Builder, FunctionFlags, Value};
+    ///
+    /// let db = Builder::new_local(":memory:").build().await?;
+    /// let conn = db.connect()?;
+    /// conn.create_scalar_function(
+    ///     "half",
+    ///     1,
+    ///     FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+    ///     |ctx| match ctx.get(0)? {
+    ///         Value::Integer(i) => Ok(Value::Real(*i as f64 / 2.0)),
+    ///         Value::Real(f) => Ok(Value::Real(f / 2.0)),
+    ///         _ => Err(libsql::Error::Misuse("half: expected a number".into())),
+    ///     },
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_scalar_function<F>(
+        &self,
+        name: &str,
+        n_args: i32,
+        flags: FunctionFlags,
+        func: F,
+    ) -> Result<()>
+    where
+        F: Fn(&Context) -> Result<Value> + Send + Sync + 'static,
+    {
+        self.conn
+            .create_scalar_function(name, n_args, flags, Arc::new(func))
+    }
+
+    /// Registers a custom aggregate SQL function named `name`, taking `n_args` arguments (or a
+    /// variable number if negative). `make_aggregate` is called once per group being aggregated
+    /// over to produce a fresh [`Aggregate`] accumulator for that group.
+    ///
+    /// Only supported on local (core or embedded-replica) connections; remote connections return
+    /// [`Error::CreateAggregateFunctionNotSupported`](crate::Error::CreateAggregateFunctionNotSupported).
+    ///
+    /// ```rust,no_run
+    /// # async fn run() -> libsql::Result<()> {
+    /// use libsql::{Aggregate, Builder, Context, FunctionFlags, Value};
+    ///
+    /// struct Sum(i64);
+    ///
+    /// impl Aggregate for Sum {
+    ///     fn step(&mut self, ctx: &Context) -> libsql::Result<()> {
+    ///         if let Value::Integer(i) = ctx.get(0)? {
+    ///             self.0 += i;
+    ///         }
+    ///         Ok(())
+    ///     }
+    ///
+    ///     fn finalize(&mut self) -> libsql::Result<Value> {
+    ///         Ok(Value::Integer(self.0))
+    ///     }
+    /// }
+    ///
+    /// let db = Builder::new_local(":memory:").build().await?;
+    /// let conn = db.connect()?;
+    /// conn.create_aggregate_function(
+    ///     "my_sum",
+    ///     1,
+    ///     FunctionFlags::SQLITE_UTF8,
+    ///     || Box::new(Sum(0)),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_aggregate_function<F>(
+        &self,
+        name: &str,
+        n_args: i32,
+        flags: FunctionFlags,
+        make_aggregate: F,
+    ) -> Result<()>
+    where
+        F: Fn() -> Box<dyn Aggregate> + Send + Sync + 'static,
+    {
+        self.conn
+            .create_aggregate_function(name, n_args, flags, Arc::new(make_aggregate))
+    }
+
+    /// Registers a custom window SQL function named `name`, taking `n_args` arguments (or a
+    /// variable number if negative), usable both as an aggregate and with an `OVER (...)` clause.
+    /// `make_aggregate` is called once per window to produce a fresh [`WindowAggregate`]
+    /// accumulator for that window.
+    ///
+    /// Only supported on local (core or embedded-replica) connections; remote connections return
+    /// [`Error::CreateWindowFunctionNotSupported`](crate::Error::CreateWindowFunctionNotSupported).
+    pub fn create_window_function<F>(
+        &self,
+        name: &str,
+        n_args: i32,
+        flags: FunctionFlags,
+        make_aggregate: F,
+    ) -> Result<()>
+    where
+        F: Fn() -> Box<dyn WindowAggregate> + Send + Sync + 'static,
+    {
+        self.conn
+            .create_window_function(name, n_args, flags, Arc::new(make_aggregate))
+    }
+
+    /// Registers a custom collating sequence named `name`, used to order and compare text values
+    /// when a query references it via `COLLATE name` (or a column/index declared with it).
+    /// `compare` is called with a pair of strings and must be a proper total ordering: consistent
+    /// (same inputs always compare the same way) and transitive.
+    ///
+    /// Only supported on local (core or embedded-replica) connections; remote connections return
+    /// [`Error::CreateCollationNotSupported`](crate::Error::CreateCollationNotSupported). An
+    /// embedded replica applies the collation to its local reads only - the primary must register
+    /// the same collation for writes that depend on it (e.g. `CREATE INDEX ... COLLATE name`) to
+    /// succeed there too.
+    ///
+    /// ```rust,no_run
+    /// # async fn run() -> libsql::Result<()> {
+    /// use libsql::Builder;
+    ///
+    /// let db = Builder::new_local(":memory:").build().await?;
+    /// let conn = db.connect()?;
+    /// conn.create_collation("nocase_ascii", |a, b| {
+    ///     a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_collation<C>(&self, name: &str, compare: C) -> Result<()>
+    where
+        C: Fn(&str, &str) -> std::cmp::Ordering + Send + Sync + 'static,
+    {
+        self.conn.create_collation(name, Arc::new(compare))
+    }
+
+    /// Registers (or, with `None`, clears) a callback invoked whenever a row is inserted, updated
+    /// or deleted in a rowid table, e.g. to invalidate an application-level cache for that row.
+    /// The callback receives the kind of change, the database name (`"main"`, ...), the table
+    /// name, and the affected `rowid`. It is not called for changes made to `WITHOUT ROWID`
+    /// tables, nor for changes undone by a later `ROLLBACK`.
+    ///
+    /// Only supported on local (core or embedded-replica) connections; remote connections return
+    /// [`Error::UpdateHookNotSupported`](crate::Error::UpdateHookNotSupported). On an embedded
+    /// replica, this only fires for writes made through this connection - frames applied by
+    /// [`Database::sync`](crate::Database::sync) don't go through SQL execution and so don't
+    /// trigger it.
+    pub fn update_hook(&self, hook: Option<UpdateHookFn>) -> Result<()> {
+        self.conn.update_hook(hook)
+    }
+
+    /// Registers (or, with `None`, clears) a callback invoked right before a transaction commits.
+    /// Returning `true` from the callback turns the commit into a rollback instead.
+    ///
+    /// Only supported on local (core or embedded-replica) connections; remote connections return
+    /// [`Error::CommitHookNotSupported`](crate::Error::CommitHookNotSupported).
+    pub fn commit_hook(&self, hook: Option<CommitHookFn>) -> Result<()> {
+        self.conn.commit_hook(hook)
+    }
+
+    /// Registers (or, with `None`, clears) a callback invoked whenever a transaction rolls back,
+    /// whether explicitly or because a statement failed.
+    ///
+    /// Only supported on local (core or embedded-replica) connections; remote connections return
+    /// [`Error::RollbackHookNotSupported`](crate::Error::RollbackHookNotSupported).
+    pub fn rollback_hook(&self, hook: Option<RollbackHookFn>) -> Result<()> {
+        self.conn.rollback_hook(hook)
+    }
+}
+
+/// Returns `true` if `err` represents `SQLITE_BUSY`, possibly one of its extended result codes
+/// (e.g. `SQLITE_BUSY_SNAPSHOT`), whether it came from a local connection or a remote one.
+fn is_busy_error(err: &crate::Error) -> bool {
+    let code = match err {
+        crate::Error::SqliteFailure(code, _) => *code,
+        crate::Error::RemoteSqliteFailure(_, extended_code, _) => *extended_code,
+        _ => return false,
+    };
+    code & 0xff == crate::ffi::SQLITE_BUSY
 }
 
 impl fmt::Debug for Connection {
@@ -30,6 +30,15 @@ cfg_core! {
             OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE
         }
     }
+
+    /// Progress reported by [`Database::backup_to`] after each batch of pages copied.
+    #[derive(Debug, Clone, Copy)]
+    pub struct BackupProgress {
+        /// Pages copied to the destination so far.
+        pub pages_done: i32,
+        /// Total pages in the source database, as of the last step.
+        pub pages_total: i32,
+    }
 }
 
 cfg_replication_or_sync! {
@@ -80,6 +89,8 @@ enum DbType {
     #[cfg(feature = "core")]
     Memory { db: crate::local::Database },
     #[cfg(feature = "core")]
+    DeserializedMemory { conn: crate::local::connection::Connection },
+    #[cfg(feature = "core")]
     File {
         path: String,
         flags: OpenFlags,
@@ -97,15 +108,17 @@ enum DbType {
         remote_writes: bool,
         read_your_writes: bool,
         url: String,
-        auth_token: String,
+        auth: crate::hrana::connection::AuthSource,
         connector: crate::util::ConnectorService,
     },
     #[cfg(feature = "remote")]
     Remote {
         url: String,
-        auth_token: String,
+        auth: crate::hrana::connection::AuthSource,
         connector: crate::util::ConnectorService,
         version: Option<String>,
+        default_headers: Vec<(http::HeaderName, http::HeaderValue)>,
+        encoding: crate::hrana::HranaEncoding,
     },
 }
 
@@ -116,6 +129,8 @@ impl fmt::Debug for DbType {
             #[cfg(feature = "core")]
             Self::Memory { .. } => write!(f, "Memory"),
             #[cfg(feature = "core")]
+            Self::DeserializedMemory { .. } => write!(f, "DeserializedMemory"),
+            #[cfg(feature = "core")]
             Self::File { .. } => write!(f, "File"),
             #[cfg(feature = "replication")]
             Self::Sync { .. } => write!(f, "Sync"),
@@ -137,6 +152,14 @@ pub struct Database {
     max_write_replication_index: std::sync::Arc<AtomicU64>,
 }
 
+impl Database {
+    /// Start building a [`Database`], e.g. `Database::builder().new_local(path).build().await`.
+    /// See [`Builder`] for the full set of variants and options.
+    pub fn builder() -> Builder<()> {
+        Builder::new()
+    }
+}
+
 cfg_core! {
     impl Database {
         /// Open an in-memory libsql database.
@@ -169,6 +192,40 @@ cfg_core! {
                 max_write_replication_index: Default::default(),
             })
         }
+
+        /// Open a file backed libsql database with flags and an encryption-at-rest key. This is
+        /// the escape hatch for callers who can't yet move to [`Builder`] but still need the
+        /// database file on disk (for example an embedded replica on a mobile device) to be
+        /// encrypted rather than sitting in plaintext.
+        #[deprecated = "Use the new `Builder` to construct `Database`"]
+        pub fn open_with_flags_and_encryption(
+            db_path: impl Into<String>,
+            flags: OpenFlags,
+            encryption_config: EncryptionConfig,
+        ) -> Result<Database> {
+            Ok(Database {
+                db_type: DbType::File {
+                    path: db_path.into(),
+                    flags,
+                    encryption_config: Some(encryption_config),
+                    skip_saftey_assert: false,
+                },
+                max_write_replication_index: Default::default(),
+            })
+        }
+
+        /// Open an in-memory database pre-populated from a serialized database image, as
+        /// produced by `sqlite3_serialize` (for example the bytes returned by a libSQL server's
+        /// `/serialize` endpoint). This lets callers such as edge functions load a snapshot
+        /// fetched over the network and query it immediately without touching a filesystem.
+        pub fn open_from_bytes(data: impl Into<Vec<u8>>) -> Result<Database> {
+            let conn = crate::local::connection::Connection::deserialize(data.into())?;
+
+            Ok(Database {
+                db_type: DbType::DeserializedMemory { conn },
+                max_write_replication_index: Default::default(),
+            })
+        }
     }
 }
 
@@ -258,6 +315,11 @@ cfg_replication! {
         }
 
         /// Open a local database file with the ability to sync from a remote database.
+        ///
+        /// This syncs over the gRPC replication protocol, whose auth token is attached once per
+        /// connection rather than per request, so unlike [`Database::open_remote`] it cannot pick
+        /// up a [`TokenProvider`](crate::hrana::TokenProvider)'s refreshed token mid-sync; pass a
+        /// long-lived token here, or re-open the database once a short-lived one expires.
         #[deprecated = "Use the new `Builder` to construct `Database`"]
         pub async fn open_with_remote_sync(
             db_path: impl Into<String>,
@@ -369,13 +431,15 @@ cfg_replication! {
                 svc,
                 db_path.into(),
                 url.into(),
+                Vec::new(),
                 token.into(),
                 version,
                 read_your_writes,
                 encryption_config.clone(),
                 sync_interval,
                 None,
-                None
+                None,
+                None,
             ).await?;
 
             Ok(Database {
@@ -388,6 +452,34 @@ cfg_replication! {
         /// Sync database from remote, and returns the committed frame_no after syncing, if
         /// applicable.
         pub async fn sync(&self) -> Result<Replicated> {
+            let start = std::time::Instant::now();
+            #[cfg(feature = "tracing")]
+            let span = crate::trace::statement_span("sync", "");
+            #[cfg(feature = "tracing")]
+            let result = {
+                use tracing::Instrument as _;
+                self.sync_inner().instrument(span.clone()).await
+            };
+            #[cfg(not(feature = "tracing"))]
+            let result = self.sync_inner().await;
+
+            let rows = result.as_ref().ok().map(|r| r.frames_synced as u64);
+            let duration = start.elapsed();
+            #[cfg(feature = "tracing")]
+            {
+                span.record("rows", rows);
+                span.record("duration_ms", duration.as_millis() as u64);
+            }
+            crate::trace::invoke_trace_callback(crate::trace::TraceEvent {
+                op: "sync",
+                sql: "",
+                duration,
+                rows,
+            });
+            result
+        }
+
+        async fn sync_inner(&self) -> Result<Replicated> {
             match &self.db_type {
                 #[cfg(feature = "replication")]
                 DbType::Sync { db, encryption_config: _ } => db.sync().await,
@@ -404,6 +496,21 @@ cfg_replication! {
             }
         }
 
+        /// Sync database from remote like [`Database::sync`], but keep pulling frames until
+        /// the replica is caught up and return a [`crate::replication::SyncReport`] describing
+        /// the amount of work done instead of a bare frame count.
+        ///
+        /// This is cancellation-safe: if the call is dropped or the process exits mid-sync, the
+        /// replica is left at the last durably applied frame and a subsequent call resumes from
+        /// there rather than starting over.
+        #[cfg(feature = "replication")]
+        pub async fn sync_with_report(&self) -> Result<crate::replication::SyncReport> {
+            match &self.db_type {
+                DbType::Sync { db, encryption_config: _ } => db.sync_with_report().await,
+                _ => Err(Error::SyncNotSupported(format!("{:?}", self.db_type))),
+            }
+        }
+
         /// Sync database from remote until it gets to a given replication_index or further,
         /// and returns the committed frame_no after syncing, if applicable.
         pub async fn sync_until(&self, replication_index: FrameNo) -> Result<Replicated> {
@@ -414,6 +521,25 @@ cfg_replication! {
             }
         }
 
+        /// Pause the background periodic sync task started via [`Builder::sync_interval`], if
+        /// any.
+        pub fn pause_sync(&self) -> Result<()> {
+            if let DbType::Sync { db, encryption_config: _ } = &self.db_type {
+                db.pause_sync()
+            } else {
+                Err(Error::SyncNotSupported(format!("{:?}", self.db_type)))
+            }
+        }
+
+        /// Resume the background periodic sync task after a [`Self::pause_sync`].
+        pub fn resume_sync(&self) -> Result<()> {
+            if let DbType::Sync { db, encryption_config: _ } = &self.db_type {
+                db.resume_sync()
+            } else {
+                Err(Error::SyncNotSupported(format!("{:?}", self.db_type)))
+            }
+        }
+
         /// Apply a set of frames to the database and returns the committed frame_no after syncing, if
         /// applicable.
         pub async fn sync_frames(&self, frames: crate::replication::Frames) -> Result<Option<FrameNo>> {
@@ -463,6 +589,96 @@ cfg_replication! {
            }
         }
 
+        /// Re-encrypt this database in place with `new_key`, replacing the key it was opened
+        /// with. Other connections to the database may keep reading and writing while the
+        /// rekey is in progress, since SQLite rewrites the pages under a single implicit
+        /// transaction rather than taking the database offline.
+        #[cfg(feature = "encryption")]
+        pub fn rekey(&self, new_key: impl AsRef<[u8]>) -> Result<()> {
+            match &self.db_type {
+                #[cfg(feature = "core")]
+                DbType::File {
+                    path,
+                    flags,
+                    encryption_config,
+                    ..
+                } => {
+                    let db = crate::local::Database::open(path, *flags)?;
+                    let conn = db.connect()?;
+
+                    // `sqlite3_rekey` re-encrypts the pages under whatever key the connection
+                    // was opened with, so the old key must be set with `sqlite3_key` first, the
+                    // same way `Connection::connect()` keys a fresh connection.
+                    if let Some(cfg) = encryption_config {
+                        if unsafe {
+                            libsql_sys::connection::set_encryption_cipher(
+                                conn.raw,
+                                cfg.cipher_id(),
+                            )
+                        } == -1
+                        {
+                            return Err(crate::Error::Misuse(
+                                "failed to set encryption cipher".to_string(),
+                            ));
+                        }
+                        if unsafe {
+                            libsql_sys::connection::set_encryption_key(
+                                conn.raw,
+                                &cfg.encryption_key,
+                            )
+                        } != crate::ffi::SQLITE_OK
+                        {
+                            return Err(crate::Error::Misuse(
+                                "failed to set encryption key".to_string(),
+                            ));
+                        }
+                    }
+
+                    let rc = libsql_sys::connection::reset_encryption_key(
+                        conn.raw,
+                        new_key.as_ref(),
+                    );
+                    if rc != crate::ffi::SQLITE_OK {
+                        return Err(crate::Error::ConnectionFailed(format!(
+                            "failed to rekey database: sqlite error {rc}"
+                        )));
+                    }
+                    Ok(())
+                }
+                #[cfg(feature = "replication")]
+                DbType::Sync { db, .. } => db.rekey(new_key.as_ref()),
+                _ => Err(Error::Misuse(
+                    "rekey is only supported on encrypted local and sync databases".to_string(),
+                )),
+            }
+        }
+
+        /// Copy this database into `dest_path` using SQLite's online backup API, which copies
+        /// the database page-by-page while other connections keep reading and writing it.
+        /// Unlike copying the file directly, this can't observe a WAL mid-checkpoint and
+        /// produce a torn copy.
+        ///
+        /// `progress` is invoked after each batch of pages with a [`BackupProgress`]; return
+        /// `false` from it to cancel the backup, in which case this returns `Err`.
+        pub fn backup_to(
+            &self,
+            dest_path: impl AsRef<str>,
+            progress: impl FnMut(BackupProgress) -> bool,
+        ) -> Result<()> {
+            match &self.db_type {
+                #[cfg(feature = "core")]
+                DbType::File { path, flags, .. } => {
+                    let db = crate::local::Database::open(path, *flags)?;
+                    db.backup_to(dest_path.as_ref(), progress)
+                }
+                #[cfg(feature = "replication")]
+                DbType::Sync { db, .. } => db.backup_to(dest_path.as_ref(), progress),
+                _ => Err(Error::Misuse(
+                    "backup_to is only supported on local and sync databases".to_string(),
+                )),
+            }
+        }
+
         /// Get the maximum replication index returned from a write performed using any connection created using this Database object.
         pub fn max_write_replication_index(&self) -> Option<FrameNo> {
             let index = self
@@ -482,6 +698,9 @@ impl Database {}
 cfg_remote! {
     impl Database {
         /// Open a remote based HTTP database using libsql's hrana protocol.
+        ///
+        /// This takes a static token; use `Builder::new_remote(..).token_provider(..)` instead if
+        /// the token is short-lived and needs to be refreshed on `401`.
         #[deprecated = "Use the new `Builder` to construct `Database`"]
         pub fn open_remote(url: impl Into<String>, auth_token: impl Into<String>) -> Result<Self> {
             let https = connector()?;
@@ -537,9 +756,11 @@ cfg_remote! {
             Ok(Database {
                 db_type: DbType::Remote {
                     url: url.into(),
-                    auth_token: auth_token.into(),
+                    auth: crate::hrana::connection::AuthSource::from(auth_token.into()),
                     connector: crate::util::ConnectorService::new(svc),
                     version,
+                    default_headers: Vec::new(),
+                    encoding: crate::hrana::HranaEncoding::Json,
                 },
                 max_write_replication_index: Default::default(),
             })
@@ -568,7 +789,24 @@ impl Database {
 
                 let conn = std::sync::Arc::new(LibsqlConnection { conn });
 
-                Ok(Connection { conn })
+                Ok(Connection {
+                    conn,
+                    schema_cache: Default::default(),
+                    query_tag: Default::default(),
+                })
+            }
+
+            #[cfg(feature = "core")]
+            DbType::DeserializedMemory { conn } => {
+                use crate::local::impls::LibsqlConnection;
+
+                let conn = std::sync::Arc::new(LibsqlConnection { conn: conn.clone() });
+
+                Ok(Connection {
+                    conn,
+                    schema_cache: Default::default(),
+                    query_tag: Default::default(),
+                })
             }
 
             #[cfg(feature = "core")]
@@ -616,7 +854,11 @@ impl Database {
 
                 let conn = std::sync::Arc::new(LibsqlConnection { conn });
 
-                Ok(Connection { conn })
+                Ok(Connection {
+                    conn,
+                    schema_cache: Default::default(),
+                    query_tag: Default::default(),
+                })
             }
 
             #[cfg(feature = "replication")]
@@ -662,7 +904,11 @@ impl Database {
                 );
                 let conn = std::sync::Arc::new(remote);
 
-                Ok(Connection { conn })
+                Ok(Connection {
+                    conn,
+                    schema_cache: Default::default(),
+                    query_tag: Default::default(),
+                })
             }
 
             #[cfg(feature = "sync")]
@@ -671,7 +917,7 @@ impl Database {
                 remote_writes,
                 read_your_writes,
                 url,
-                auth_token,
+                auth,
                 connector,
             } => {
                 use crate::{
@@ -699,10 +945,11 @@ impl Database {
                 if *remote_writes {
                     let synced = SyncedConnection {
                         local,
-                        remote: HttpConnection::new(
+                        remote: HttpConnection::new_with_auth(
                             url.clone(),
-                            auth_token.clone(),
+                            auth.clone(),
                             HttpSender::new(connector.clone(), None),
+                            crate::hrana::HranaEncoding::Json,
                         ),
                         read_your_writes: *read_your_writes,
                         context: db.sync_ctx.clone().unwrap(),
@@ -710,30 +957,46 @@ impl Database {
                     };
 
                     let conn = std::sync::Arc::new(synced);
-                    return Ok(Connection { conn });
+                    return Ok(Connection {
+                        conn,
+                        schema_cache: Default::default(),
+                        query_tag: Default::default(),
+                    });
                 }
 
                 let conn = std::sync::Arc::new(LibsqlConnection { conn: local });
-                Ok(Connection { conn })
+                Ok(Connection {
+                    conn,
+                    schema_cache: Default::default(),
+                    query_tag: Default::default(),
+                })
             }
 
             #[cfg(feature = "remote")]
             DbType::Remote {
                 url,
-                auth_token,
+                auth,
                 connector,
                 version,
+                default_headers,
+                encoding,
             } => {
                 let conn = std::sync::Arc::new(
-                    crate::hrana::connection::HttpConnection::new_with_connector(
+                    crate::hrana::connection::HttpConnection::new_with_connector_and_auth(
                         url,
-                        auth_token,
+                        auth.clone(),
                         connector.clone(),
                         version.as_ref().map(|s| s.as_str()),
+                        default_headers.clone(),
+                        *encoding,
                     ),
                 );
 
-                Ok(Connection { conn })
+                Ok(Connection {
+                    conn,
+                    schema_cache: Default::default(),
+                    query_tag: Default::default(),
+                })
             }
 
             _ => unreachable!("no database type set"),
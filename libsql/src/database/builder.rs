@@ -30,7 +30,20 @@ pub struct Builder<T = ()> {
     inner: T,
 }
 
+impl Default for Builder<()> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Builder<()> {
+    /// Start building a [`Database`]. Call one of `new_local`, `new_remote_replica`,
+    /// `new_local_replica`, `new_remote` or `new_synced_database` next to pick the variant to
+    /// build, then chain the shared setters before `.build()`.
+    pub fn new() -> Self {
+        Builder { inner: () }
+    }
+
     cfg_core! {
         /// Create a new local database.
         pub fn new_local(path: impl AsRef<std::path::Path>) -> Builder<Local> {
@@ -60,10 +73,18 @@ impl Builder<()> {
                         auth_token,
                         connector: None,
                         version: None,
+                        headers: Vec::new(),
+                        protobuf: false,
+                        #[cfg(feature = "tls")]
+                        tls_config: None,
+                        #[cfg(feature = "remote")]
+                        token_provider: None,
                     },
+                    failover_urls: Vec::new(),
                     encryption_config: None,
                     read_your_writes: true,
                     sync_interval: None,
+                    sync_error_callback: None,
                     http_request_callback: None,
                     namespace: None,
                     skip_safety_assert: false,
@@ -103,11 +124,20 @@ impl Builder<()> {
                         auth_token,
                         connector: None,
                         version: None,
+                        headers: Vec::new(),
+                        protobuf: false,
+                        #[cfg(feature = "tls")]
+                        tls_config: None,
+                        #[cfg(feature = "remote")]
+                        token_provider: None,
                     },
                     connector: None,
+                    #[cfg(feature = "tls")]
+                    tls_config: None,
                     read_your_writes: true,
                     remote_writes: false,
                     push_batch_size: 0,
+                    token_provider: None,
                 },
             }
         }
@@ -122,6 +152,11 @@ impl Builder<()> {
                     auth_token,
                     connector: None,
                     version: None,
+                    headers: Vec::new(),
+                    protobuf: false,
+                    #[cfg(feature = "tls")]
+                    tls_config: None,
+                    token_provider: None,
                 },
             }
         }
@@ -135,6 +170,12 @@ cfg_replication_or_remote_or_sync! {
         auth_token: String,
         connector: Option<crate::util::ConnectorService>,
         version: Option<String>,
+        headers: Vec<(http::HeaderName, http::HeaderValue)>,
+        protobuf: bool,
+        #[cfg(feature = "tls")]
+        tls_config: Option<crate::util::TlsConfig>,
+        #[cfg(feature = "remote")]
+        token_provider: Option<std::sync::Arc<dyn crate::hrana::TokenProvider>>,
     }
 }
 
@@ -219,9 +260,11 @@ cfg_replication! {
     pub struct RemoteReplica {
         path: std::path::PathBuf,
         remote: Remote,
+        failover_urls: Vec<String>,
         encryption_config: Option<EncryptionConfig>,
         read_your_writes: bool,
         sync_interval: Option<std::time::Duration>,
+        sync_error_callback: Option<crate::replication::SyncErrorCallback>,
         http_request_callback: Option<crate::util::HttpRequestCallback>,
         namespace: Option<String>,
         skip_safety_assert: bool,
@@ -271,6 +314,14 @@ cfg_replication! {
             self
         }
 
+        /// Provide additional replica URLs to fail over to, in order, if `url` (or the candidate
+        /// currently in use) becomes unreachable. Both the replication log sync source and the
+        /// write-forwarding target fail over together, since they talk to the same primary.
+        pub fn failover_urls(mut self, urls: Vec<String>) -> Builder<RemoteReplica> {
+            self.inner.failover_urls = urls;
+            self
+        }
+
         /// Set the duration at which the replicator will automatically call `sync` in the
         /// background. The sync will continue for the duration that the resulted `Database`
         /// type is alive for, once it is dropped the background task will get dropped and stop.
@@ -279,6 +330,16 @@ cfg_replication! {
             self
         }
 
+        /// Set a callback invoked whenever a background periodic sync (see
+        /// [`Self::sync_interval`]) fails. Has no effect unless `sync_interval` is also set.
+        pub fn sync_error_callback<F>(mut self, f: F) -> Builder<RemoteReplica>
+        where
+            F: Fn(&crate::Error) + Send + Sync + 'static,
+        {
+            self.inner.sync_error_callback = Some(std::sync::Arc::new(f));
+            self
+        }
+
         /// Set the duration at which the replicator will automatically call `sync` in the
         /// background. The sync will continue for the duration that the resulted `Database`
         /// type is alive for, once it is dropped the background task will get dropped and stop.
@@ -334,10 +395,18 @@ cfg_replication! {
                         auth_token,
                         connector,
                         version,
+                        headers: _,
+                        protobuf: _,
+                        #[cfg(feature = "tls")]
+                        tls_config: _,
+                        #[cfg(feature = "remote")]
+                        token_provider: _,
                     },
+                failover_urls,
                 encryption_config,
                 read_your_writes,
                 sync_interval,
+                sync_error_callback,
                 http_request_callback,
                 namespace,
                 skip_safety_assert,
@@ -423,6 +492,7 @@ cfg_replication! {
                     connector,
                     path,
                     url,
+                    failover_urls,
                     auth_token,
                     version,
                     read_your_writes,
@@ -430,6 +500,7 @@ cfg_replication! {
                     sync_interval,
                     http_request_callback,
                     namespace,
+                    sync_error_callback,
                 )
                 .await?
             } else {
@@ -440,6 +511,7 @@ cfg_replication! {
                         connector,
                         path,
                         url,
+                        failover_urls,
                         auth_token,
                         version,
                         read_your_writes,
@@ -447,6 +519,7 @@ cfg_replication! {
                         sync_interval,
                         http_request_callback,
                         namespace,
+                        sync_error_callback,
                     )
                     .await?
                 }
@@ -494,6 +567,12 @@ cfg_replication! {
                 auth_token,
                 connector,
                 version,
+                headers: _,
+                protobuf: _,
+                #[cfg(feature = "tls")]
+                tls_config: _,
+                #[cfg(feature = "remote")]
+                token_provider: _,
             }) = remote
             {
                 let connector = if let Some(connector) = connector {
@@ -539,9 +618,12 @@ cfg_sync! {
         flags: crate::OpenFlags,
         remote: Remote,
         connector: Option<crate::util::ConnectorService>,
+        #[cfg(feature = "tls")]
+        tls_config: Option<crate::util::TlsConfig>,
         remote_writes: bool,
         read_your_writes: bool,
         push_batch_size: u32,
+        token_provider: Option<std::sync::Arc<dyn crate::hrana::TokenProvider>>,
     }
 
     impl Builder<SyncedDatabase> {
@@ -578,6 +660,29 @@ cfg_sync! {
             self
         }
 
+        /// Use custom root CAs, a client certificate, or an SNI override for this connection's
+        /// TLS handshake, instead of the built-in connector's platform native root store.
+        ///
+        /// Has no effect if [`Self::connector`] is also called.
+        #[cfg(feature = "tls")]
+        pub fn tls_config(mut self, tls_config: crate::util::TlsConfig) -> Builder<SyncedDatabase> {
+            self.inner.tls_config = Some(tls_config);
+            self
+        }
+
+        /// Resolve the `Authorization` header for remote writes from a [`TokenProvider`] instead
+        /// of the static token passed to [`Builder::new_synced_database`], so a short-lived token
+        /// can be rotated transparently when the server returns `401`.
+        ///
+        /// [`TokenProvider`]: crate::hrana::TokenProvider
+        pub fn token_provider(
+            mut self,
+            token_provider: impl crate::hrana::TokenProvider + 'static,
+        ) -> Builder<SyncedDatabase> {
+            self.inner.token_provider = Some(std::sync::Arc::new(token_provider));
+            self
+        }
+
         /// Build a connection to a local database that can be synced to remote server.
         pub async fn build(self) -> Result<Database> {
             let SyncedDatabase {
@@ -589,11 +694,20 @@ cfg_sync! {
                         auth_token,
                         connector: _,
                         version: _,
+                        headers: _,
+                        protobuf: _,
+                        #[cfg(feature = "tls")]
+                        tls_config: _,
+                        #[cfg(feature = "remote")]
+                        token_provider: _,
                     },
                 connector,
+                #[cfg(feature = "tls")]
+                tls_config,
                 remote_writes,
                 read_your_writes,
                 push_batch_size,
+                token_provider,
             } = self.inner;
 
             let path = path.to_str().ok_or(crate::Error::InvalidUTF8Path)?.to_owned();
@@ -601,7 +715,18 @@ cfg_sync! {
             let https = if let Some(connector) = connector {
                 connector
             } else {
-                wrap_connector(super::connector()?)
+                #[cfg(feature = "tls")]
+                let from_tls_config = match &tls_config {
+                    Some(tls_config) => Some(tls_config.build_connector()?),
+                    None => None,
+                };
+                #[cfg(not(feature = "tls"))]
+                let from_tls_config: Option<crate::util::ConnectorService> = None;
+
+                match from_tls_config {
+                    Some(connector) => connector,
+                    None => wrap_connector(super::connector()?),
+                }
             };
             use tower::ServiceExt;
 
@@ -624,13 +749,21 @@ cfg_sync! {
                 db.sync_ctx.as_ref().unwrap().lock().await.set_push_batch_size(push_batch_size);
             }
 
+            // `open_local_with_offline_writes` above sets up the offline-write bookkeeping using
+            // the static token; it doesn't make HTTP requests itself, so it's unaffected by
+            // whether a `TokenProvider` was configured for the actual remote-write connection.
+            let auth = match token_provider {
+                Some(provider) => crate::hrana::connection::AuthSource::Provider(provider),
+                None => auth_token.into(),
+            };
+
             Ok(Database {
                 db_type: DbType::Offline {
                     db,
                     remote_writes,
                     read_your_writes,
                     url,
-                    auth_token,
+                    auth,
                     connector,
                 },
                 max_write_replication_index: Default::default(),
@@ -659,6 +792,50 @@ cfg_remote! {
             self
         }
 
+        /// Attach extra HTTP headers (tenant hints, trace IDs, feature flags, ...) to every
+        /// Hrana request this connection makes.
+        pub fn default_headers(mut self, options: crate::hrana::QueryOptions) -> Builder<Remote> {
+            self.inner = self.inner.headers(options.headers);
+            self
+        }
+
+        /// Use Hrana's protobuf encoding instead of JSON for pipeline requests made by this
+        /// connection, reducing serialization overhead and payload size.
+        ///
+        /// The cursor endpoint always uses JSON regardless of this setting.
+        ///
+        /// # Default
+        ///
+        /// This defaults to `false`.
+        pub fn protobuf(mut self, enabled: bool) -> Builder<Remote> {
+            self.inner = self.inner.protobuf(enabled);
+            self
+        }
+
+        /// Use custom root CAs, a client certificate, or an SNI override for this connection's
+        /// TLS handshake, instead of the built-in connector's platform native root store.
+        ///
+        /// Has no effect if [`Self::connector`] is also called.
+        #[cfg(feature = "tls")]
+        pub fn tls_config(mut self, tls_config: crate::util::TlsConfig) -> Builder<Remote> {
+            self.inner.tls_config = Some(tls_config);
+            self
+        }
+
+        /// Resolve the `Authorization` header from a [`TokenProvider`] instead of the static
+        /// token passed to [`Builder::new_remote`], so a short-lived token (e.g. a JWT) can be
+        /// rotated transparently when the server returns `401`, instead of the connection
+        /// failing once it expires.
+        ///
+        /// [`TokenProvider`]: crate::hrana::TokenProvider
+        pub fn token_provider(
+            mut self,
+            token_provider: impl crate::hrana::TokenProvider + 'static,
+        ) -> Builder<Remote> {
+            self.inner.token_provider = Some(std::sync::Arc::new(token_provider));
+            self
+        }
+
         /// Build the remote database client.
         pub async fn build(self) -> Result<Database> {
             let Remote {
@@ -666,27 +843,57 @@ cfg_remote! {
                 auth_token,
                 connector,
                 version,
+                headers,
+                protobuf,
+                #[cfg(feature = "tls")]
+                tls_config,
+                token_provider,
             } = self.inner;
 
             let connector = if let Some(connector) = connector {
                 connector
             } else {
-                let https = super::connector()?;
-                use tower::ServiceExt;
+                #[cfg(feature = "tls")]
+                let connector = match &tls_config {
+                    Some(tls_config) => Some(tls_config.build_connector()?),
+                    None => None,
+                };
+                #[cfg(not(feature = "tls"))]
+                let connector: Option<crate::util::ConnectorService> = None;
 
-                let svc = https
-                    .map_err(|e| e.into())
-                    .map_response(|s| Box::new(s) as Box<dyn crate::util::Socket>);
+                if let Some(connector) = connector {
+                    connector
+                } else {
+                    let https = super::connector()?;
+                    use tower::ServiceExt;
 
-                crate::util::ConnectorService::new(svc)
+                    let svc = https
+                        .map_err(|e| e.into())
+                        .map_response(|s| Box::new(s) as Box<dyn crate::util::Socket>);
+
+                    crate::util::ConnectorService::new(svc)
+                }
+            };
+
+            let encoding = if protobuf {
+                crate::hrana::HranaEncoding::Protobuf
+            } else {
+                crate::hrana::HranaEncoding::Json
+            };
+
+            let auth = match token_provider {
+                Some(provider) => crate::hrana::connection::AuthSource::Provider(provider),
+                None => auth_token.into(),
             };
 
             Ok(Database {
                 db_type: DbType::Remote {
                     url,
-                    auth_token,
+                    auth,
                     connector,
                     version,
+                    default_headers: headers,
+                    encoding,
                 },
                 max_write_replication_index: Default::default(),
             })
@@ -727,5 +934,15 @@ cfg_replication_or_remote_or_sync! {
             self.version = Some(version);
             self
         }
+
+        fn headers(mut self, headers: Vec<(http::HeaderName, http::HeaderValue)>) -> Remote {
+            self.headers = headers;
+            self
+        }
+
+        fn protobuf(mut self, protobuf: bool) -> Remote {
+            self.protobuf = protobuf;
+            self
+        }
     }
 }
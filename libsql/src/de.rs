@@ -84,3 +84,66 @@ pub fn from_row<'de, T: Deserialize<'de>>(row: &'de Row) -> Result<T, DeError> {
     let de = RowDeserializer { row };
     T::deserialize(de)
 }
+
+/// Wraps a column whose contents are a JSON document, so [`from_row`]/[`Row::deserialize`]
+/// parses it into `T` instead of treating the raw column value as `T` directly. SQLite has no
+/// native JSON column type, so this is how a `TEXT` column storing e.g. `{"role":"admin"}` maps
+/// onto a struct or enum field instead of a plain string.
+///
+/// ```rust,no_run
+/// # use libsql::de::Json;
+/// #[derive(serde::Deserialize)]
+/// struct Row {
+///     // a TEXT column containing a JSON document
+///     metadata: Json<serde_json::Value>,
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Json<T>(pub T);
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Json<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct JsonVisitor<T>(std::marker::PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for JsonVisitor<T> {
+            type Value = Json<T>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a string or bytes containing a JSON document")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                serde_json::from_str(v).map(Json).map_err(Error::custom)
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                self.visit_str(&v)
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                serde_json::from_slice(v).map(Json).map_err(Error::custom)
+            }
+
+            fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                self.visit_bytes(&v)
+            }
+        }
+
+        deserializer.deserialize_any(JsonVisitor(std::marker::PhantomData))
+    }
+}
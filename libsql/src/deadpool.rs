@@ -0,0 +1,38 @@
+//! A [`deadpool::managed::Manager`] adapter for pooling [`Connection`]s.
+
+use deadpool::async_trait;
+use deadpool::managed::{Metrics, RecycleError, RecycleResult};
+
+use crate::{Connection, Database};
+
+/// Manages a pool of libsql [`Connection`]s for `deadpool`. Connections are validated with a
+/// lightweight `SELECT 1` before being recycled back into the pool.
+pub struct DeadpoolManager {
+    db: Database,
+}
+
+impl DeadpoolManager {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+/// A `deadpool` pool of libsql connections managed by [`DeadpoolManager`].
+pub type Pool = deadpool::managed::Pool<DeadpoolManager>;
+
+#[async_trait]
+impl deadpool::managed::Manager for DeadpoolManager {
+    type Type = Connection;
+    type Error = crate::Error;
+
+    async fn create(&self) -> Result<Self::Type, Self::Error> {
+        self.db.connect()
+    }
+
+    async fn recycle(&self, conn: &mut Self::Type, _metrics: &Metrics) -> RecycleResult<Self::Error> {
+        conn.query("SELECT 1", ())
+            .await
+            .map_err(RecycleError::Backend)?;
+        Ok(())
+    }
+}
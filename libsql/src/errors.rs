@@ -23,6 +23,20 @@ pub enum Error {
     LoadExtensionNotSupported, // Not in rusqlite
     #[error("Authorizer is only supported in local databases.")]
     AuthorizerNotSupported, // Not in rusqlite
+    #[error("Registering scalar functions is only supported in local databases.")]
+    CreateScalarFunctionNotSupported, // Not in rusqlite
+    #[error("Registering aggregate functions is only supported in local databases.")]
+    CreateAggregateFunctionNotSupported, // Not in rusqlite
+    #[error("Registering window functions is only supported in local databases.")]
+    CreateWindowFunctionNotSupported, // Not in rusqlite
+    #[error("Registering collations is only supported in local databases.")]
+    CreateCollationNotSupported, // Not in rusqlite
+    #[error("Registering update hooks is only supported in local databases.")]
+    UpdateHookNotSupported, // Not in rusqlite
+    #[error("Registering commit hooks is only supported in local databases.")]
+    CommitHookNotSupported, // Not in rusqlite
+    #[error("Registering rollback hooks is only supported in local databases.")]
+    RollbackHookNotSupported, // Not in rusqlite
     #[error("Column not found: {0}")]
     ColumnNotFound(i32), // Not in rusqlite
     #[error("Hrana: `{0}`")]
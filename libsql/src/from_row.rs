@@ -0,0 +1,25 @@
+//! This module contains the [`FromRow`] trait used to map a query result [`crate::Row`] onto a
+//! struct. See `#[derive(FromRow)]` in the `libsql-macros` crate.
+
+use crate::{Result, Row};
+
+/// Maps a [`Row`] onto `Self`, field by field, by looking up each field's column by name instead
+/// of trusting a hand-maintained positional index to line up with the query -- the same class of
+/// bug `#[derive(IntoParams)]` avoids on the way in.
+///
+/// Most users should reach for `#[derive(FromRow)]` rather than implementing this by hand:
+///
+/// ```rust,no_run
+/// # async fn run(row: &libsql::Row) -> libsql::Result<()> {
+/// #[derive(libsql::FromRow)]
+/// struct Person {
+///     name: String,
+///     age: u64,
+/// }
+/// let person = Person::from_row(row)?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait FromRow: Sized {
+    fn from_row(row: &Row) -> Result<Self>;
+}
@@ -0,0 +1,105 @@
+use crate::{Error, Result, Value};
+
+/// Flags controlling how SQLite treats a function registered with
+/// [`Connection::create_scalar_function`](crate::Connection::create_scalar_function). The
+/// individual flags mirror `rusqlite::functions::FunctionFlags`, with the same numeric values, so
+/// a closure ported from rusqlite doesn't need its flags translated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FunctionFlags(i32);
+
+impl FunctionFlags {
+    /// The function only works with UTF-8 text arguments (the default).
+    pub const SQLITE_UTF8: FunctionFlags = FunctionFlags(0x0000_0001);
+    /// The function always returns the same result for the same arguments within one SQL
+    /// statement, letting SQLite fold repeated calls.
+    pub const SQLITE_DETERMINISTIC: FunctionFlags = FunctionFlags(0x0000_0800);
+    /// The function may only be invoked from top-level SQL, not from views, triggers, CHECK
+    /// constraints, or other schema items - useful for functions with side effects.
+    pub const SQLITE_DIRECTONLY: FunctionFlags = FunctionFlags(0x0008_0000);
+    /// The function is safe to run for arbitrary callers even under `PRAGMA trusted_schema =
+    /// OFF`, i.e. it has no side effects and its result depends only on its arguments.
+    pub const SQLITE_INNOCUOUS: FunctionFlags = FunctionFlags(0x0020_0000);
+
+    pub(crate) fn bits(self) -> std::os::raw::c_int {
+        self.0 as std::os::raw::c_int
+    }
+}
+
+impl Default for FunctionFlags {
+    #[inline]
+    fn default() -> FunctionFlags {
+        FunctionFlags::SQLITE_UTF8
+    }
+}
+
+impl std::ops::BitOr for FunctionFlags {
+    type Output = FunctionFlags;
+
+    #[inline]
+    fn bitor(self, rhs: FunctionFlags) -> FunctionFlags {
+        FunctionFlags(self.0 | rhs.0)
+    }
+}
+
+/// The arguments a scalar function registered with
+/// [`Connection::create_scalar_function`](crate::Connection::create_scalar_function) was called
+/// with.
+pub struct Context {
+    args: Vec<Value>,
+}
+
+impl Context {
+    pub(crate) fn new(args: Vec<Value>) -> Self {
+        Self { args }
+    }
+
+    /// The number of arguments the function was called with.
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    /// Returns `true` if the function was called with no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    /// Returns the `idx`-th argument.
+    pub fn get(&self, idx: usize) -> Result<&Value> {
+        self.args.get(idx).ok_or(Error::InvalidColumnIndex)
+    }
+}
+
+/// The per-group accumulator for a user-defined aggregate function registered with
+/// [`Connection::create_aggregate_function`](crate::Connection::create_aggregate_function). SQLite
+/// creates one instance per group being aggregated over, folding it row by row with [`step`](
+/// Aggregate::step) before consuming it with [`finalize`](Aggregate::finalize).
+pub trait Aggregate: Send {
+    /// Folds one row of the group into the accumulator. Called once per row; never called at all
+    /// for an empty group.
+    fn step(&mut self, cx: &Context) -> Result<()>;
+
+    /// Returns the group's result. Called exactly once, after the last call to `step` (or with no
+    /// prior `step` calls at all, for an empty group).
+    fn finalize(&mut self) -> Result<Value>;
+}
+
+/// Extends [`Aggregate`] so the function can also be used as a window function (`OVER (...)`),
+/// where SQLite asks for the current value mid-window with [`value`](WindowAggregate::value) and
+/// removes rows sliding out of the window with [`inverse`](WindowAggregate::inverse).
+///
+/// See <https://sqlite.org/windowfunctions.html#udfwinfunc> for the semantics SQLite expects.
+pub trait WindowAggregate: Aggregate {
+    /// Returns the accumulator's current value without consuming it, unlike `finalize`.
+    fn value(&self) -> Result<Value>;
+
+    /// Removes a row that's sliding out of the window from the accumulator.
+    fn inverse(&mut self, cx: &Context) -> Result<()>;
+}
+
+/// Creates a fresh, empty accumulator for one group. Called by SQLite once per group the first
+/// time a row is stepped into it.
+pub type AggregateFactory = std::sync::Arc<dyn Fn() -> Box<dyn Aggregate> + Send + Sync>;
+
+/// Creates a fresh, empty accumulator for one window. Called by SQLite once per window the first
+/// time a row is stepped into it.
+pub type WindowAggregateFactory = std::sync::Arc<dyn Fn() -> Box<dyn WindowAggregate> + Send + Sync>;
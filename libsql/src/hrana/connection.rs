@@ -1,6 +1,9 @@
 use crate::hrana::stream::{parse_hrana_urls, HranaStream};
-use crate::hrana::{HttpSend, Statement};
+use crate::hrana::{HranaEncoding, HranaError, HttpSend, Statement};
+#[cfg(feature = "remote")]
+use crate::hrana::TokenProvider;
 use crate::util::coerce_url_scheme;
+use std::fmt;
 use std::ops::Deref;
 use std::sync::Arc;
 
@@ -18,28 +21,104 @@ where
     inner: T,
     /// Hrana stream used to execute statements directly on the connection itself.
     conn_stream: HranaStream<T>,
-    /// URL of a pipeline API: `{base_url}/v3/pipeline`.
+    /// URL of a pipeline API: `{base_url}/v3/pipeline` or `{base_url}/v3-protobuf/pipeline`.
     pipeline_url: Arc<str>,
     /// URL of a cursor API: `{base_url}/v3/cursor`.
     cursor_url: Arc<str>,
-    /// Authentication token.
-    auth: Arc<str>,
+    /// Source of the `Authorization` header value.
+    auth: AuthSource,
+    /// Wire encoding used for the pipeline API.
+    encoding: HranaEncoding,
+}
+
+/// Where the `Authorization` header value for a Hrana HTTP connection comes from: a fixed
+/// token, or a [`TokenProvider`] queried before every request (and again on `401`) so a
+/// rotating credential can be refreshed without rebuilding the connection.
+#[derive(Clone)]
+pub(crate) enum AuthSource {
+    Token(Arc<str>),
+    #[cfg(feature = "remote")]
+    Provider(Arc<dyn TokenProvider>),
+}
+
+impl AuthSource {
+    pub(crate) async fn header(&self) -> crate::hrana::Result<Arc<str>> {
+        match self {
+            AuthSource::Token(header) => Ok(header.clone()),
+            #[cfg(feature = "remote")]
+            AuthSource::Provider(provider) => {
+                let token = provider
+                    .token()
+                    .await
+                    .map_err(|e| HranaError::Http(e.to_string()))?;
+                Ok(Arc::from(format!("Bearer {token}")))
+            }
+        }
+    }
+
+    /// A token fetched from a [`TokenProvider`] is worth asking for again after a `401`; a
+    /// fixed token never changes, so retrying it would just repeat the same failure.
+    pub(crate) fn is_refreshable(&self) -> bool {
+        #[cfg(feature = "remote")]
+        {
+            matches!(self, AuthSource::Provider(_))
+        }
+        #[cfg(not(feature = "remote"))]
+        {
+            false
+        }
+    }
+
+    /// The header value if it's already available without an async call, for use from
+    /// non-async contexts (e.g. `Drop`).
+    pub(crate) fn cached(&self) -> Option<Arc<str>> {
+        match self {
+            AuthSource::Token(header) => Some(header.clone()),
+            #[cfg(feature = "remote")]
+            AuthSource::Provider(_) => None,
+        }
+    }
+}
+
+impl fmt::Debug for AuthSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthSource::Token(_) => f.write_str("Token(..)"),
+            #[cfg(feature = "remote")]
+            AuthSource::Provider(_) => f.write_str("Provider(..)"),
+        }
+    }
+}
+
+impl From<String> for AuthSource {
+    fn from(token: String) -> Self {
+        AuthSource::Token(Arc::from(format!("Bearer {token}")))
+    }
 }
 
 impl<T> HttpConnection<T>
 where
     T: HttpSend,
 {
-    pub fn new(url: String, token: String, inner: T) -> Self {
+    pub fn new(url: String, token: String, inner: T, encoding: HranaEncoding) -> Self {
+        Self::new_with_auth(url, AuthSource::from(token), inner, encoding)
+    }
+
+    pub(crate) fn new_with_auth(
+        url: String,
+        auth: AuthSource,
+        inner: T,
+        encoding: HranaEncoding,
+    ) -> Self {
         // The `libsql://` protocol is an alias for `https://`.
         let base_url = coerce_url_scheme(url);
-        let (pipeline_url, cursor_url) = parse_hrana_urls(&base_url);
-        let auth: Arc<str> = Arc::from(format!("Bearer {token}"));
+        let (pipeline_url, cursor_url) = parse_hrana_urls(&base_url, encoding);
         let conn_stream = HranaStream::open(
             inner.clone(),
             pipeline_url.clone(),
             cursor_url.clone(),
             auth.clone(),
+            encoding,
         );
         HttpConnection(Arc::new(InnerClient {
             inner,
@@ -47,6 +126,7 @@ where
             cursor_url,
             conn_stream,
             auth,
+            encoding,
         }))
     }
 
@@ -77,6 +157,7 @@ where
             client.pipeline_url.clone(),
             client.cursor_url.clone(),
             client.auth.clone(),
+            client.encoding,
         )
     }
 
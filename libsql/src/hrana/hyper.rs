@@ -3,7 +3,7 @@ use crate::hrana::connection::HttpConnection;
 use crate::hrana::proto::{Batch, Stmt};
 use crate::hrana::stream::HranaStream;
 use crate::hrana::transaction::{HttpTransaction, TxScopeCounter};
-use crate::hrana::{bind_params, unwrap_err, HranaError, HttpSend, Result};
+use crate::hrana::{bind_params, unwrap_err, HranaEncoding, HranaError, HttpSend, Result};
 use crate::params::Params;
 use crate::transaction::Tx;
 use crate::util::ConnectorService;
@@ -11,7 +11,7 @@ use crate::{Error, Rows, Statement};
 use bytes::Bytes;
 use futures::future::BoxFuture;
 use futures::{Stream, TryStreamExt};
-use http::header::AUTHORIZATION;
+use http::header::{AUTHORIZATION, CONTENT_TYPE};
 use http::{HeaderValue, StatusCode};
 use hyper::body::HttpBody;
 use std::io::ErrorKind;
@@ -26,28 +26,67 @@ pub type ByteStream = Box<dyn Stream<Item = std::io::Result<Bytes>> + Send + Syn
 pub struct HttpSender {
     inner: hyper::Client<ConnectorService, hyper::Body>,
     version: HeaderValue,
+    extra_headers: Arc<Vec<(http::HeaderName, HeaderValue)>>,
+    encoding: HranaEncoding,
 }
 
 impl HttpSender {
     pub fn new(connector: ConnectorService, version: Option<&str>) -> Self {
+        Self::new_with_headers(connector, version, Vec::new())
+    }
+
+    pub fn new_with_headers(
+        connector: ConnectorService,
+        version: Option<&str>,
+        extra_headers: Vec<(http::HeaderName, HeaderValue)>,
+    ) -> Self {
         let ver = version.unwrap_or(env!("CARGO_PKG_VERSION"));
 
         let version = HeaderValue::try_from(format!("libsql-remote-{ver}")).unwrap();
 
-        let inner = hyper::Client::builder().build(connector);
+        // Probe idle keep-alive connections so a half-open one (e.g. behind a proxy that
+        // dropped the connection without a FIN) is noticed and replaced within seconds, rather
+        // than surfacing as a hang the next time it's reused.
+        let inner = hyper::Client::builder()
+            .pool_idle_timeout(Duration::from_secs(30))
+            .http2_keep_alive_interval(Duration::from_secs(20))
+            .http2_keep_alive_timeout(Duration::from_secs(10))
+            .build(connector);
+
+        Self {
+            inner,
+            version,
+            extra_headers: Arc::new(extra_headers),
+            encoding: HranaEncoding::Json,
+        }
+    }
 
-        Self { inner, version }
+    /// Selects the wire encoding used for pipeline requests sent through this sender.
+    pub(crate) fn with_encoding(mut self, encoding: HranaEncoding) -> Self {
+        self.encoding = encoding;
+        self
     }
 
     async fn send(
         self,
         url: Arc<str>,
         auth: Arc<str>,
-        body: String,
+        body: Bytes,
     ) -> Result<super::HttpBody<ByteStream>> {
-        let req = hyper::Request::post(url.as_ref())
+        let content_type = match self.encoding {
+            HranaEncoding::Json => "application/json",
+            HranaEncoding::Protobuf => "application/x-protobuf",
+        };
+        let mut req = hyper::Request::post(url.as_ref())
             .header(AUTHORIZATION, auth.as_ref())
-            .header("x-libsql-client-version", self.version.clone())
+            .header(CONTENT_TYPE, content_type)
+            .header("x-libsql-client-version", self.version.clone());
+
+        for (name, value) in self.extra_headers.iter() {
+            req = req.header(name, value);
+        }
+
+        let req = req
             .body(hyper::Body::from(body))
             .map_err(|err| HranaError::Http(format!("{:?}", err)))?;
 
@@ -59,6 +98,9 @@ impl HttpSender {
                 .await
                 .map_err(HranaError::from)?;
             let body = String::from_utf8(body.into()).unwrap();
+            if status == StatusCode::UNAUTHORIZED {
+                return Err(HranaError::Unauthorized(body));
+            }
             return Err(HranaError::Api(format!("status={}, body={}", status, body)));
         }
 
@@ -83,12 +125,12 @@ impl HttpSend for HttpSender {
     type Stream = super::HttpBody<ByteStream>;
     type Result = BoxFuture<'static, Result<Self::Stream>>;
 
-    fn http_send(&self, url: Arc<str>, auth: Arc<str>, body: String) -> Self::Result {
+    fn http_send(&self, url: Arc<str>, auth: Arc<str>, body: Bytes) -> Self::Result {
         let fut = self.clone().send(url, auth, body);
         Box::pin(fut)
     }
 
-    fn oneshot(self, url: Arc<str>, auth: Arc<str>, body: String) {
+    fn oneshot(self, url: Arc<str>, auth: Arc<str>, body: Bytes) {
         if let Ok(rt) = tokio::runtime::Handle::try_current() {
             rt.spawn(self.send(url, auth, body));
         } else {
@@ -110,8 +152,45 @@ impl HttpConnection<HttpSender> {
         connector: ConnectorService,
         version: Option<&str>,
     ) -> Self {
-        let inner = HttpSender::new(connector, version);
-        Self::new(url.into(), token.into(), inner)
+        Self::new_with_connector_and_headers(
+            url,
+            token,
+            connector,
+            version,
+            Vec::new(),
+            HranaEncoding::Json,
+        )
+    }
+
+    pub(crate) fn new_with_connector_and_headers(
+        url: impl Into<String>,
+        token: impl Into<String>,
+        connector: ConnectorService,
+        version: Option<&str>,
+        extra_headers: Vec<(http::HeaderName, HeaderValue)>,
+        encoding: HranaEncoding,
+    ) -> Self {
+        Self::new_with_connector_and_auth(
+            url,
+            crate::hrana::connection::AuthSource::from(token.into()),
+            connector,
+            version,
+            extra_headers,
+            encoding,
+        )
+    }
+
+    pub(crate) fn new_with_connector_and_auth(
+        url: impl Into<String>,
+        auth: crate::hrana::connection::AuthSource,
+        connector: ConnectorService,
+        version: Option<&str>,
+        extra_headers: Vec<(http::HeaderName, HeaderValue)>,
+        encoding: HranaEncoding,
+    ) -> Self {
+        let inner =
+            HttpSender::new_with_headers(connector, version, extra_headers).with_encoding(encoding);
+        Self::new_with_auth(url.into(), auth, inner, encoding)
     }
 }
 
@@ -149,6 +228,8 @@ impl Conn for HttpConnection<HttpSender> {
             inner: Box::new(tx.clone()),
             conn: crate::Connection {
                 conn: Arc::new(tx.stream().clone()),
+                schema_cache: Default::default(),
+                query_tag: Default::default(),
             },
             close: Some(Box::new(|| {
                 // make sure that Hrana connection is closed and all uncommitted changes
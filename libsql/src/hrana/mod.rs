@@ -4,6 +4,63 @@ pub mod connection;
 
 cfg_remote! {
     pub mod hyper;
+
+    /// Extra HTTP headers attached to every Hrana-over-HTTP request made by a `remote`
+    /// connection, e.g. a tenant hint or trace ID for the server's request log to pick up.
+    ///
+    /// Set via [`crate::Builder::default_headers`] when the connection is built; there's
+    /// currently no way to override headers for a single statement, since the request pipeline
+    /// doesn't thread per-call metadata down to the transport layer.
+    #[derive(Debug, Clone, Default)]
+    pub struct QueryOptions {
+        pub(crate) headers: Vec<(http::HeaderName, http::HeaderValue)>,
+    }
+
+    impl QueryOptions {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Attach a header, ignoring it (with a logged warning) if `name` or `value` isn't a
+        /// valid HTTP header name/value.
+        pub fn header(mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+            let name = name.as_ref();
+            match (
+                http::HeaderName::try_from(name),
+                http::HeaderValue::try_from(value.as_ref()),
+            ) {
+                (Ok(name), Ok(value)) => self.headers.push((name, value)),
+                _ => tracing::warn!("ignoring invalid custom header `{name}`"),
+            }
+            self
+        }
+    }
+
+    /// Supplies the bearer token used to authenticate a `remote`/`sync` connection's HTTP
+    /// requests, in place of a static token string.
+    ///
+    /// The provider is asked for a token before the first request made on a connection, and
+    /// again whenever a request comes back `401 Unauthorized`, so a short-lived token (e.g. a
+    /// JWT) can be rotated transparently instead of the connection failing once it expires.
+    #[async_trait::async_trait]
+    pub trait TokenProvider: Send + Sync {
+        /// Return the token to send as `Authorization: Bearer <token>`.
+        async fn token(&self) -> crate::Result<String>;
+    }
+}
+
+/// Wire encoding used for Hrana-over-HTTP pipeline requests, negotiated via the request's
+/// `Content-Type` and the URL suffix the server dispatches on.
+///
+/// Set via [`crate::Builder::protobuf`] for `remote`/`sync` connections; other transports (e.g.
+/// `cloudflare`) always use [`HranaEncoding::Json`]. The cursor endpoint always uses JSON
+/// regardless of this setting, since its response is a stream of newline-delimited entries that
+/// protobuf's length-delimited framing doesn't fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HranaEncoding {
+    #[default]
+    Json,
+    Protobuf,
 }
 
 mod cursor;
@@ -39,10 +96,10 @@ struct Cookie {
 pub trait HttpSend: Clone {
     type Stream: Stream<Item = std::io::Result<Bytes>> + Unpin;
     type Result: Future<Output = Result<Self::Stream>>;
-    fn http_send(&self, url: Arc<str>, auth: Arc<str>, body: String) -> Self::Result;
+    fn http_send(&self, url: Arc<str>, auth: Arc<str>, body: Bytes) -> Self::Result;
 
     /// Schedule sending a HTTP post request without waiting for the completion.
-    fn oneshot(self, url: Arc<str>, auth: Arc<str>, body: String);
+    fn oneshot(self, url: Arc<str>, auth: Arc<str>, body: Bytes);
 }
 
 pub enum HttpBody<S> {
@@ -91,10 +148,14 @@ pub enum HranaError {
     CursorError(CursorResponseError),
     #[error("json error: `{0}`")]
     Json(#[from] serde_json::Error),
+    #[error("protobuf decode error: `{0}`")]
+    Decode(#[from] prost::DecodeError),
     #[error("http error: `{0}`")]
     Http(String),
     #[error("api error: `{0}`")]
     Api(String),
+    #[error("unauthorized: `{0}`")]
+    Unauthorized(String),
 }
 
 #[derive(Debug, thiserror::Error)]
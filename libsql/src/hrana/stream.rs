@@ -1,6 +1,7 @@
+use crate::hrana::connection::AuthSource;
 use crate::hrana::cursor::{Cursor, CursorReq};
 use crate::hrana::proto::{Batch, BatchResult, DescribeResult, Stmt, StmtResult};
-use crate::hrana::{CursorResponseError, HranaError, HttpSend, Result};
+use crate::hrana::{CursorResponseError, HranaError, HranaEncoding, HttpSend, Result};
 use bytes::{Bytes, BytesMut};
 use futures::Stream;
 use libsql_hrana::proto::{
@@ -8,6 +9,7 @@ use libsql_hrana::proto::{
     GetAutocommitStreamReq, PipelineReqBody, PipelineRespBody, SequenceStreamReq,
     StoreSqlStreamReq, StreamRequest, StreamResponse, StreamResult,
 };
+use prost::Message as _;
 use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -52,7 +54,8 @@ where
         client: T,
         pipeline_url: Arc<str>,
         cursor_url: Arc<str>,
-        auth_token: Arc<str>,
+        auth: AuthSource,
+        encoding: HranaEncoding,
     ) -> Self {
         tracing::trace!("opening stream");
         HranaStream {
@@ -65,7 +68,8 @@ where
                     client,
                     pipeline_url,
                     cursor_url,
-                    auth_token,
+                    auth,
+                    encoding,
                     sql_id_generator: 0,
                     baton: None,
                 }),
@@ -299,7 +303,8 @@ where
     baton: Option<String>,
     pipeline_url: Arc<str>,
     cursor_url: Arc<str>,
-    auth_token: Arc<str>,
+    auth: AuthSource,
+    encoding: HranaEncoding,
     sql_id_generator: SqlId,
 }
 
@@ -312,20 +317,34 @@ where
         Ok(resp)
     }
 
+    // The cursor endpoint is always JSON: its response is a stream of newline-delimited
+    // entries, which doesn't fit protobuf's length-delimited framing, so `self.encoding` is
+    // never consulted here.
     pub async fn open_cursor(&mut self, batch: Batch) -> Result<Cursor<T::Stream>> {
         let msg = CursorReq {
             baton: self.baton.clone(),
             batch,
         };
-        let body = serde_json::to_string(&msg).map_err(HranaError::Json)?;
-        let stream = self
+        let body: Bytes = serde_json::to_string(&msg).map_err(HranaError::Json)?.into();
+        let auth = self.auth.header().await?;
+        let stream = match self
             .client
-            .http_send(self.cursor_url.clone(), self.auth_token.clone(), body)
-            .await?;
+            .http_send(self.cursor_url.clone(), auth, body.clone())
+            .await
+        {
+            Err(HranaError::Unauthorized(_)) if self.auth.is_refreshable() => {
+                let auth = self.auth.header().await?;
+                self.client
+                    .http_send(self.cursor_url.clone(), auth, body)
+                    .await?
+            }
+            other => other?,
+        };
         let (cursor, mut response) = Cursor::open(stream).await?;
         if let Some(base_url) = response.base_url.take() {
-            self.pipeline_url = Arc::from(format!("{base_url}/v3/pipeline"));
-            self.cursor_url = Arc::from(format!("{base_url}/v3/cursor"));
+            let (pipeline_url, cursor_url) = parse_hrana_urls(&base_url, self.encoding);
+            self.pipeline_url = pipeline_url;
+            self.cursor_url = cursor_url;
         }
         match response.baton.take() {
             None => {
@@ -354,15 +373,31 @@ where
             baton: self.baton.clone(),
             requests: Vec::from(requests),
         };
-        let body = serde_json::to_string(&msg).map_err(HranaError::Json)?;
-        let body = self
+        let body: Bytes = match self.encoding {
+            HranaEncoding::Json => serde_json::to_string(&msg).map_err(HranaError::Json)?.into(),
+            HranaEncoding::Protobuf => msg.encode_to_vec().into(),
+        };
+        let auth = self.auth.header().await?;
+        let resp = match self
             .client
-            .http_send(self.pipeline_url.clone(), self.auth_token.clone(), body)
-            .await?;
-        let body = stream_to_bytes(body).await?;
-        let mut response: PipelineRespBody = serde_json::from_slice(&body)?;
+            .http_send(self.pipeline_url.clone(), auth, body.clone())
+            .await
+        {
+            Err(HranaError::Unauthorized(_)) if self.auth.is_refreshable() => {
+                let auth = self.auth.header().await?;
+                self.client
+                    .http_send(self.pipeline_url.clone(), auth, body)
+                    .await?
+            }
+            other => other?,
+        };
+        let body = stream_to_bytes(resp).await?;
+        let mut response: PipelineRespBody = match self.encoding {
+            HranaEncoding::Json => serde_json::from_slice(&body)?,
+            HranaEncoding::Protobuf => PipelineRespBody::decode(body)?,
+        };
         if let Some(base_url) = response.base_url.take() {
-            let (pipeline_url, cursor_url) = parse_hrana_urls(&base_url);
+            let (pipeline_url, cursor_url) = parse_hrana_urls(&base_url, self.encoding);
             self.pipeline_url = pipeline_url;
             self.cursor_url = cursor_url;
         }
@@ -444,20 +479,27 @@ where
         if let Some(baton) = self.baton.take() {
             // only send a close request if stream was ever used to send the data
             tracing::trace!("closing client stream (baton: `{}`)", baton);
-            let req = serde_json::to_string(&PipelineReqBody {
-                baton: Some(baton),
-                requests: vec![StreamRequest::Close(CloseStreamReq {})],
-            })
-            .unwrap();
-            self.client
-                .clone()
-                .oneshot(self.pipeline_url.clone(), self.auth_token.clone(), req);
+            // `Drop` can't await a `TokenProvider`, so a provider-backed stream just skips this
+            // best-effort notification; the server will eventually expire the stream on its own.
+            if let Some(auth) = self.auth.cached() {
+                let msg = PipelineReqBody {
+                    baton: Some(baton),
+                    requests: vec![StreamRequest::Close(CloseStreamReq {})],
+                };
+                let req = match self.encoding {
+                    HranaEncoding::Json => serde_json::to_string(&msg).unwrap().into(),
+                    HranaEncoding::Protobuf => msg.encode_to_vec().into(),
+                };
+                self.client
+                    .clone()
+                    .oneshot(self.pipeline_url.clone(), auth, req);
+            }
             self.reset();
         }
     }
 }
 
-pub(super) fn parse_hrana_urls(url: &str) -> (Arc<str>, Arc<str>) {
+pub(super) fn parse_hrana_urls(url: &str, encoding: HranaEncoding) -> (Arc<str>, Arc<str>) {
     let (mut base_url, query) = match url.rfind('?') {
         Some(i) => url.split_at(i),
         None => (url, ""),
@@ -465,7 +507,11 @@ pub(super) fn parse_hrana_urls(url: &str) -> (Arc<str>, Arc<str>) {
     if base_url.ends_with('/') {
         base_url = &base_url[0..(base_url.len() - 1)];
     }
-    let pipeline_url = Arc::from(format!("{base_url}/v3/pipeline{query}"));
+    let pipeline_suffix = match encoding {
+        HranaEncoding::Json => "v3/pipeline",
+        HranaEncoding::Protobuf => "v3-protobuf/pipeline",
+    };
+    let pipeline_url = Arc::from(format!("{base_url}/{pipeline_suffix}{query}"));
     let cursor_url = Arc::from(format!("{base_url}/v3/cursor{query}"));
     (pipeline_url, cursor_url)
 }
@@ -0,0 +1,62 @@
+//! Helpers for running periodic integrity checks against a database.
+
+use crate::{Database, Result, Value};
+
+/// How thorough an [`Database::integrity_check`] should be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrityLevel {
+    /// Run `PRAGMA quick_check`, which skips the (expensive) index cross-checks.
+    Quick,
+    /// Run the full `PRAGMA integrity_check`.
+    Full,
+}
+
+/// The outcome of an [`Database::integrity_check`] call.
+#[derive(Debug, Clone, Default)]
+pub struct IntegrityReport {
+    /// Individual problem descriptions reported by SQLite. Empty means the database is sound.
+    pub findings: Vec<String>,
+}
+
+impl IntegrityReport {
+    /// Returns `true` if no problems were found.
+    pub fn is_ok(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+impl Database {
+    /// Run an integrity check against this database and return a structured report.
+    ///
+    /// This runs `PRAGMA integrity_check` (or `quick_check` for [`IntegrityLevel::Quick`])
+    /// and, for embedded replica databases, additionally checks that the locally recorded
+    /// replication index is not ahead of what the connection can see, which would indicate
+    /// corrupted replication metadata.
+    pub async fn integrity_check(&self, level: IntegrityLevel) -> Result<IntegrityReport> {
+        let conn = self.connect()?;
+
+        let pragma = match level {
+            IntegrityLevel::Quick => "PRAGMA quick_check",
+            IntegrityLevel::Full => "PRAGMA integrity_check",
+        };
+
+        let mut rows = conn.query(pragma, ()).await?;
+        let mut findings = Vec::new();
+        while let Some(row) = rows.next().await? {
+            if let Value::Text(msg) = row.get_value(0)? {
+                if msg != "ok" {
+                    findings.push(msg);
+                }
+            }
+        }
+
+        #[cfg(feature = "replication")]
+        if let Err(e) = self.replication_index().await {
+            if !matches!(e, crate::Error::SyncNotSupported(_)) {
+                findings.push(format!("replication metadata is unreadable: {e}"));
+            }
+        }
+
+        Ok(IntegrityReport { findings })
+    }
+}
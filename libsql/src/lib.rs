@@ -124,9 +124,22 @@ cfg_core! {
     pub use local::{version, version_number, RowsFuture};
     pub use database::OpenFlags;
 
-    pub use database::{Cipher, EncryptionConfig};
+    pub use database::{BackupProgress, Cipher, EncryptionConfig};
+
+    mod integrity;
+    pub use integrity::{IntegrityLevel, IntegrityReport};
+
+    mod cache;
+    pub use cache::{CachedRow, ReadThroughCache};
+
+    mod stmt_cache;
+    pub use stmt_cache::{CachedStatement, StatementCache, StatementCacheStats};
+
+    #[cfg(feature = "session")]
+    pub use local::session::{ConflictAction, ConflictHandler, ConflictType, Session};
 }
 
+pub mod from_row;
 pub mod params;
 
 cfg_sync! {
@@ -134,6 +147,10 @@ cfg_sync! {
     pub use database::SyncProtocol;
 }
 
+cfg_sync2! {
+    pub mod sync2;
+}
+
 cfg_replication! {
     pub mod replication;
 }
@@ -146,8 +163,19 @@ cfg_wasm! {
     pub mod wasm;
 }
 
+cfg_testing! {
+    pub mod testing;
+}
+
 mod util;
 
+#[cfg(any(
+    all(feature = "tls", feature = "replication"),
+    all(feature = "tls", feature = "remote"),
+    all(feature = "tls", feature = "sync")
+))]
+pub use util::TlsConfig;
+
 pub mod errors;
 pub use errors::Error;
 
@@ -156,10 +184,17 @@ pub use params::params_from_iter;
 mod auth;
 mod connection;
 mod database;
+mod function;
 mod load_extension_guard;
+mod schema;
+mod trace;
+
+pub use schema::{ColumnSchema, IndexSchema, TableSchema};
+pub use trace::{set_trace_callback, set_trace_policy, TraceCallback, TraceEvent, TracePolicy};
 
 cfg_parser! {
     mod parser;
+    pub use parser::KnownPragma;
 }
 
 mod rows;
@@ -170,20 +205,59 @@ mod value;
 #[cfg(feature = "serde")]
 pub mod de;
 
-pub use value::{Value, ValueRef, ValueType};
+pub use value::{CoercionPolicy, Value, ValueRef, ValueType};
 
 cfg_hrana! {
     mod hrana;
 }
 
+cfg_hrana! {
+    pub use hrana::HranaEncoding;
+}
+
+cfg_remote! {
+    pub use hrana::QueryOptions;
+}
+
+cfg_derive! {
+    pub use libsql_macros::{FromRow, IntoParams};
+}
+
+cfg_r2d2! {
+    mod r2d2;
+    pub use self::r2d2::R2D2Manager;
+}
+
+cfg_deadpool! {
+    mod deadpool;
+    pub use self::deadpool::{DeadpoolManager, Pool as DeadpoolPool};
+}
+
+cfg_blocking! {
+    mod blocking;
+    pub use self::blocking::{BlockingConnection, Savepoint};
+}
+
+cfg_pool! {
+    mod pool;
+    pub use self::pool::{Pool, PoolConfig, PooledConnection};
+}
+
 pub use self::{
     auth::{AuthAction, AuthContext, Authorization},
-    connection::{AuthHook, BatchRows, Connection},
+    connection::{
+        AuthHook, BatchRows, BatchStepResult, Connection, InterruptHandle, ResultSets,
+        UpdateHookAction,
+    },
     database::{Builder, Database},
+    function::{
+        Aggregate, AggregateFactory, Context, FunctionFlags, WindowAggregate,
+        WindowAggregateFactory,
+    },
     load_extension_guard::LoadExtensionGuard,
     rows::{Column, Row, Rows},
     statement::Statement,
-    transaction::{Transaction, TransactionBehavior},
+    transaction::{Transaction, TransactionBehavior, TransactionBuilder},
 };
 
 /// Convenient alias for `Result` using the `libsql::Error` type.
@@ -7,9 +7,16 @@ use crate::{Connection, Result};
 ///
 /// # Example
 ///
-/// ```ignore
-/// let _guard = LoadExtensionGuard::new(conn)?;
+/// ```no_run
+/// # async fn run() -> libsql::Result<()> {
+/// use libsql::{Builder, LoadExtensionGuard};
+///
+/// let db = Builder::new_local(":memory:").build().await?;
+/// let conn = db.connect()?;
+/// let _guard = LoadExtensionGuard::new(&conn)?;
 /// conn.load_extension("uuid", None)?;
+/// # Ok(())
+/// # }
 /// ```
 pub struct LoadExtensionGuard {
     pub(crate) conn: Arc<dyn Conn + Send + Sync>,
@@ -1,15 +1,21 @@
 #![allow(dead_code)]
 
 use crate::auth::{AuthAction, AuthContext, Authorization};
-use crate::connection::AuthHook;
+use crate::connection::{
+    AuthHook, CollationFn, CommitHookFn, RollbackHookFn, ScalarFn, UpdateHookAction, UpdateHookFn,
+};
+use crate::function::{
+    Aggregate, AggregateFactory, Context, FunctionFlags, WindowAggregate, WindowAggregateFactory,
+};
 use crate::local::rows::BatchedRows;
 use crate::params::Params;
 use crate::{connection::BatchRows, errors};
+use std::os::raw::c_void;
 use std::time::Duration;
 
 use super::{Database, Error, Result, Rows, RowsFuture, Statement, Transaction};
 
-use crate::TransactionBehavior;
+use crate::{TransactionBehavior, Value};
 
 use libsql_sys::ffi;
 use std::cell::RefCell;
@@ -26,6 +32,9 @@ pub struct Connection {
     pub(crate) writer: Option<crate::replication::Writer>,
 
     authorizer: RefCell<Option<AuthHook>>,
+    update_hook: RefCell<Option<UpdateHookFn>>,
+    commit_hook: RefCell<Option<CommitHookFn>>,
+    rollback_hook: RefCell<Option<RollbackHookFn>>,
 }
 
 impl Drop for Connection {
@@ -69,6 +78,9 @@ impl Connection {
             #[cfg(feature = "replication")]
             writer: db.writer()?,
             authorizer: RefCell::new(None),
+            update_hook: RefCell::new(None),
+            commit_hook: RefCell::new(None),
+            rollback_hook: RefCell::new(None),
         };
         #[cfg(feature = "sync")]
         if let Some(_) = db.sync_ctx {
@@ -96,9 +108,76 @@ impl Connection {
             #[cfg(feature = "replication")]
             writer: None,
             authorizer: RefCell::new(None),
+            update_hook: RefCell::new(None),
+            commit_hook: RefCell::new(None),
+            rollback_hook: RefCell::new(None),
         }
     }
 
+    /// Open a fresh in-memory connection and load it from a serialized database image, as
+    /// produced by `sqlite3_serialize` (for example the bytes returned by a libSQL server's
+    /// `/serialize` endpoint). `data` is copied into a buffer owned by SQLite so that SQLite is
+    /// free to resize or free it as the connection is used and eventually closed.
+    pub(crate) fn deserialize(data: Vec<u8>) -> Result<Connection> {
+        let mut raw = std::ptr::null_mut();
+        let err = unsafe {
+            ffi::sqlite3_open_v2(
+                std::ffi::CString::new(":memory:").unwrap().as_c_str().as_ptr() as *const _,
+                &mut raw,
+                crate::database::OpenFlags::default().bits() as c_int,
+                std::ptr::null(),
+            )
+        };
+        if err != ffi::SQLITE_OK {
+            return Err(Error::ConnectionFailed(format!(
+                "Unable to open in-memory database for deserialize: {err}",
+            )));
+        }
+
+        let size = data.len() as ffi::sqlite3_int64;
+        let buf = unsafe { ffi::sqlite3_malloc64(data.len() as ffi::sqlite3_uint64) };
+        if buf.is_null() {
+            unsafe { ffi::sqlite3_close_v2(raw) };
+            return Err(Error::ConnectionFailed(
+                "Unable to allocate buffer for deserialize".into(),
+            ));
+        }
+        unsafe { std::ptr::copy_nonoverlapping(data.as_ptr(), buf as *mut u8, data.len()) };
+
+        let err = unsafe {
+            ffi::sqlite3_deserialize(
+                raw,
+                b"main\0".as_ptr() as *const _,
+                buf as *mut u8,
+                size,
+                size,
+                (ffi::SQLITE_DESERIALIZE_FREEONCLOSE | ffi::SQLITE_DESERIALIZE_RESIZEABLE) as u32,
+            )
+        };
+        if err != ffi::SQLITE_OK {
+            // SQLITE_DESERIALIZE_FREEONCLOSE only takes effect once the deserialize call
+            // succeeds, so on failure we still own `buf` and must free it ourselves.
+            unsafe {
+                ffi::sqlite3_free(buf);
+                ffi::sqlite3_close_v2(raw);
+            }
+            return Err(Error::ConnectionFailed(format!(
+                "Unable to deserialize database image: {err}",
+            )));
+        }
+
+        Ok(Connection {
+            raw,
+            drop_ref: Arc::new(()),
+            #[cfg(feature = "replication")]
+            writer: None,
+            authorizer: RefCell::new(None),
+            update_hook: RefCell::new(None),
+            commit_hook: RefCell::new(None),
+            rollback_hook: RefCell::new(None),
+        })
+    }
+
     /// Disconnect from the database.
     pub fn disconnect(&mut self) {
         // Clean up the authorizer before closing
@@ -108,6 +187,13 @@ impl Connection {
                 tracing::error!("Failed to clear authorizer during disconnect");
             }
         }
+        // Clean up the update/commit/rollback hooks before closing, for the same reason: their
+        // user data is a pointer to this `Connection`, which is about to become invalid.
+        unsafe {
+            libsql_sys::ffi::sqlite3_update_hook(self.handle(), None, std::ptr::null_mut());
+            libsql_sys::ffi::sqlite3_commit_hook(self.handle(), None, std::ptr::null_mut());
+            libsql_sys::ffi::sqlite3_rollback_hook(self.handle(), None, std::ptr::null_mut());
+        }
         if Arc::get_mut(&mut self.drop_ref).is_some() {
             unsafe { libsql_sys::ffi::sqlite3_close_v2(self.raw) };
         }
@@ -172,6 +258,7 @@ impl Connection {
         let mut sql = sql.as_str();
 
         let mut batch_rows = Vec::new();
+        let mut batch_results = Vec::new();
 
         while !sql.is_empty() {
             let stmt = self.prepare(sql)?;
@@ -179,6 +266,11 @@ impl Connection {
             let tail = if !stmt.inner.raw_stmt.is_null() {
                 let returned_rows = stmt.step()?;
 
+                batch_results.push(crate::connection::BatchStepResult {
+                    rows_affected: Some(self.changes()),
+                    last_insert_rowid: Some(self.last_insert_rowid()),
+                });
+
                 let tail = stmt.tail();
 
                 // Check if there are rows to be extracted, we must do this upfront due to the lazy
@@ -259,7 +351,7 @@ impl Connection {
             sql = &sql[tail..];
         }
 
-        Ok(BatchRows::new(batch_rows))
+        Ok(BatchRows::new_with_results(batch_rows, batch_results))
     }
 
     fn execute_transactional_batch_inner<S>(&self, sql: S) -> Result<()>
@@ -503,6 +595,198 @@ impl Connection {
         Ok(())
     }
 
+    pub fn update_hook(&self, hook: Option<UpdateHookFn>) -> Result<()> {
+        *self.update_hook.borrow_mut() = hook.clone();
+
+        let (callback, user_data) = match hook {
+            Some(_) => {
+                let callback = update_hook_callback as unsafe extern "C" fn(_, _, _, _, _);
+                let user_data = self as *const Connection as *mut ::std::os::raw::c_void;
+                (Some(callback), user_data)
+            }
+            None => (None, std::ptr::null_mut()),
+        };
+
+        unsafe { libsql_sys::ffi::sqlite3_update_hook(self.handle(), callback, user_data) };
+        Ok(())
+    }
+
+    pub fn commit_hook(&self, hook: Option<CommitHookFn>) -> Result<()> {
+        *self.commit_hook.borrow_mut() = hook.clone();
+
+        let (callback, user_data) = match hook {
+            Some(_) => {
+                let callback = commit_hook_callback as unsafe extern "C" fn(_) -> _;
+                let user_data = self as *const Connection as *mut ::std::os::raw::c_void;
+                (Some(callback), user_data)
+            }
+            None => (None, std::ptr::null_mut()),
+        };
+
+        unsafe { libsql_sys::ffi::sqlite3_commit_hook(self.handle(), callback, user_data) };
+        Ok(())
+    }
+
+    pub fn rollback_hook(&self, hook: Option<RollbackHookFn>) -> Result<()> {
+        *self.rollback_hook.borrow_mut() = hook.clone();
+
+        let (callback, user_data) = match hook {
+            Some(_) => {
+                let callback = rollback_hook_callback as unsafe extern "C" fn(_);
+                let user_data = self as *const Connection as *mut ::std::os::raw::c_void;
+                (Some(callback), user_data)
+            }
+            None => (None, std::ptr::null_mut()),
+        };
+
+        unsafe { libsql_sys::ffi::sqlite3_rollback_hook(self.handle(), callback, user_data) };
+        Ok(())
+    }
+
+    pub fn create_scalar_function(
+        &self,
+        name: &str,
+        n_args: i32,
+        flags: FunctionFlags,
+        func: ScalarFn,
+    ) -> Result<()> {
+        let c_name = std::ffi::CString::new(name)
+            .map_err(|_| Error::Misuse("function name must not contain a NUL byte".to_string()))?;
+        // Ownership of the box passes to sqlite3: it's reclaimed by `destroy_scalar_function`
+        // when the function is dropped, overloaded, or the connection closes - including if this
+        // very call fails, since sqlite3_create_function_v2 always invokes the destructor exactly
+        // once regardless of outcome.
+        let user_data = Box::into_raw(Box::new(func)) as *mut c_void;
+
+        let rc = unsafe {
+            ffi::sqlite3_create_function_v2(
+                self.raw,
+                c_name.as_ptr(),
+                n_args,
+                flags.bits(),
+                user_data,
+                Some(call_scalar_function),
+                None,
+                None,
+                Some(destroy_scalar_function),
+            )
+        };
+
+        if rc != ffi::SQLITE_OK {
+            return Err(errors::Error::SqliteFailure(
+                rc,
+                format!("Failed to register scalar function {name:?}"),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn create_aggregate_function(
+        &self,
+        name: &str,
+        n_args: i32,
+        flags: FunctionFlags,
+        factory: AggregateFactory,
+    ) -> Result<()> {
+        let c_name = std::ffi::CString::new(name)
+            .map_err(|_| Error::Misuse("function name must not contain a NUL byte".to_string()))?;
+        // Ownership of the factory passes to sqlite3: it's reclaimed by `destroy_aggregate_factory`
+        // when the function is dropped, overloaded, or the connection closes.
+        let user_data = Box::into_raw(Box::new(factory)) as *mut c_void;
+
+        let rc = unsafe {
+            ffi::sqlite3_create_function_v2(
+                self.raw,
+                c_name.as_ptr(),
+                n_args,
+                flags.bits(),
+                user_data,
+                None,
+                Some(call_aggregate_step),
+                Some(call_aggregate_final),
+                Some(destroy_aggregate_factory),
+            )
+        };
+
+        if rc != ffi::SQLITE_OK {
+            return Err(errors::Error::SqliteFailure(
+                rc,
+                format!("Failed to register aggregate function {name:?}"),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn create_window_function(
+        &self,
+        name: &str,
+        n_args: i32,
+        flags: FunctionFlags,
+        factory: WindowAggregateFactory,
+    ) -> Result<()> {
+        let c_name = std::ffi::CString::new(name)
+            .map_err(|_| Error::Misuse("function name must not contain a NUL byte".to_string()))?;
+        // Ownership of the factory passes to sqlite3: it's reclaimed by `destroy_window_factory`
+        // when the function is dropped, overloaded, or the connection closes.
+        let user_data = Box::into_raw(Box::new(factory)) as *mut c_void;
+
+        let rc = unsafe {
+            ffi::sqlite3_create_window_function(
+                self.raw,
+                c_name.as_ptr(),
+                n_args,
+                flags.bits(),
+                user_data,
+                Some(call_window_step),
+                Some(call_window_final),
+                Some(call_window_value),
+                Some(call_window_inverse),
+                Some(destroy_window_factory),
+            )
+        };
+
+        if rc != ffi::SQLITE_OK {
+            return Err(errors::Error::SqliteFailure(
+                rc,
+                format!("Failed to register window function {name:?}"),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn create_collation(&self, name: &str, compare: CollationFn) -> Result<()> {
+        let c_name = std::ffi::CString::new(name)
+            .map_err(|_| Error::Misuse("collation name must not contain a NUL byte".to_string()))?;
+        // Ownership of the closure passes to sqlite3: it's reclaimed by `destroy_collation`
+        // when the collation is overridden, removed, or the connection closes - including if
+        // this very call fails, since sqlite3_create_collation_v2 always invokes the destructor
+        // exactly once regardless of outcome.
+        let user_data = Box::into_raw(Box::new(compare)) as *mut c_void;
+
+        let rc = unsafe {
+            ffi::sqlite3_create_collation_v2(
+                self.raw,
+                c_name.as_ptr(),
+                ffi::SQLITE_UTF8 as c_int,
+                user_data,
+                Some(call_collation),
+                Some(destroy_collation),
+            )
+        };
+
+        if rc != ffi::SQLITE_OK {
+            return Err(errors::Error::SqliteFailure(
+                rc,
+                format!("Failed to register collation {name:?}"),
+            ));
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn wal_checkpoint(&self, truncate: bool) -> Result<()> {
         let rc = unsafe { libsql_sys::ffi::sqlite3_wal_checkpoint_v2(self.handle(), std::ptr::null(), truncate as i32, std::ptr::null_mut(), std::ptr::null_mut()) };
         if rc != 0 {
@@ -663,6 +947,274 @@ unsafe extern "C" fn authorizer_callback(
     }
 }
 
+unsafe extern "C" fn update_hook_callback(
+    user_data: *mut ::std::os::raw::c_void,
+    action_code: ::std::os::raw::c_int,
+    database_name: *const ::std::os::raw::c_char,
+    table_name: *const ::std::os::raw::c_char,
+    rowid: i64,
+) {
+    let conn = user_data as *const Connection;
+    let hook = unsafe { (*conn).update_hook.borrow() };
+    let Some(hook) = &*hook else { return };
+
+    let action = match action_code {
+        ffi::SQLITE_INSERT => UpdateHookAction::Insert,
+        ffi::SQLITE_DELETE => UpdateHookAction::Delete,
+        _ => UpdateHookAction::Update,
+    };
+    let database_name = unsafe { std::ffi::CStr::from_ptr(database_name) }
+        .to_str()
+        .unwrap_or_default();
+    let table_name = unsafe { std::ffi::CStr::from_ptr(table_name) }
+        .to_str()
+        .unwrap_or_default();
+    hook(action, database_name, table_name, rowid);
+}
+
+unsafe extern "C" fn commit_hook_callback(user_data: *mut ::std::os::raw::c_void) -> ::std::os::raw::c_int {
+    let conn = user_data as *const Connection;
+    let hook = unsafe { (*conn).commit_hook.borrow() };
+    match &*hook {
+        Some(hook) => hook() as ::std::os::raw::c_int,
+        None => 0,
+    }
+}
+
+unsafe extern "C" fn rollback_hook_callback(user_data: *mut ::std::os::raw::c_void) {
+    let conn = user_data as *const Connection;
+    let hook = unsafe { (*conn).rollback_hook.borrow() };
+    if let Some(hook) = &*hook {
+        hook();
+    }
+}
+
+unsafe extern "C" fn call_scalar_function(
+    ctx: *mut ffi::sqlite3_context,
+    argc: c_int,
+    argv: *mut *mut ffi::sqlite3_value,
+) {
+    let func = ffi::sqlite3_user_data(ctx) as *const ScalarFn;
+    let args = (0..argc as isize)
+        .map(|i| value_from_sqlite(*argv.offset(i)))
+        .collect();
+    let cx = Context::new(args);
+    match (*func)(&cx) {
+        Ok(value) => set_scalar_result(ctx, &value),
+        Err(err) => scalar_result_error(ctx, &err.to_string()),
+    }
+}
+
+unsafe extern "C" fn destroy_scalar_function(user_data: *mut c_void) {
+    drop(Box::from_raw(user_data as *mut ScalarFn));
+}
+
+/// Reserves (and lazily creates) the slot SQLite keeps per aggregate group for `ctx`, sized to
+/// hold a single `Box<dyn Aggregate>`/`Box<dyn WindowAggregate>` fat pointer. Returns `None` if
+/// SQLite couldn't allocate the slot (out of memory).
+unsafe fn aggregate_slot(ctx: *mut ffi::sqlite3_context) -> Option<*mut *mut c_void> {
+    let pac = ffi::sqlite3_aggregate_context(ctx, std::mem::size_of::<*mut c_void>() as c_int)
+        as *mut *mut c_void;
+    if pac.is_null() {
+        None
+    } else {
+        Some(pac)
+    }
+}
+
+unsafe extern "C" fn call_aggregate_step(
+    ctx: *mut ffi::sqlite3_context,
+    argc: c_int,
+    argv: *mut *mut ffi::sqlite3_value,
+) {
+    let slot = match aggregate_slot(ctx) {
+        Some(slot) => slot,
+        None => return ffi::sqlite3_result_error_nomem(ctx),
+    };
+    if (*slot).is_null() {
+        let factory = ffi::sqlite3_user_data(ctx) as *const AggregateFactory;
+        *slot = Box::into_raw(Box::new((*factory)())) as *mut c_void;
+    }
+    let aggregate = &mut *(*slot as *mut Box<dyn Aggregate>);
+
+    let args = (0..argc as isize)
+        .map(|i| value_from_sqlite(*argv.offset(i)))
+        .collect();
+    let cx = Context::new(args);
+    if let Err(err) = aggregate.step(&cx) {
+        scalar_result_error(ctx, &err.to_string());
+    }
+}
+
+unsafe extern "C" fn call_aggregate_final(ctx: *mut ffi::sqlite3_context) {
+    // xFinal is called even for an empty group, where xStep never ran and the aggregate context
+    // was never allocated - fall back to a freshly made accumulator in that case.
+    let mut aggregate = match aggregate_slot(ctx) {
+        Some(slot) if !(*slot).is_null() => *Box::from_raw(*slot as *mut Box<dyn Aggregate>),
+        _ => {
+            let factory = ffi::sqlite3_user_data(ctx) as *const AggregateFactory;
+            (*factory)()
+        }
+    };
+    match aggregate.finalize() {
+        Ok(value) => set_scalar_result(ctx, &value),
+        Err(err) => scalar_result_error(ctx, &err.to_string()),
+    }
+}
+
+unsafe extern "C" fn destroy_aggregate_factory(user_data: *mut c_void) {
+    drop(Box::from_raw(user_data as *mut AggregateFactory));
+}
+
+unsafe extern "C" fn call_window_step(
+    ctx: *mut ffi::sqlite3_context,
+    argc: c_int,
+    argv: *mut *mut ffi::sqlite3_value,
+) {
+    let slot = match aggregate_slot(ctx) {
+        Some(slot) => slot,
+        None => return ffi::sqlite3_result_error_nomem(ctx),
+    };
+    if (*slot).is_null() {
+        let factory = ffi::sqlite3_user_data(ctx) as *const WindowAggregateFactory;
+        *slot = Box::into_raw(Box::new((*factory)())) as *mut c_void;
+    }
+    let aggregate = &mut *(*slot as *mut Box<dyn WindowAggregate>);
+
+    let args = (0..argc as isize)
+        .map(|i| value_from_sqlite(*argv.offset(i)))
+        .collect();
+    let cx = Context::new(args);
+    if let Err(err) = aggregate.step(&cx) {
+        scalar_result_error(ctx, &err.to_string());
+    }
+}
+
+unsafe extern "C" fn call_window_final(ctx: *mut ffi::sqlite3_context) {
+    let mut aggregate = match aggregate_slot(ctx) {
+        Some(slot) if !(*slot).is_null() => *Box::from_raw(*slot as *mut Box<dyn WindowAggregate>),
+        _ => {
+            let factory = ffi::sqlite3_user_data(ctx) as *const WindowAggregateFactory;
+            (*factory)()
+        }
+    };
+    match aggregate.finalize() {
+        Ok(value) => set_scalar_result(ctx, &value),
+        Err(err) => scalar_result_error(ctx, &err.to_string()),
+    }
+}
+
+unsafe extern "C" fn call_window_value(ctx: *mut ffi::sqlite3_context) {
+    let slot = match aggregate_slot(ctx) {
+        Some(slot) => slot,
+        None => return ffi::sqlite3_result_error_nomem(ctx),
+    };
+    if (*slot).is_null() {
+        let factory = ffi::sqlite3_user_data(ctx) as *const WindowAggregateFactory;
+        *slot = Box::into_raw(Box::new((*factory)())) as *mut c_void;
+    }
+    let aggregate = &*(*slot as *mut Box<dyn WindowAggregate>);
+    match aggregate.value() {
+        Ok(value) => set_scalar_result(ctx, &value),
+        Err(err) => scalar_result_error(ctx, &err.to_string()),
+    }
+}
+
+unsafe extern "C" fn call_window_inverse(
+    ctx: *mut ffi::sqlite3_context,
+    argc: c_int,
+    argv: *mut *mut ffi::sqlite3_value,
+) {
+    let slot = match aggregate_slot(ctx) {
+        Some(slot) if !(*slot).is_null() => slot,
+        _ => return,
+    };
+    let aggregate = &mut *(*slot as *mut Box<dyn WindowAggregate>);
+
+    let args = (0..argc as isize)
+        .map(|i| value_from_sqlite(*argv.offset(i)))
+        .collect();
+    let cx = Context::new(args);
+    if let Err(err) = aggregate.inverse(&cx) {
+        scalar_result_error(ctx, &err.to_string());
+    }
+}
+
+unsafe extern "C" fn destroy_window_factory(user_data: *mut c_void) {
+    drop(Box::from_raw(user_data as *mut WindowAggregateFactory));
+}
+
+unsafe extern "C" fn call_collation(
+    user_data: *mut c_void,
+    len1: c_int,
+    text1: *const c_void,
+    len2: c_int,
+    text2: *const c_void,
+) -> c_int {
+    let compare = user_data as *const CollationFn;
+    let s1 = String::from_utf8_lossy(std::slice::from_raw_parts(
+        text1 as *const u8,
+        len1 as usize,
+    ));
+    let s2 = String::from_utf8_lossy(std::slice::from_raw_parts(
+        text2 as *const u8,
+        len2 as usize,
+    ));
+    match (*compare)(&s1, &s2) {
+        std::cmp::Ordering::Less => -1,
+        std::cmp::Ordering::Equal => 0,
+        std::cmp::Ordering::Greater => 1,
+    }
+}
+
+unsafe extern "C" fn destroy_collation(user_data: *mut c_void) {
+    drop(Box::from_raw(user_data as *mut CollationFn));
+}
+
+unsafe fn value_from_sqlite(value: *mut ffi::sqlite3_value) -> Value {
+    match ffi::sqlite3_value_type(value) {
+        ffi::SQLITE_NULL => Value::Null,
+        ffi::SQLITE_INTEGER => Value::Integer(ffi::sqlite3_value_int64(value)),
+        ffi::SQLITE_FLOAT => Value::Real(ffi::sqlite3_value_double(value)),
+        ffi::SQLITE_TEXT => {
+            let text = ffi::sqlite3_value_text(value);
+            let len = ffi::sqlite3_value_bytes(value);
+            let bytes = std::slice::from_raw_parts(text.cast::<u8>(), len as usize);
+            Value::Text(String::from_utf8_lossy(bytes).into_owned())
+        }
+        ffi::SQLITE_BLOB => {
+            let blob = ffi::sqlite3_value_blob(value);
+            let len = ffi::sqlite3_value_bytes(value);
+            if len > 0 {
+                let bytes = std::slice::from_raw_parts(blob.cast::<u8>(), len as usize);
+                Value::Blob(bytes.to_vec())
+            } else {
+                Value::Blob(Vec::new())
+            }
+        }
+        _ => unreachable!("sqlite3_value_type returned invalid value"),
+    }
+}
+
+unsafe fn set_scalar_result(ctx: *mut ffi::sqlite3_context, value: &Value) {
+    match value {
+        Value::Null => ffi::sqlite3_result_null(ctx),
+        Value::Integer(i) => ffi::sqlite3_result_int64(ctx, *i),
+        Value::Real(f) => ffi::sqlite3_result_double(ctx, *f),
+        Value::Text(s) => {
+            ffi::sqlite3_result_text(ctx, s.as_ptr().cast(), s.len() as c_int, ffi::SQLITE_TRANSIENT());
+        }
+        Value::Blob(b) => {
+            ffi::sqlite3_result_blob(ctx, b.as_ptr().cast(), b.len() as c_int, ffi::SQLITE_TRANSIENT());
+        }
+    }
+}
+
+unsafe fn scalar_result_error(ctx: *mut ffi::sqlite3_context, msg: &str) {
+    let cstring = std::ffi::CString::new(msg).unwrap_or_default();
+    ffi::sqlite3_result_error(ctx, cstring.as_ptr(), -1);
+}
+
 pub(crate) struct WalInsertHandle<'a> {
     conn: &'a Connection,
     in_session: RefCell<bool>
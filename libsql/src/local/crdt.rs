@@ -0,0 +1,189 @@
+//! SQL functions implementing CRDT merge semantics for multi-writer, offline-first columns:
+//! grow-only counters, last-write-wins registers, and grow-only sets.
+//!
+//! Each function is pure, commutative, and idempotent, so merging is safe no matter how many
+//! times or in what order it runs - which is what lets independent offline writers converge
+//! without coordination. They compose with [`crate::sync2`]: a [`sync2::ConflictStrategy`] for a
+//! CRDT column calls the matching `crdt_*_merge` function (via a one-off `SELECT`) instead of
+//! picking a side outright.
+//!
+//! Counters and sets store their state as a JSON-encoded column that every writer updates with
+//! its own local change; merging combines two such states rather than two plain values, so the
+//! result also happens to be a legal state that's ready to merge again.
+//!
+//! [`sync2`]: crate::sync2
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_int, c_void};
+
+use libsql_sys::ffi;
+
+use super::{Connection, Result};
+
+unsafe fn arg_str<'a>(argv: *mut *mut ffi::sqlite3_value, i: isize) -> Option<&'a str> {
+    let v = *argv.offset(i);
+    let ptr = ffi::sqlite3_value_text(v);
+    if ptr.is_null() {
+        return None;
+    }
+    CStr::from_ptr(ptr as *const _).to_str().ok()
+}
+
+unsafe fn result_error(ctx: *mut ffi::sqlite3_context, msg: &str) {
+    let cstr = CString::new(msg).unwrap_or_default();
+    ffi::sqlite3_result_error(ctx, cstr.as_ptr(), -1);
+}
+
+unsafe fn result_text(ctx: *mut ffi::sqlite3_context, s: &str) {
+    let cstr = CString::new(s).unwrap_or_default();
+    ffi::sqlite3_result_text(ctx, cstr.as_ptr(), -1, ffi::SQLITE_TRANSIENT());
+}
+
+/// A grow-only counter's state: the running total contributed by each writer. Summing the values
+/// gives the counter's current value; merging two states takes the max contribution per writer,
+/// so re-merging the same update twice doesn't double-count it.
+type GCounter = BTreeMap<String, i64>;
+
+unsafe extern "C" fn lww_merge(
+    ctx: *mut ffi::sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut ffi::sqlite3_value,
+) {
+    let (Some(value_a), Some(value_b)) = (arg_str(argv, 0), arg_str(argv, 2)) else {
+        return result_error(ctx, "crdt_lww_merge: value arguments must be text");
+    };
+    let ts_a = ffi::sqlite3_value_int64(*argv.offset(1));
+    let ts_b = ffi::sqlite3_value_int64(*argv.offset(3));
+    // Ties broken by value so the merge stays deterministic regardless of argument order.
+    let winner = if ts_a != ts_b {
+        if ts_a > ts_b {
+            value_a
+        } else {
+            value_b
+        }
+    } else if value_a >= value_b {
+        value_a
+    } else {
+        value_b
+    };
+    result_text(ctx, winner);
+}
+
+unsafe extern "C" fn gcounter_merge(
+    ctx: *mut ffi::sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut ffi::sqlite3_value,
+) {
+    let (Some(a), Some(b)) = (arg_str(argv, 0), arg_str(argv, 1)) else {
+        return result_error(ctx, "crdt_gcounter_merge: arguments must be text");
+    };
+    let (a, b): (GCounter, GCounter) = match (parse_counter(a), parse_counter(b)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return result_error(ctx, "crdt_gcounter_merge: arguments must be a JSON object"),
+    };
+    let mut merged = a;
+    for (writer, count) in b {
+        merged
+            .entry(writer)
+            .and_modify(|existing| *existing = (*existing).max(count))
+            .or_insert(count);
+    }
+    let Ok(json) = serde_json::to_string(&merged) else {
+        return result_error(ctx, "crdt_gcounter_merge: failed to serialize merged state");
+    };
+    result_text(ctx, &json);
+}
+
+unsafe extern "C" fn gcounter_value(
+    ctx: *mut ffi::sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut ffi::sqlite3_value,
+) {
+    let Some(state) = arg_str(argv, 0) else {
+        return result_error(ctx, "crdt_gcounter_value: argument must be text");
+    };
+    let Some(state) = parse_counter(state) else {
+        return result_error(ctx, "crdt_gcounter_value: argument must be a JSON object");
+    };
+    ffi::sqlite3_result_int64(ctx, state.values().sum());
+}
+
+unsafe extern "C" fn set_merge(
+    ctx: *mut ffi::sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut ffi::sqlite3_value,
+) {
+    let (Some(a), Some(b)) = (arg_str(argv, 0), arg_str(argv, 1)) else {
+        return result_error(ctx, "crdt_set_merge: arguments must be text");
+    };
+    let (a, b): (BTreeSet<String>, BTreeSet<String>) = match (parse_set(a), parse_set(b)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return result_error(ctx, "crdt_set_merge: arguments must be a JSON array"),
+    };
+    let merged: BTreeSet<&String> = a.union(&b).collect();
+    let Ok(json) = serde_json::to_string(&merged) else {
+        return result_error(ctx, "crdt_set_merge: failed to serialize merged state");
+    };
+    result_text(ctx, &json);
+}
+
+fn parse_counter(s: &str) -> Option<GCounter> {
+    if s.is_empty() {
+        Some(GCounter::default())
+    } else {
+        serde_json::from_str(s).ok()
+    }
+}
+
+fn parse_set(s: &str) -> Option<BTreeSet<String>> {
+    if s.is_empty() {
+        Some(BTreeSet::default())
+    } else {
+        serde_json::from_str(s).ok()
+    }
+}
+
+impl Connection {
+    /// Registers the `crdt_lww_merge`, `crdt_gcounter_merge`, `crdt_gcounter_value`, and
+    /// `crdt_set_merge` SQL functions on this connection. See the [module docs](self) for what
+    /// each one does.
+    pub fn create_crdt_functions(&self) -> Result<()> {
+        unsafe {
+            self.create_scalar_function("crdt_lww_merge", 4, Some(lww_merge))?;
+            self.create_scalar_function("crdt_gcounter_merge", 2, Some(gcounter_merge))?;
+            self.create_scalar_function("crdt_gcounter_value", 1, Some(gcounter_value))?;
+            self.create_scalar_function("crdt_set_merge", 2, Some(set_merge))?;
+        }
+        Ok(())
+    }
+
+    unsafe fn create_scalar_function(
+        &self,
+        name: &str,
+        n_arg: c_int,
+        func: Option<
+            unsafe extern "C" fn(*mut ffi::sqlite3_context, c_int, *mut *mut ffi::sqlite3_value),
+        >,
+    ) -> Result<()> {
+        let name = CString::new(name).unwrap();
+        let rc = ffi::sqlite3_create_function_v2(
+            self.handle(),
+            name.as_ptr(),
+            n_arg,
+            ffi::SQLITE_UTF8 | ffi::SQLITE_DETERMINISTIC,
+            std::ptr::null_mut::<c_void>(),
+            func,
+            None,
+            None,
+            None,
+        );
+        if rc != ffi::SQLITE_OK {
+            return Err(crate::errors::Error::SqliteFailure(
+                rc,
+                format!("failed to register {:?}", name),
+            ));
+        }
+        Ok(())
+    }
+}
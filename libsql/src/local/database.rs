@@ -10,11 +10,12 @@ cfg_replication!(
     use crate::replication::EmbeddedReplicator;
     pub use crate::replication::Frames;
     pub use crate::replication::SyncUsageStats;
+    use crate::replication::ConsistencyMode;
 
     pub struct ReplicationContext {
         pub(crate) replicator: EmbeddedReplicator,
         client: Option<Client>,
-        read_your_writes: bool,
+        consistency_mode: ConsistencyMode,
     }
 );
 
@@ -91,6 +92,7 @@ impl Database {
             connector,
             db_path,
             endpoint,
+            Vec::new(),
             auth_token,
             None,
             false,
@@ -98,6 +100,7 @@ impl Database {
             sync_interval,
             None,
             None,
+            None,
         )
         .await
     }
@@ -108,6 +111,7 @@ impl Database {
         connector: crate::util::ConnectorService,
         db_path: String,
         endpoint: String,
+        failover_endpoints: Vec<String>,
         auth_token: String,
         version: Option<String>,
         read_your_writes: bool,
@@ -115,6 +119,7 @@ impl Database {
         sync_interval: Option<std::time::Duration>,
         http_request_callback: Option<crate::util::HttpRequestCallback>,
         namespace: Option<String>,
+        sync_error_callback: Option<crate::replication::SyncErrorCallback>,
     ) -> Result<Database> {
         use std::path::PathBuf;
 
@@ -122,13 +127,19 @@ impl Database {
 
         let mut db = Database::open(&db_path, OpenFlags::default())?;
 
-        let endpoint = coerce_url_scheme(endpoint);
-        let remote = crate::replication::client::Client::new(
+        let origins = std::iter::once(endpoint)
+            .chain(failover_endpoints)
+            .map(coerce_url_scheme)
+            .map(|endpoint| {
+                endpoint
+                    .as_str()
+                    .try_into()
+                    .map_err(|e: InvalidUri| crate::Error::Replication(e.into()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let remote = crate::replication::client::Client::new_with_failover(
             connector.clone(),
-            endpoint
-                .as_str()
-                .try_into()
-                .map_err(|e: InvalidUri| crate::Error::Replication(e.into()))?,
+            origins,
             auth_token.clone(),
             version.as_deref(),
             http_request_callback.clone(),
@@ -140,14 +151,24 @@ impl Database {
             .await
             .map_err(|e| crate::errors::Error::ConnectionFailed(e.to_string()))?;
 
-        let replicator =
-            EmbeddedReplicator::with_remote(client, path, 1000, encryption_config, sync_interval)
-                .await?;
+        let replicator = EmbeddedReplicator::with_remote(
+            client,
+            path,
+            1000,
+            encryption_config,
+            sync_interval,
+            sync_error_callback,
+        )
+        .await?;
 
         db.replication_ctx = Some(ReplicationContext {
             replicator,
             client: Some(remote),
-            read_your_writes,
+            consistency_mode: if read_your_writes {
+                ConsistencyMode::ReadYourWrites
+            } else {
+                ConsistencyMode::Eventual
+            },
         });
 
         Ok(db)
@@ -159,6 +180,7 @@ impl Database {
         connector: crate::util::ConnectorService,
         db_path: String,
         endpoint: String,
+        failover_endpoints: Vec<String>,
         auth_token: String,
         version: Option<String>,
         read_your_writes: bool,
@@ -166,6 +188,7 @@ impl Database {
         sync_interval: Option<std::time::Duration>,
         http_request_callback: Option<crate::util::HttpRequestCallback>,
         namespace: Option<String>,
+        sync_error_callback: Option<crate::replication::SyncErrorCallback>,
     ) -> Result<Database> {
         use std::path::PathBuf;
 
@@ -173,13 +196,19 @@ impl Database {
 
         let mut db = Database::open_raw(&db_path, OpenFlags::default())?;
 
-        let endpoint = coerce_url_scheme(endpoint);
-        let remote = crate::replication::client::Client::new(
+        let origins = std::iter::once(endpoint)
+            .chain(failover_endpoints)
+            .map(coerce_url_scheme)
+            .map(|endpoint| {
+                endpoint
+                    .as_str()
+                    .try_into()
+                    .map_err(|e: InvalidUri| crate::Error::Replication(e.into()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let remote = crate::replication::client::Client::new_with_failover(
             connector.clone(),
-            endpoint
-                .as_str()
-                .try_into()
-                .map_err(|e: InvalidUri| crate::Error::Replication(e.into()))?,
+            origins,
             auth_token.clone(),
             version.as_deref(),
             http_request_callback.clone(),
@@ -191,14 +220,24 @@ impl Database {
             .await
             .map_err(|e| crate::errors::Error::ConnectionFailed(e.to_string()))?;
 
-        let replicator =
-            EmbeddedReplicator::with_remote(client, path, 1000, encryption_config, sync_interval)
-                .await?;
+        let replicator = EmbeddedReplicator::with_remote(
+            client,
+            path,
+            1000,
+            encryption_config,
+            sync_interval,
+            sync_error_callback,
+        )
+        .await?;
 
         db.replication_ctx = Some(ReplicationContext {
             replicator,
             client: Some(remote),
-            read_your_writes,
+            consistency_mode: if read_your_writes {
+                ConsistencyMode::ReadYourWrites
+            } else {
+                ConsistencyMode::Eventual
+            },
         });
 
         Ok(db)
@@ -250,7 +289,7 @@ impl Database {
         db.replication_ctx = Some(ReplicationContext {
             replicator,
             client: None,
-            read_your_writes: false,
+            consistency_mode: ConsistencyMode::Eventual,
         });
 
         Ok(db)
@@ -299,7 +338,7 @@ impl Database {
         db.replication_ctx = Some(ReplicationContext {
             replicator,
             client: Some(remote),
-            read_your_writes: false,
+            consistency_mode: ConsistencyMode::Eventual,
         });
 
         Ok(db)
@@ -343,21 +382,98 @@ impl Database {
         Connection::connect(self)
     }
 
+    #[cfg(feature = "encryption")]
+    /// Re-encrypt the database in place with `new_key`, replacing whatever key it was opened
+    /// with. The database stays readable and writable by other connections throughout, since
+    /// SQLite rewrites every page under a single implicit transaction rather than taking the
+    /// database offline.
+    pub fn rekey(&self, new_key: &[u8]) -> Result<()> {
+        let conn = self.connect()?;
+        let rc = libsql_sys::connection::reset_encryption_key(conn.handle(), new_key);
+        if rc != ffi::SQLITE_OK {
+            return Err(ConnectionFailed(format!(
+                "failed to rekey database: sqlite error {rc}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Copy this database into `dest_path` using `sqlite3_backup_*`, stepping a fixed number of
+    /// pages at a time and reporting progress after each step so callers can cancel a long
+    /// backup by returning `false`.
+    pub fn backup_to(
+        &self,
+        dest_path: &str,
+        mut progress: impl FnMut(crate::database::BackupProgress) -> bool,
+    ) -> Result<()> {
+        use crate::database::BackupProgress;
+        use crate::errors::{error_from_code, Error};
+
+        const PAGES_PER_STEP: i32 = 32;
+
+        let conn = self.connect()?;
+
+        let dest_path_c = std::ffi::CString::new(dest_path)
+            .map_err(|_| ConnectionFailed("destination path contains a NUL byte".to_string()))?;
+        let mut dest: *mut ffi::sqlite3 = std::ptr::null_mut();
+        let rc = unsafe { ffi::sqlite3_open(dest_path_c.as_ptr(), &mut dest) };
+        if rc != ffi::SQLITE_OK {
+            unsafe { ffi::sqlite3_close(dest) };
+            return Err(Error::SqliteFailure(rc, error_from_code(rc)));
+        }
+
+        let main = std::ffi::CString::new("main").unwrap();
+        let backup =
+            unsafe { ffi::sqlite3_backup_init(dest, main.as_ptr(), conn.handle(), main.as_ptr()) };
+        let backup = match std::ptr::NonNull::new(backup) {
+            Some(backup) => backup,
+            None => {
+                let rc = unsafe { ffi::sqlite3_errcode(dest) };
+                unsafe { ffi::sqlite3_close(dest) };
+                return Err(Error::SqliteFailure(rc, error_from_code(rc)));
+            }
+        };
+        let backup = backup.as_ptr();
+
+        let result = loop {
+            let rc = unsafe { ffi::sqlite3_backup_step(backup, PAGES_PER_STEP) };
+
+            let pages_total = unsafe { ffi::sqlite3_backup_pagecount(backup) };
+            let pages_done = pages_total - unsafe { ffi::sqlite3_backup_remaining(backup) };
+            let keep_going = progress(BackupProgress {
+                pages_done,
+                pages_total,
+            });
+
+            match rc {
+                ffi::SQLITE_DONE => break Ok(()),
+                ffi::SQLITE_OK | ffi::SQLITE_BUSY | ffi::SQLITE_LOCKED if keep_going => continue,
+                ffi::SQLITE_OK | ffi::SQLITE_BUSY | ffi::SQLITE_LOCKED => {
+                    break Err(Error::Misuse("backup cancelled".to_string()))
+                }
+                _ => break Err(Error::SqliteFailure(rc, error_from_code(rc))),
+            }
+        };
+
+        unsafe { ffi::sqlite3_backup_finish(backup) };
+        unsafe { ffi::sqlite3_close(dest) };
+        result
+    }
+
     #[cfg(feature = "replication")]
     pub(crate) fn writer(&self) -> Result<Option<crate::replication::Writer>> {
         use crate::replication::Writer;
         if let Some(ReplicationContext {
             client: Some(ref client),
             replicator,
-            read_your_writes,
+            consistency_mode,
         }) = &self.replication_ctx
         {
             Ok(Some(Writer {
                 client: client.clone(),
-                replicator: if *read_your_writes {
-                    Some(replicator.clone())
-                } else {
-                    None
+                replicator: match consistency_mode {
+                    ConsistencyMode::ReadYourWrites => Some(replicator.clone()),
+                    ConsistencyMode::Eventual => None,
                 },
             }))
         } else {
@@ -385,6 +501,34 @@ impl Database {
         Ok(self.sync_oneshot().await?)
     }
 
+    #[cfg(feature = "replication")]
+    /// Pause the background periodic sync task started via `Builder::sync_interval`, if any.
+    pub fn pause_sync(&self) -> Result<()> {
+        if let Some(ctx) = &self.replication_ctx {
+            ctx.replicator.pause();
+            Ok(())
+        } else {
+            Err(crate::errors::Error::Misuse(
+                "No replicator available. Use Database::with_replicator() to enable replication"
+                    .to_string(),
+            ))
+        }
+    }
+
+    #[cfg(feature = "replication")]
+    /// Resume the background periodic sync task after a [`Self::pause_sync`].
+    pub fn resume_sync(&self) -> Result<()> {
+        if let Some(ctx) = &self.replication_ctx {
+            ctx.replicator.resume();
+            Ok(())
+        } else {
+            Err(crate::errors::Error::Misuse(
+                "No replicator available. Use Database::with_replicator() to enable replication"
+                    .to_string(),
+            ))
+        }
+    }
+
     #[cfg(feature = "replication")]
     /// Return detailed logs about bytes synced with primary
     pub async fn get_sync_usage_stats(&self) -> Result<SyncUsageStats> {
@@ -425,6 +569,51 @@ impl Database {
         }
     }
 
+    #[cfg(feature = "replication")]
+    /// Sync with primary, pulling frames until the replica reports it is caught up, and
+    /// return a [`crate::replication::SyncReport`] summarizing the work done.
+    ///
+    /// This call is cancellation-safe: each batch of frames is durably applied (and the
+    /// replicator's cursor persisted) before the next batch is requested, so dropping the
+    /// future part-way through leaves the replica at a consistent, resumable frame boundary.
+    /// Calling this again later simply continues from there.
+    pub async fn sync_with_report(&self) -> Result<crate::replication::SyncReport> {
+        if let Some(ctx) = &self.replication_ctx {
+            let started_at = std::time::Instant::now();
+            let stats_before = ctx.replicator.get_sync_usage_stats().await?;
+            let bytes_before = stats_before.synced_bytes_used() + stats_before.snapshot_bytes();
+
+            let mut frames_applied = 0usize;
+            let mut caught_up = false;
+            let mut replication_index = ctx.replicator.committed_frame_no().await;
+            loop {
+                let res = ctx.replicator.sync_oneshot().await?;
+                frames_applied += res.frames_synced();
+                replication_index = res.frame_no().or(replication_index);
+                if res.frames_synced() == 0 {
+                    caught_up = true;
+                    break;
+                }
+            }
+
+            let stats_after = ctx.replicator.get_sync_usage_stats().await?;
+            let bytes_after = stats_after.synced_bytes_used() + stats_after.snapshot_bytes();
+
+            Ok(crate::replication::SyncReport {
+                frames_applied,
+                bytes: bytes_after.saturating_sub(bytes_before),
+                duration: started_at.elapsed(),
+                caught_up,
+                replication_index,
+            })
+        } else {
+            Err(crate::errors::Error::Misuse(
+                "No replicator available. Use Database::with_replicator() to enable replication"
+                    .to_string(),
+            ))
+        }
+    }
+
     #[cfg(feature = "replication")]
     pub async fn sync_frames(&self, frames: Frames) -> Result<Option<FrameNo>> {
         if let Some(ref ctx) = self.replication_ctx {
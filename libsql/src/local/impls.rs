@@ -3,8 +3,9 @@ use std::{fmt, path::Path};
 use std::time::Duration;
 
 use crate::connection::BatchRows;
+use crate::function::{AggregateFactory, FunctionFlags, WindowAggregateFactory};
 use crate::{
-    connection::{AuthHook, Conn},
+    connection::{AuthHook, CollationFn, CommitHookFn, Conn, RollbackHookFn, ScalarFn, UpdateHookFn},
     params::Params,
     rows::{ColumnsInner, RowInner, RowsInner},
     statement::Stmt,
@@ -50,6 +51,8 @@ impl Conn for LibsqlConnection {
             inner: Box::new(LibsqlTx(Some(tx))),
             conn: Connection {
                 conn: Arc::new(self.clone()),
+                schema_cache: Default::default(),
+                query_tag: Default::default(),
             },
             close: None,
         })
@@ -92,6 +95,54 @@ impl Conn for LibsqlConnection {
     fn authorizer(&self, hook: Option<AuthHook>) -> Result<()> {
         self.conn.authorizer(hook)
     }
+
+    fn create_scalar_function(
+        &self,
+        name: &str,
+        n_args: i32,
+        flags: FunctionFlags,
+        func: ScalarFn,
+    ) -> Result<()> {
+        self.conn.create_scalar_function(name, n_args, flags, func)
+    }
+
+    fn create_aggregate_function(
+        &self,
+        name: &str,
+        n_args: i32,
+        flags: FunctionFlags,
+        factory: AggregateFactory,
+    ) -> Result<()> {
+        self.conn
+            .create_aggregate_function(name, n_args, flags, factory)
+    }
+
+    fn create_window_function(
+        &self,
+        name: &str,
+        n_args: i32,
+        flags: FunctionFlags,
+        factory: WindowAggregateFactory,
+    ) -> Result<()> {
+        self.conn
+            .create_window_function(name, n_args, flags, factory)
+    }
+
+    fn create_collation(&self, name: &str, compare: CollationFn) -> Result<()> {
+        self.conn.create_collation(name, compare)
+    }
+
+    fn update_hook(&self, hook: Option<UpdateHookFn>) -> Result<()> {
+        self.conn.update_hook(hook)
+    }
+
+    fn commit_hook(&self, hook: Option<CommitHookFn>) -> Result<()> {
+        self.conn.commit_hook(hook)
+    }
+
+    fn rollback_hook(&self, hook: Option<RollbackHookFn>) -> Result<()> {
+        self.conn.rollback_hook(hook)
+    }
 }
 
 impl Drop for LibsqlConnection {
@@ -3,8 +3,12 @@
 #![allow(dead_code)]
 
 pub mod connection;
+#[cfg(feature = "crdt")]
+pub mod crdt;
 pub mod database;
 pub mod rows;
+#[cfg(feature = "session")]
+pub mod session;
 pub mod statement;
 pub mod transaction;
 
@@ -18,6 +22,8 @@ pub use database::Database;
 pub use rows::Row;
 pub use rows::Rows;
 pub use rows::RowsFuture;
+#[cfg(feature = "session")]
+pub use session::Session;
 pub use statement::Statement;
 pub use transaction::Transaction;
 
@@ -0,0 +1,209 @@
+//! SQLite session extension support: recording changes made to a connection into a changeset
+//! that can be applied to another connection, as a building block for offline/bidirectional
+//! sync. See <https://www.sqlite.org/sessionintro.html>.
+
+use std::ffi::CString;
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+
+use libsql_sys::ffi;
+
+use super::{Connection, Error, Result};
+
+fn sqlite_result(rc: c_int) -> Result<()> {
+    if rc == ffi::SQLITE_OK {
+        Ok(())
+    } else {
+        Err(Error::SqliteFailure(rc, "session extension call failed".to_string()))
+    }
+}
+
+/// Records changes made to one or more tables of a [`Connection`] so they can later be turned
+/// into a changeset with [`Session::changeset`].
+///
+/// Dropping a `Session` stops recording and releases it; it does not undo anything it recorded.
+pub struct Session<'conn> {
+    session: *mut ffi::sqlite3_session,
+    _conn: &'conn Connection,
+}
+
+impl<'conn> Session<'conn> {
+    /// Creates a session against `conn`'s `main` database. No tables are tracked until
+    /// [`attach`](Self::attach) is called.
+    pub(crate) fn new(conn: &'conn Connection) -> Result<Self> {
+        let main = CString::new("main").unwrap();
+        let mut session = ptr::null_mut();
+        let rc = unsafe { ffi::sqlite3session_create(conn.handle(), main.as_ptr(), &mut session) };
+        sqlite_result(rc)?;
+        Ok(Self {
+            session,
+            _conn: conn,
+        })
+    }
+
+    /// Starts tracking `table`, or every table in the database (current and future) if `None`.
+    pub fn attach(&mut self, table: Option<&str>) -> Result<()> {
+        let rc = match table {
+            Some(table) => {
+                let table = CString::new(table).unwrap();
+                unsafe { ffi::sqlite3session_attach(self.session, table.as_ptr()) }
+            }
+            None => unsafe { ffi::sqlite3session_attach(self.session, ptr::null()) },
+        };
+        sqlite_result(rc)
+    }
+
+    /// Whether any change has been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        unsafe { ffi::sqlite3session_isempty(self.session) != 0 }
+    }
+
+    /// Serializes everything recorded so far into a changeset, suitable for transport and later
+    /// replay with [`Connection::apply_changeset`].
+    pub fn changeset(&self) -> Result<Vec<u8>> {
+        let mut len: c_int = 0;
+        let mut buf: *mut c_void = ptr::null_mut();
+        let rc = unsafe { ffi::sqlite3session_changeset(self.session, &mut len, &mut buf) };
+        sqlite_result(rc)?;
+        let changeset = if buf.is_null() || len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(buf as *const u8, len as usize).to_vec() }
+        };
+        unsafe { ffi::sqlite3_free(buf) };
+        Ok(changeset)
+    }
+}
+
+impl Drop for Session<'_> {
+    fn drop(&mut self) {
+        unsafe { ffi::sqlite3session_delete(self.session) };
+    }
+}
+
+/// The kind of conflict [`ConflictHandler`] is asked to resolve, mirroring sqlite's
+/// `SQLITE_CHANGESET_*` constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictType {
+    /// The local row was modified since the changeset was generated, and now differs from the
+    /// changeset's expectation of its old values.
+    Data,
+    /// A row the changeset expects to update or delete no longer exists locally.
+    NotFound,
+    /// Applying an insert would violate a `PRIMARY KEY` or `UNIQUE` constraint.
+    Conflict,
+    /// Applying the change would violate a `NOT NULL`, `CHECK`, or other constraint.
+    Constraint,
+    /// Applying the change would violate a foreign key constraint (reported once per changeset).
+    ForeignKey,
+}
+
+impl ConflictType {
+    fn from_raw(v: c_int) -> Self {
+        match v {
+            ffi::SQLITE_CHANGESET_DATA => Self::Data,
+            ffi::SQLITE_CHANGESET_NOTFOUND => Self::NotFound,
+            ffi::SQLITE_CHANGESET_CONFLICT => Self::Conflict,
+            ffi::SQLITE_CHANGESET_CONSTRAINT => Self::Constraint,
+            ffi::SQLITE_CHANGESET_FOREIGN_KEY => Self::ForeignKey,
+            _ => Self::Conflict,
+        }
+    }
+}
+
+/// How to resolve a conflict reported to a [`ConflictHandler`], mirroring sqlite's
+/// `SQLITE_CHANGESET_*` resolution constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictAction {
+    /// Skip this change and continue applying the rest of the changeset.
+    Omit,
+    /// Replace the conflicting local row with the changeset's version (not valid for every
+    /// conflict type; falls back to [`Omit`](Self::Omit) if sqlite rejects it).
+    Replace,
+    /// Abort applying the changeset and roll back everything it has applied so far.
+    Abort,
+}
+
+impl ConflictAction {
+    fn into_raw(self) -> c_int {
+        match self {
+            Self::Omit => ffi::SQLITE_CHANGESET_OMIT,
+            Self::Replace => ffi::SQLITE_CHANGESET_REPLACE,
+            Self::Abort => ffi::SQLITE_CHANGESET_ABORT,
+        }
+    }
+}
+
+/// Decides how to resolve each conflict encountered while applying a changeset. `table` is the
+/// name of the table the conflicting change belongs to.
+pub trait ConflictHandler {
+    fn handle_conflict(&mut self, conflict: ConflictType, table: &str) -> ConflictAction;
+}
+
+impl<F> ConflictHandler for F
+where
+    F: FnMut(ConflictType, &str) -> ConflictAction,
+{
+    fn handle_conflict(&mut self, conflict: ConflictType, table: &str) -> ConflictAction {
+        (self)(conflict, table)
+    }
+}
+
+unsafe extern "C" fn conflict_callback<H: ConflictHandler>(
+    ctx: *mut c_void,
+    conflict: c_int,
+    iter: *mut ffi::sqlite3_changeset_iter,
+) -> c_int {
+    let handler = &mut *(ctx as *mut H);
+
+    let mut table_name: *const std::os::raw::c_char = ptr::null();
+    let mut num_cols = 0;
+    let mut op = 0;
+    let mut indirect = 0;
+    let table = if ffi::sqlite3changeset_op(
+        iter,
+        &mut table_name,
+        &mut num_cols,
+        &mut op,
+        &mut indirect,
+    ) == ffi::SQLITE_OK
+        && !table_name.is_null()
+    {
+        std::ffi::CStr::from_ptr(table_name)
+            .to_str()
+            .unwrap_or("")
+    } else {
+        ""
+    };
+
+    handler
+        .handle_conflict(ConflictType::from_raw(conflict), table)
+        .into_raw()
+}
+
+impl Connection {
+    /// Starts a new [`Session`] recording changes made through this connection.
+    pub fn create_session(&self) -> Result<Session<'_>> {
+        Session::new(self)
+    }
+
+    /// Applies `changeset` (as produced by [`Session::changeset`]) to this connection, calling
+    /// `conflict` to resolve any row that doesn't apply cleanly.
+    pub fn apply_changeset<H: ConflictHandler>(
+        &self,
+        changeset: &[u8],
+        mut conflict: H,
+    ) -> Result<()> {
+        let rc = unsafe {
+            ffi::sqlite3changeset_apply(
+                self.handle(),
+                changeset.len() as c_int,
+                changeset.as_ptr() as *mut c_void,
+                None,
+                Some(conflict_callback::<H>),
+                &mut conflict as *mut H as *mut c_void,
+            )
+        };
+        sqlite_result(rc)
+    }
+}
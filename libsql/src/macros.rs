@@ -50,6 +50,16 @@ macro_rules! cfg_sync {
     }
 }
 
+macro_rules! cfg_sync2 {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "sync2")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "sync2")))]
+            $item
+        )*
+    }
+}
+
 macro_rules! cfg_replication_or_sync {
     ($($item:item)*) => {
         $(
@@ -60,6 +70,56 @@ macro_rules! cfg_replication_or_sync {
     }
 }
 
+macro_rules! cfg_derive {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "derive")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+            $item
+        )*
+    }
+}
+
+macro_rules! cfg_r2d2 {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "r2d2")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "r2d2")))]
+            $item
+        )*
+    }
+}
+
+macro_rules! cfg_deadpool {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "deadpool")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "deadpool")))]
+            $item
+        )*
+    }
+}
+
+macro_rules! cfg_blocking {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "blocking")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+            $item
+        )*
+    }
+}
+
+macro_rules! cfg_pool {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "pool")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "pool")))]
+            $item
+        )*
+    }
+}
+
 macro_rules! cfg_parser {
     ($($item:item)*) => {
         $(
@@ -109,3 +169,13 @@ macro_rules! cfg_wasm {
         )*
     }
 }
+
+macro_rules! cfg_testing {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "testing")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+            $item
+        )*
+    }
+}
@@ -101,6 +101,34 @@ pub enum Params {
     Named(Vec<(String, Value)>),
 }
 
+/// Maps a type's fields onto named parameters (`:field`).
+///
+/// [`IntoParams`] is sealed so that only this crate can add new ways of passing parameters, but
+/// that means a derive macro outside the crate can't implement it directly. `NamedParams` is the
+/// escape hatch: anything that implements it gets a blanket [`IntoParams`] implementation below,
+/// so `#[derive(IntoParams)]` from the `libsql-macros` crate only has to implement this trait.
+///
+/// Most users should reach for the derive macro rather than implementing this by hand:
+///
+/// ```rust,ignore
+/// #[derive(libsql::IntoParams)]
+/// struct User {
+///     id: i64,
+///     name: String,
+/// }
+/// ```
+pub trait NamedParams {
+    /// Returns `(":field", value)` pairs for every field, in declaration order.
+    fn named_params(self) -> Result<Vec<(String, Value)>>;
+}
+
+impl<T: NamedParams> Sealed for T {}
+impl<T: NamedParams> IntoParams for T {
+    fn into_params(self) -> Result<Params> {
+        Ok(Params::Named(self.named_params()?))
+    }
+}
+
 /// Convert an owned iterator into Params.
 ///
 /// # Example
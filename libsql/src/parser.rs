@@ -127,69 +127,198 @@ impl StmtKind {
 
     fn pragma_kind(name: &QualifiedName, body: Option<&PragmaBody>) -> Option<Self> {
         let name = name.name.0.as_str();
-        match name {
-            // always ok to be served by primary or replicas - pure readonly pragmas
-            "table_list" | "index_list" | "table_info" | "table_xinfo" | "index_info" | "index_xinfo"
-            | "pragma_list" | "compile_options" | "database_list" | "function_list"
-            | "module_list" => Some(Self::Read),
+        let Some(pragma) = KnownPragma::from_name(name) else {
+            tracing::debug!("Unknown pragma: {name}");
+            return None;
+        };
+        match pragma.access() {
+            PragmaAccess::Read => Some(Self::Read),
+            PragmaAccess::Write => Some(Self::Write),
+            PragmaAccess::WriteIfNoArgs => match body {
+                Some(_) => None,
+                None => Some(Self::Write),
+            },
+            PragmaAccess::Disallowed => None,
+        }
+    }
+}
+
+/// A PRAGMA recognized by [`StmtKind::pragma_kind`], modeled as a typed enum instead of matching
+/// on the pragma's name string. Exposed so that callers routing statements between a primary and
+/// its replicas (or a client-side sync engine) can classify a pragma without re-implementing this
+/// name matching themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnownPragma {
+    TableList,
+    IndexList,
+    TableInfo,
+    TableXinfo,
+    IndexInfo,
+    IndexXinfo,
+    PragmaList,
+    CompileOptions,
+    DatabaseList,
+    FunctionList,
+    ModuleList,
+    Encoding,
+    DeferForeignKeys,
+    ForeignKeys,
+    ForeignKeyList,
+    ForeignKeyCheck,
+    CollationList,
+    DataVersion,
+    FreelistCount,
+    IntegrityCheck,
+    LegacyFileFormat,
+    PageCount,
+    QuickCheck,
+    Stats,
+    UserVersion,
+    AnalysisLimit,
+    ApplicationId,
+    AutoVacuum,
+    AutomaticIndex,
+    BusyTimeout,
+    CacheSize,
+    CacheSpill,
+    CellSizeCheck,
+    CheckpointFullfsync,
+    Fullfsync,
+    HardHeapLimit,
+    JournalMode,
+    JournalSizeLimit,
+    LegacyAlterTable,
+    LockingMode,
+    MaxPageCount,
+    MmapSize,
+    PageSize,
+    QueryOnly,
+    ReadUncommitted,
+    RecursiveTriggers,
+    ReverseUnorderedSelects,
+    SchemaVersion,
+    SecureDelete,
+    SoftHeapLimit,
+    Synchronous,
+    TempStore,
+    Threads,
+    TrustedSchema,
+    WalAutocheckpoint,
+    CaseSensitiveLike,
+    IgnoreCheckConstraints,
+    IncrementalVacuum,
+    Optimize,
+    ParserTrace,
+    ShrinkMemory,
+    WalCheckpoint,
+}
+
+impl KnownPragma {
+    /// Looks up a pragma by its exact (case-sensitive) SQL name, e.g. `"journal_mode"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "table_list" => Self::TableList,
+            "index_list" => Self::IndexList,
+            "table_info" => Self::TableInfo,
+            "table_xinfo" => Self::TableXinfo,
+            "index_info" => Self::IndexInfo,
+            "index_xinfo" => Self::IndexXinfo,
+            "pragma_list" => Self::PragmaList,
+            "compile_options" => Self::CompileOptions,
+            "database_list" => Self::DatabaseList,
+            "function_list" => Self::FunctionList,
+            "module_list" => Self::ModuleList,
+            "encoding" => Self::Encoding,
+            "defer_foreign_keys" => Self::DeferForeignKeys,
+            "foreign_keys" => Self::ForeignKeys,
+            "foreign_key_list" => Self::ForeignKeyList,
+            "foreign_key_check" => Self::ForeignKeyCheck,
+            "collation_list" => Self::CollationList,
+            "data_version" => Self::DataVersion,
+            "freelist_count" => Self::FreelistCount,
+            "integrity_check" => Self::IntegrityCheck,
+            "legacy_file_format" => Self::LegacyFileFormat,
+            "page_count" => Self::PageCount,
+            "quick_check" => Self::QuickCheck,
+            "stats" => Self::Stats,
+            "user_version" => Self::UserVersion,
+            "analysis_limit" => Self::AnalysisLimit,
+            "application_id" => Self::ApplicationId,
+            "auto_vacuum" => Self::AutoVacuum,
+            "automatic_index" => Self::AutomaticIndex,
+            "busy_timeout" => Self::BusyTimeout,
+            "cache_size" => Self::CacheSize,
+            "cache_spill" => Self::CacheSpill,
+            "cell_size_check" => Self::CellSizeCheck,
+            "checkpoint_fullfsync" => Self::CheckpointFullfsync,
+            "fullfsync" => Self::Fullfsync,
+            "hard_heap_limit" => Self::HardHeapLimit,
+            "journal_mode" => Self::JournalMode,
+            "journal_size_limit" => Self::JournalSizeLimit,
+            "legacy_alter_table" => Self::LegacyAlterTable,
+            "locking_mode" => Self::LockingMode,
+            "max_page_count" => Self::MaxPageCount,
+            "mmap_size" => Self::MmapSize,
+            "page_size" => Self::PageSize,
+            "query_only" => Self::QueryOnly,
+            "read_uncommitted" => Self::ReadUncommitted,
+            "recursive_triggers" => Self::RecursiveTriggers,
+            "reverse_unordered_selects" => Self::ReverseUnorderedSelects,
+            "schema_version" => Self::SchemaVersion,
+            "secure_delete" => Self::SecureDelete,
+            "soft_heap_limit" => Self::SoftHeapLimit,
+            "synchronous" => Self::Synchronous,
+            "temp_store" => Self::TempStore,
+            "threads" => Self::Threads,
+            "trusted_schema" => Self::TrustedSchema,
+            "wal_autocheckpoint" => Self::WalAutocheckpoint,
+            "case_sensitive_like" => Self::CaseSensitiveLike,
+            "ignore_check_constraints" => Self::IgnoreCheckConstraints,
+            "incremental_vacuum" => Self::IncrementalVacuum,
+            "optimize" => Self::Optimize,
+            "parser_trace" => Self::ParserTrace,
+            "shrink_memory" => Self::ShrinkMemory,
+            "wal_checkpoint" => Self::WalCheckpoint,
+            _ => return None,
+        })
+    }
+
+    /// How this pragma should be routed between a primary and its replicas.
+    fn access(self) -> PragmaAccess {
+        use KnownPragma::*;
+        match self {
+            TableList | IndexList | TableInfo | TableXinfo | IndexInfo | IndexXinfo | PragmaList
+            | CompileOptions | DatabaseList | FunctionList | ModuleList
             // special case for `encoding` - it's effectively readonly for connections
             // that already created a database, which is always the case for sqld
-            "encoding" => Some(Self::Read),
-            // always ok to be served by primary
-            "defer_foreign_keys" | "foreign_keys" | "foreign_key_list" | "foreign_key_check" | "collation_list"
-            | "data_version" | "freelist_count" | "integrity_check" | "legacy_file_format"
-            | "page_count" | "quick_check" | "stats" | "user_version" => Some(Self::Write),
-            // ok to be served by primary without args
-            "analysis_limit"
-            | "application_id"
-            | "auto_vacuum"
-            | "automatic_index"
-            | "busy_timeout"
-            | "cache_size"
-            | "cache_spill"
-            | "cell_size_check"
-            | "checkpoint_fullfsync"
-            | "fullfsync"
-            | "hard_heap_limit"
-            | "journal_mode"
-            | "journal_size_limit"
-            | "legacy_alter_table"
-            | "locking_mode"
-            | "max_page_count"
-            | "mmap_size"
-            | "page_size"
-            | "query_only"
-            | "read_uncommitted"
-            | "recursive_triggers"
-            | "reverse_unordered_selects"
-            | "schema_version"
-            | "secure_delete"
-            | "soft_heap_limit"
-            | "synchronous"
-            | "temp_store"
-            | "threads"
-            | "trusted_schema"
-            | "wal_autocheckpoint" => {
-                match body {
-                    Some(_) => None,
-                    None => Some(Self::Write),
-                }
-            }
+            | Encoding => PragmaAccess::Read,
+            DeferForeignKeys | ForeignKeys | ForeignKeyList | ForeignKeyCheck | CollationList
+            | DataVersion | FreelistCount | IntegrityCheck | LegacyFileFormat | PageCount
+            | QuickCheck | Stats | UserVersion => PragmaAccess::Write,
+            AnalysisLimit | ApplicationId | AutoVacuum | AutomaticIndex | BusyTimeout | CacheSize
+            | CacheSpill | CellSizeCheck | CheckpointFullfsync | Fullfsync | HardHeapLimit
+            | JournalMode | JournalSizeLimit | LegacyAlterTable | LockingMode | MaxPageCount
+            | MmapSize | PageSize | QueryOnly | ReadUncommitted | RecursiveTriggers
+            | ReverseUnorderedSelects | SchemaVersion | SecureDelete | SoftHeapLimit | Synchronous
+            | TempStore | Threads | TrustedSchema | WalAutocheckpoint => PragmaAccess::WriteIfNoArgs,
             // changes the state of the connection, and can't be allowed rn:
-            "case_sensitive_like" | "ignore_check_constraints" | "incremental_vacuum"
+            CaseSensitiveLike | IgnoreCheckConstraints | IncrementalVacuum
                 // TODO: check if optimize can be safely performed
-                | "optimize"
-                | "parser_trace"
-                | "shrink_memory"
-                | "wal_checkpoint" => None,
-            _ => {
-                tracing::debug!("Unknown pragma: {name}");
-                None
-            },
+                | Optimize | ParserTrace | ShrinkMemory | WalCheckpoint => PragmaAccess::Disallowed,
         }
     }
 }
 
+/// Whether a pragma can be routed to a read replica, must always go to the primary, must go to
+/// the primary only when it's being read (no argument given), or can't be routed at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PragmaAccess {
+    Read,
+    Write,
+    WriteIfNoArgs,
+    Disallowed,
+}
+
 impl Statement {
     pub fn empty() -> Self {
         Self {
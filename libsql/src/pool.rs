@@ -0,0 +1,138 @@
+//! A small async connection pool built into the client, so callers don't have to reach for a
+//! deadpool/bb8 wrapper that doesn't understand Hrana batons or embedded-replica writer
+//! semantics — it just hands out [`Connection`]s obtained from [`Database::connect`].
+
+use std::collections::VecDeque;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::{Connection, Database, Result};
+
+/// Configuration for a [`Pool`].
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    /// Maximum number of connections the pool will hand out at once. Callers beyond this limit
+    /// wait in [`Pool::get`] until a connection is returned.
+    pub max_size: usize,
+    /// Idle connections older than this are discarded instead of reused. `None` disables
+    /// eviction and idle connections are kept forever.
+    pub idle_timeout: Option<Duration>,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 10,
+            idle_timeout: Some(Duration::from_secs(5 * 60)),
+        }
+    }
+}
+
+struct Idle {
+    conn: Connection,
+    since: Instant,
+}
+
+struct Inner {
+    db: Database,
+    config: PoolConfig,
+    idle: Mutex<VecDeque<Idle>>,
+    semaphore: Arc<Semaphore>,
+}
+
+/// An async connection pool over a single [`Database`].
+#[derive(Clone)]
+pub struct Pool {
+    inner: Arc<Inner>,
+}
+
+impl Pool {
+    pub fn new(db: Database, config: PoolConfig) -> Self {
+        let semaphore = Arc::new(Semaphore::new(config.max_size));
+        Self {
+            inner: Arc::new(Inner {
+                db,
+                config,
+                idle: Mutex::new(VecDeque::new()),
+                semaphore,
+            }),
+        }
+    }
+
+    /// Checks out a connection, waiting until one is available if the pool is at `max_size`.
+    /// Idle connections are health-checked with a `SELECT 1` before being handed back out;
+    /// ones that fail the check, or have been idle longer than `idle_timeout`, are dropped and
+    /// replaced with a freshly opened connection.
+    pub async fn get(&self) -> Result<PooledConnection> {
+        let permit = self
+            .inner
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("pool semaphore is never closed");
+
+        loop {
+            let candidate = self.inner.idle.lock().unwrap().pop_front();
+            let Some(candidate) = candidate else {
+                break;
+            };
+
+            if let Some(idle_timeout) = self.inner.config.idle_timeout {
+                if candidate.since.elapsed() > idle_timeout {
+                    continue;
+                }
+            }
+
+            if candidate.conn.query("SELECT 1", ()).await.is_ok() {
+                return Ok(PooledConnection {
+                    conn: Some(candidate.conn),
+                    inner: self.inner.clone(),
+                    _permit: permit,
+                });
+            }
+        }
+
+        let conn = self.inner.db.connect()?;
+        Ok(PooledConnection {
+            conn: Some(conn),
+            inner: self.inner.clone(),
+            _permit: permit,
+        })
+    }
+}
+
+/// A [`Connection`] checked out of a [`Pool`]. Returned to the pool's idle queue when dropped.
+pub struct PooledConnection {
+    conn: Option<Connection>,
+    inner: Arc<Inner>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("connection taken")
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("connection taken")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.inner.idle.lock().unwrap().push_back(Idle {
+                conn,
+                since: Instant::now(),
+            });
+        }
+    }
+}
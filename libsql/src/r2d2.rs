@@ -0,0 +1,40 @@
+//! An [`r2d2::ManageConnection`] adapter for pooling [`Connection`]s.
+
+use crate::{Connection, Database};
+
+/// Manages a pool of libsql [`Connection`]s for `r2d2`. Connections are validated with a
+/// lightweight `SELECT 1` before being handed out of the pool.
+///
+/// Must be constructed from within a Tokio runtime: `r2d2`'s connection manager trait is
+/// synchronous, so connection creation and validation block on that runtime.
+pub struct R2D2Manager {
+    db: Database,
+    rt: tokio::runtime::Handle,
+}
+
+impl R2D2Manager {
+    pub fn new(db: Database) -> Self {
+        Self {
+            db,
+            rt: tokio::runtime::Handle::current(),
+        }
+    }
+}
+
+impl r2d2::ManageConnection for R2D2Manager {
+    type Connection = Connection;
+    type Error = crate::Error;
+
+    fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        self.db.connect()
+    }
+
+    fn is_valid(&self, conn: &mut Self::Connection) -> Result<(), Self::Error> {
+        self.rt.block_on(conn.query("SELECT 1", ()))?;
+        Ok(())
+    }
+
+    fn has_broken(&self, _conn: &mut Self::Connection) -> bool {
+        false
+    }
+}
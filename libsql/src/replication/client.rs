@@ -1,4 +1,6 @@
 use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use anyhow::Context as _;
@@ -12,6 +14,7 @@ use tonic::{
     codegen::InterceptedService,
     metadata::{AsciiMetadataValue, BinaryMetadataValue},
     service::Interceptor,
+    Status,
 };
 use tonic_web::{GrpcWebCall, GrpcWebClientService};
 use tower::{Service, ServiceBuilder};
@@ -33,11 +36,20 @@ type ResponseBody = trace::ResponseBody<
     trace::DefaultOnFailure,
 >;
 
+#[derive(Debug, Clone)]
+struct Endpoint {
+    replication: ReplicationLogClient<InterceptedService<GrpcChannel, GrpcInterceptor>>,
+    proxy: ProxyClient<InterceptedService<GrpcChannel, GrpcInterceptor>>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Client {
     client_id: Uuid,
-    pub(crate) replication: ReplicationLogClient<InterceptedService<GrpcChannel, GrpcInterceptor>>,
-    proxy: ProxyClient<InterceptedService<GrpcChannel, GrpcInterceptor>>,
+    // One endpoint per candidate origin. There's always at least one. `current` picks which one
+    // new calls go through; `advance` moves it to the next candidate, wrapping around, so a
+    // primary that comes back later is eventually retried instead of being abandoned forever.
+    endpoints: Arc<[Endpoint]>,
+    current: Arc<AtomicUsize>,
 }
 
 impl Client {
@@ -49,6 +61,33 @@ impl Client {
         http_request_callback: Option<HttpRequestCallback>,
         maybe_namespace: Option<String>,
     ) -> anyhow::Result<Self> {
+        Self::new_with_failover(
+            connector,
+            vec![origin],
+            auth_token,
+            version,
+            http_request_callback,
+            maybe_namespace,
+        )
+    }
+
+    /// Like [`Client::new`], but takes a list of candidate origins instead of a single one.
+    /// Write-forwarding calls (`execute_program`/`describe`) automatically retry against the next
+    /// candidate when one is unreachable. The replication log stream (`hello`/
+    /// `batch_log_entries`/`snapshot`) always targets `origins[0]` until [`Client::advance`] is
+    /// called, since [`RemoteClient`](super::remote_client::RemoteClient) already retries the
+    /// whole handshake loop on error and is the one that decides when a stream-level failure
+    /// warrants moving on to the next candidate.
+    pub fn new_with_failover(
+        connector: ConnectorService,
+        origins: Vec<Uri>,
+        auth_token: impl AsRef<str>,
+        version: Option<&str>,
+        http_request_callback: Option<HttpRequestCallback>,
+        maybe_namespace: Option<String>,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(!origins.is_empty(), "at least one origin is required");
+
         let ver = version.unwrap_or(env!("CARGO_PKG_VERSION"));
 
         let version: AsciiMetadataValue = format!("libsql-rpc-{ver}")
@@ -61,15 +100,13 @@ impl Client {
 
         let ns = if let Some(ns_from_arg) = maybe_namespace {
             ns_from_arg
-        } else if let Ok(ns_from_host) = split_namespace(origin.host().unwrap()) {
+        } else if let Ok(ns_from_host) = split_namespace(origins[0].host().unwrap()) {
             ns_from_host
         } else {
             "default".to_string()
         };
-        
-        let namespace = BinaryMetadataValue::from_bytes(ns.as_bytes());
 
-        let channel = GrpcChannel::new(connector, http_request_callback);
+        let namespace = BinaryMetadataValue::from_bytes(ns.as_bytes());
 
         let interceptor = GrpcInterceptor {
             auth_token,
@@ -77,24 +114,34 @@ impl Client {
             version,
         };
 
-        let replication = ReplicationLogClient::with_origin(
-            InterceptedService::new(channel.clone(), interceptor.clone()),
-            origin.clone(),
-        );
-
-        let proxy = ProxyClient::with_origin(InterceptedService::new(channel, interceptor), origin);
-
-        // Remove default tonic `8mb` message limits since fly may buffer
-        // messages causing the msg len to be longer.
-        let replication = replication.max_decoding_message_size(usize::MAX);
-        let proxy = proxy.max_decoding_message_size(usize::MAX);
-
-        let client_id = Uuid::new_v4();
+        let endpoints = origins
+            .into_iter()
+            .map(|origin| {
+                let channel = GrpcChannel::new(connector.clone(), http_request_callback.clone());
+
+                let replication = ReplicationLogClient::with_origin(
+                    InterceptedService::new(channel.clone(), interceptor.clone()),
+                    origin.clone(),
+                );
+
+                let proxy = ProxyClient::with_origin(
+                    InterceptedService::new(channel, interceptor.clone()),
+                    origin,
+                );
+
+                // Remove default tonic `8mb` message limits since fly may buffer messages
+                // causing the msg len to be longer.
+                Endpoint {
+                    replication: replication.max_decoding_message_size(usize::MAX),
+                    proxy: proxy.max_decoding_message_size(usize::MAX),
+                }
+            })
+            .collect::<Vec<_>>();
 
         Ok(Self {
-            client_id,
-            replication,
-            proxy,
+            client_id: Uuid::new_v4(),
+            endpoints: endpoints.into(),
+            current: Arc::new(AtomicUsize::new(0)),
         })
     }
 
@@ -106,26 +153,91 @@ impl Client {
         self.client_id.to_string()
     }
 
+    fn current(&self) -> &Endpoint {
+        &self.endpoints[self.current.load(Ordering::Relaxed) % self.endpoints.len()]
+    }
+
+    pub(crate) fn replication(
+        &self,
+    ) -> ReplicationLogClient<InterceptedService<GrpcChannel, GrpcInterceptor>> {
+        self.current().replication.clone()
+    }
+
+    /// Moves on to the next candidate origin, wrapping back to the first once every candidate has
+    /// been tried. Called after a transport-level failure talking to the current one.
+    pub(crate) fn advance(&self) {
+        if self.endpoints.len() > 1 {
+            self.current.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Reports a replication-stream error against the endpoint currently in use, failing over to
+    /// the next candidate if the error looks like the server was unreachable rather than a
+    /// legitimate application-level response.
+    pub(crate) fn note_replication_error(&self, status: &Status) {
+        if is_unreachable(status) {
+            tracing::warn!(
+                "replication endpoint unreachable ({status}), failing over to the next candidate origin"
+            );
+            self.advance();
+        }
+    }
+
     pub async fn execute_program(&self, program: ProgramReq) -> anyhow::Result<ExecuteResults> {
         // TODO(lucio): Map errors correctly
-        self.proxy
-            .clone()
-            .execute(program)
-            .await
-            .map(|r| r.into_inner())
-            .map_err(Into::into)
+        let attempts = self.endpoints.len();
+        let mut last_err = None;
+        for attempt in 0..attempts {
+            match self.current().proxy.clone().execute(program.clone()).await {
+                Ok(r) => return Ok(r.into_inner()),
+                Err(status) if attempt + 1 < attempts && is_unreachable(&status) => {
+                    tracing::warn!(
+                        "write-forwarding target unreachable ({status}), failing over to the next candidate origin"
+                    );
+                    self.advance();
+                    last_err = Some(status);
+                }
+                Err(status) => return Err(status.into()),
+            }
+        }
+        Err(last_err.expect("at least one attempt is always made").into())
     }
 
     pub async fn describe(&self, describe_req: DescribeRequest) -> anyhow::Result<DescribeResult> {
-        self.proxy
-            .clone()
-            .describe(describe_req)
-            .await
-            .map(|r| r.into_inner())
-            .map_err(Into::into)
+        let attempts = self.endpoints.len();
+        let mut last_err = None;
+        for attempt in 0..attempts {
+            match self
+                .current()
+                .proxy
+                .clone()
+                .describe(describe_req.clone())
+                .await
+            {
+                Ok(r) => return Ok(r.into_inner()),
+                Err(status) if attempt + 1 < attempts && is_unreachable(&status) => {
+                    tracing::warn!(
+                        "write-forwarding target unreachable ({status}), failing over to the next candidate origin"
+                    );
+                    self.advance();
+                    last_err = Some(status);
+                }
+                Err(status) => return Err(status.into()),
+            }
+        }
+        Err(last_err.expect("at least one attempt is always made").into())
     }
 }
 
+/// Whether a gRPC status looks like it came from a server that couldn't be reached at all, as
+/// opposed to one that was reached and returned a legitimate error.
+fn is_unreachable(status: &tonic::Status) -> bool {
+    matches!(
+        status.code(),
+        tonic::Code::Unavailable | tonic::Code::Unknown
+    )
+}
+
 #[derive(Debug, Clone)]
 pub struct GrpcChannel {
     client: BoxCloneService<http::Request<BoxBody>, http::Response<ResponseBody>, hyper::Error>,
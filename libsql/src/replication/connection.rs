@@ -194,6 +194,23 @@ impl RemoteConnection {
         matches!(self.inner.lock().state, State::Init)
     }
 
+    /// If this connection's writer is configured for
+    /// [`crate::replication::ConsistencyMode::ReadYourWrites`], blocks until the embedded replica
+    /// has caught up to the highest replication index this connection has written, so a local
+    /// read that follows always observes that write.
+    async fn wait_for_read_your_writes(&self) -> Result<()> {
+        let Some(replicator) = self.writer.as_ref().and_then(Writer::replicator) else {
+            return Ok(());
+        };
+        let target = self
+            .max_write_replication_index
+            .load(std::sync::atomic::Ordering::SeqCst);
+        if target > 0 {
+            replicator.sync_until(target).await?;
+        }
+        Ok(())
+    }
+
     pub(self) async fn execute_remote(
         &self,
         stmts: Vec<parser::Statement>,
@@ -218,6 +235,10 @@ impl RemoteConnection {
 
         self.update_max_write_replication_index(res.current_frame_no);
 
+        // Best-effort: nudge the replica towards this write now rather than waiting for the next
+        // background sync. This single round may not fully catch up (e.g. a large write, or a
+        // concurrent write from another client), so it's not by itself a read-your-writes
+        // guarantee -- that's `wait_for_read_your_writes`, called before the next local read.
         if let Some(replicator) = writer.replicator() {
             replicator.sync_oneshot().await?;
         }
@@ -245,6 +266,10 @@ impl RemoteConnection {
 
         self.update_max_write_replication_index(res.current_frame_no);
 
+        // Best-effort: nudge the replica towards this write now rather than waiting for the next
+        // background sync. This single round may not fully catch up (e.g. a large write, or a
+        // concurrent write from another client), so it's not by itself a read-your-writes
+        // guarantee -- that's `wait_for_read_your_writes`, called before the next local read.
         if let Some(replicator) = writer.replicator() {
             replicator.sync_oneshot().await?;
         }
@@ -302,6 +327,8 @@ impl Conn for RemoteConnection {
         let stmts = parser::Statement::parse(sql).collect::<Result<Vec<_>>>()?;
 
         if self.should_execute_local(&stmts[..])? {
+            self.wait_for_read_your_writes().await?;
+
             // TODO(lucio): See if we can arc the params here to cheaply clone
             // or convert the inner bytes type to an Arc<[u8]>
             let changes = self.local.execute(sql, params.clone()).await?;
@@ -341,6 +368,7 @@ impl Conn for RemoteConnection {
         let stmts = parser::Statement::parse(sql).collect::<Result<Vec<_>>>()?;
 
         if self.should_execute_local(&stmts[..])? {
+            self.wait_for_read_your_writes().await?;
             self.local.execute_batch(sql).await?;
 
             if !self.maybe_execute_rollback().await? {
@@ -384,6 +412,7 @@ impl Conn for RemoteConnection {
         }
 
         if self.should_execute_local(&stmts[..])? {
+            self.wait_for_read_your_writes().await?;
             self.local.execute_transactional_batch(sql).await?;
 
             if !self.maybe_execute_rollback().await? {
@@ -499,6 +528,8 @@ impl Conn for RemoteConnection {
             inner: Box::new(tx),
             conn: crate::Connection {
                 conn: Arc::new(self.clone()),
+                schema_cache: Default::default(),
+                query_tag: Default::default(),
             },
             close: None,
         })
@@ -586,6 +617,7 @@ impl RemoteStatement {
 
         if conn.should_execute_local(&stmts[..])? {
             tracing::trace!("Preparing {sql} locally");
+            conn.wait_for_read_your_writes().await?;
             let stmt = conn.local.prepare(sql).await?;
             return Ok(Self {
                 conn,
@@ -0,0 +1,132 @@
+//! Read-only utilities for inspecting a local embedded replica's on-disk replication state: its
+//! `client_wal_index` metadata file, and any snapshot files staged during a full resync. Intended
+//! for debugging embedded replicas in the field; nothing here is used by normal sync.
+
+use std::path::Path;
+
+use libsql_replication::frame::FrameNo;
+use libsql_replication::meta::WalIndexMeta;
+use libsql_replication::snapshot::SnapshotFile;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+use crate::{Error, Result};
+
+/// The replication generation and durably-applied frame position recorded in a replica's
+/// `client_wal_index` metadata file.
+#[derive(Debug, Clone, Copy)]
+pub struct ReplicaMeta {
+    /// Identifies the primary's replication log this replica last completed a handshake
+    /// against. Changes whenever the primary's log is reset (e.g. after a restore), at which
+    /// point a replica still on the previous generation must be recreated rather than resumed.
+    pub log_id: Uuid,
+    /// The last frame durably applied to this replica, or `None` if it has never completed a
+    /// handshake with its primary.
+    pub committed_frame_no: Option<FrameNo>,
+}
+
+/// Reads the `<db_path>-client_wal_index` metadata file next to an embedded replica's database
+/// at `db_path`, the same file `Database::open_with_local_sync` and friends read and write.
+/// Returns `None` if the file doesn't exist yet, i.e. the replica has never synced.
+pub async fn read_replica_meta(db_path: impl AsRef<Path>) -> Result<Option<ReplicaMeta>> {
+    let meta = WalIndexMeta::open_prefixed(db_path.as_ref())
+        .await
+        .map_err(|e| Error::Replication(e.into()))?;
+    Ok(meta.data.map(|data| ReplicaMeta {
+        log_id: data.log_id(),
+        committed_frame_no: (data.committed_frame_no.get() != FrameNo::MAX)
+            .then_some(data.committed_frame_no.get()),
+    }))
+}
+
+/// Metadata read from a snapshot file's header, without streaming its frames.
+#[derive(Debug, Clone, Copy)]
+pub struct SnapshotInfo {
+    /// The replication log this snapshot was taken from.
+    pub log_id: Uuid,
+    /// The first frame covered by the snapshot.
+    pub start_frame_no: FrameNo,
+    /// The last frame covered by the snapshot.
+    pub end_frame_no: FrameNo,
+    /// Number of (deduplicated) frames stored in the snapshot.
+    pub frame_count: u64,
+    /// Size of the database, in pages, after applying the snapshot.
+    pub size_after_pages: u32,
+}
+
+/// Reads a snapshot file's header at `path`, one of the files injected into a replica during a
+/// full resync (see [`super::Frames::Snapshot`]).
+pub async fn inspect_snapshot(path: impl AsRef<Path>) -> Result<SnapshotInfo> {
+    let file = SnapshotFile::open(path, None)
+        .await
+        .map_err(|e| Error::Replication(e.into()))?;
+    let header = file.header();
+    Ok(SnapshotInfo {
+        log_id: Uuid::from_u128(header.log_id.get()),
+        start_frame_no: header.start_frame_no.get(),
+        end_frame_no: header.end_frame_no.get(),
+        frame_count: header.frame_count.get(),
+        size_after_pages: header.size_after.get(),
+    })
+}
+
+/// A single frame's header, without its page data.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameSummary {
+    pub frame_no: FrameNo,
+    pub page_no: u32,
+    pub checksum: u64,
+    pub is_commit: bool,
+}
+
+/// Reads every frame header in a snapshot file at `path`, in on-disk order (from the highest
+/// frame_no down to the lowest), without validating the checksum chain -- see
+/// [`first_broken_checksum`] for that.
+pub async fn snapshot_frames(path: impl AsRef<Path>) -> Result<Vec<FrameSummary>> {
+    let file = SnapshotFile::open(path, None)
+        .await
+        .map_err(|e| Error::Replication(e.into()))?;
+    let stream = file.into_stream_mut();
+    tokio::pin!(stream);
+
+    let mut frames = Vec::new();
+    while let Some(frame) = stream.next().await {
+        let frame = frame.map_err(|e| Error::Replication(e.into()))?;
+        let header = frame.header();
+        frames.push(FrameSummary {
+            frame_no: header.frame_no.get(),
+            page_no: header.page_no.get(),
+            checksum: header.checksum.get(),
+            is_commit: frame.is_commit(),
+        });
+    }
+    Ok(frames)
+}
+
+/// Verifies the rolling checksum chain across every frame in a snapshot file at `path`. Since
+/// snapshot frames are stored from the highest frame_no down to the lowest, this walks them in
+/// that order, checking each one against the checksum carried by its logical predecessor (the
+/// next, lower-numbered frame read off the stream). The very first frame in the chain (the
+/// lowest frame_no in the file) has nothing to check against and is assumed valid.
+///
+/// Returns the frame_no of the first frame whose checksum doesn't match, or `None` if the whole
+/// chain checks out.
+pub async fn first_broken_checksum(path: impl AsRef<Path>) -> Result<Option<FrameNo>> {
+    let file = SnapshotFile::open(path, None)
+        .await
+        .map_err(|e| Error::Replication(e.into()))?;
+    let stream = file.into_stream_mut();
+    tokio::pin!(stream);
+
+    let mut newer: Option<libsql_replication::frame::FrameMut> = None;
+    while let Some(frame) = stream.next().await {
+        let frame = frame.map_err(|e| Error::Replication(e.into()))?;
+        if let Some(newer_frame) = newer.take() {
+            if !newer_frame.verify_checksum(frame.header().checksum.get()) {
+                return Ok(Some(newer_frame.header().frame_no.get()));
+            }
+        }
+        newer = Some(frame);
+    }
+    Ok(None)
+}
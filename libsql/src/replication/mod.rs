@@ -1,7 +1,7 @@
 //! Utilities used when using a replicated version of libsql.
 
 use std::path::PathBuf;
-use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -31,11 +31,25 @@ use self::remote_client::RemoteClient;
 
 pub(crate) mod client;
 pub(crate) mod connection;
+pub mod inspect;
 pub(crate) mod local_client;
 pub(crate) mod remote_client;
 
 pub use crate::database::Replicated;
 
+/// A source of replication frames that can be fed into an embedded replica.
+///
+/// Implement this trait to replicate from a transport other than the built-in HTTP/gRPC
+/// primary connection, e.g. a message queue, a local file, or a peer-to-peer channel. The
+/// HTTP-based primary connection used by `Database::open_with_remote_sync` is itself just one
+/// implementation of this trait.
+pub use libsql_replication::replicator::ReplicatorClient as FrameSource;
+
+/// Callback invoked with the error from a failed background periodic sync (see
+/// [`crate::database::Builder::sync_interval`]), in addition to the `tracing::error!` this crate
+/// already logs, so applications can surface sync failures without scraping logs.
+pub type SyncErrorCallback = Arc<dyn Fn(&errors::Error) + Send + Sync>;
+
 /// A set of rames to be injected via `sync_frames`.
 pub enum Frames {
     /// A set of frames, in increasing frame_no.
@@ -45,6 +59,19 @@ pub enum Frames {
     Snapshot(SnapshotFile),
 }
 
+/// Controls whether a local read on an embedded replica may return data older than the caller's
+/// own most recent write made through the remote writer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConsistencyMode {
+    /// Local reads may lag behind the caller's own writes until the next background sync.
+    #[default]
+    Eventual,
+    /// Before executing a query locally, wait until the replica has caught up to the highest
+    /// replication index this connection has written, so a read always observes its own prior
+    /// writes.
+    ReadYourWrites,
+}
+
 /// Detailed logs about bytes synced with primary
 pub struct SyncUsageStats {
     prefetched_bytes: u64,
@@ -99,6 +126,28 @@ impl SyncUsageStats {
     }
 }
 
+/// The outcome of a call to [`crate::Database::sync_with_report`].
+///
+/// Because each batch of frames is durably applied (and the replicator's cursor persisted)
+/// before the next batch is requested, a `sync_with_report` call can be cancelled or interrupted
+/// at any point and safely resumed later: the next call picks up from the last durably applied
+/// frame rather than redoing work.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SyncReport {
+    /// Number of frames durably applied during this call.
+    pub frames_applied: usize,
+    /// Number of bytes received over the wire during this call.
+    pub bytes: u64,
+    /// Wall-clock time spent syncing during this call.
+    pub duration: Duration,
+    /// Whether the replica is caught up with the primary as of the end of this call.
+    pub caught_up: bool,
+    /// The replica's committed replication index as of the end of this call, or `None` if
+    /// nothing has been replicated yet. Mirrors [`crate::Database::replication_index`], but
+    /// avoids an extra round trip when a report is already in hand.
+    pub replication_index: Option<FrameNo>,
+}
+
 #[derive(Clone)]
 pub(crate) struct Writer {
     pub(crate) client: client::Client,
@@ -167,6 +216,9 @@ pub(crate) struct EmbeddedReplicator {
     replicator: Arc<Mutex<Replicator<Either<RemoteClient, LocalClient>, SqliteInjector>>>,
     bg_abort: Option<Arc<DropAbort>>,
     last_frames_synced: Arc<AtomicUsize>,
+    /// Whether the background periodic sync task (if any) is currently paused. See
+    /// [`Self::pause`]/[`Self::resume`].
+    paused: Arc<AtomicBool>,
 }
 
 impl From<libsql_replication::replicator::Error> for errors::Error {
@@ -182,6 +234,7 @@ impl EmbeddedReplicator {
         auto_checkpoint: u32,
         encryption_config: Option<EncryptionConfig>,
         perodic_sync: Option<Duration>,
+        sync_error_callback: Option<SyncErrorCallback>,
     ) -> Result<Self> {
         let mut replicator =
             Replicator::new_sqlite(
@@ -197,16 +250,23 @@ impl EmbeddedReplicator {
             replicator,
             bg_abort: None,
             last_frames_synced: Arc::new(AtomicUsize::new(0)),
+            paused: Arc::new(AtomicBool::new(false)),
         };
 
         if let Some(sync_duration) = perodic_sync {
             let replicator2 = replicator.clone();
+            let paused = replicator.paused.clone();
 
             let jh = tokio::spawn(
                 async move {
                     loop {
-                        if let Err(e) = replicator2.sync_oneshot().await {
-                            tracing::error!("replicator sync error: {}", e);
+                        if !paused.load(Ordering::SeqCst) {
+                            if let Err(e) = replicator2.sync_oneshot().await {
+                                tracing::error!("replicator sync error: {}", e);
+                                if let Some(sync_error_callback) = &sync_error_callback {
+                                    sync_error_callback(&e);
+                                }
+                            }
                         }
 
                         tokio::time::sleep(sync_duration).await;
@@ -241,9 +301,22 @@ impl EmbeddedReplicator {
             replicator,
             bg_abort: None,
             last_frames_synced: Arc::new(AtomicUsize::new(0)),
+            paused: Arc::new(AtomicBool::new(false)),
         })
     }
 
+    /// Pauses the background periodic sync task started via `sync_interval`, if any. Has no
+    /// effect if periodic sync isn't enabled. Reads and explicit `sync`/`sync_until` calls are
+    /// unaffected.
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Resumes the background periodic sync task after a [`Self::pause`].
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
     pub async fn get_sync_usage_stats(&self) -> Result<SyncUsageStats> {
         let mut replicator = self.replicator.lock().await;
         match replicator.client_mut() {
@@ -385,6 +458,24 @@ impl EmbeddedReplicator {
             .client_mut()
             .committed_frame_no()
     }
+
+    /// Repeatedly syncs until the replica has caught up to `replication_index`. Used to implement
+    /// [`ConsistencyMode::ReadYourWrites`] for [`RemoteConnection`], which only has access to a
+    /// `Writer`'s `EmbeddedReplicator` rather than the owning [`crate::local::Database`] (whose
+    /// own `sync_until` this mirrors).
+    pub async fn sync_until(&self, replication_index: FrameNo) -> Result<Replicated> {
+        let mut frame_no = self.committed_frame_no().await;
+        let mut frames_synced = 0;
+        while frame_no.unwrap_or(0) < replication_index {
+            let res = self.sync_oneshot().await?;
+            frame_no = res.frame_no();
+            frames_synced += res.frames_synced();
+        }
+        Ok(Replicated {
+            frame_no,
+            frames_synced,
+        })
+    }
 }
 
 struct DropAbort(AbortHandle);
@@ -191,16 +191,22 @@ impl RemoteClient {
             next_offset: self.next_offset(),
             wal_flavor: None,
         });
-        let mut client_clone = self.remote.clone();
+        let client_clone = self.remote.clone();
         let hello_fut = time(async {
-            let res = self.remote.replication.hello(hello_req).await;
+            let res = self.remote.replication().hello(hello_req).await;
+            if let Err(status) = &res {
+                self.remote.note_replication_error(status);
+            }
             self.handle_handshake_response(res).await
         });
         let (hello, frames) = if prefetch {
             let (hello, frames) = tokio::join!(
                 hello_fut,
-                time(client_clone.replication.batch_log_entries(log_offset_req))
+                time(client_clone.replication().batch_log_entries(log_offset_req))
             );
+            if let (Err(status), _) = &frames {
+                client_clone.note_replication_error(status);
+            }
             (hello, Some(frames))
         } else {
             (hello_fut.await, None)
@@ -276,10 +282,13 @@ impl RemoteClient {
                     next_offset: self.next_offset(),
                     wal_flavor: None,
                 });
-                let result = time(self.remote.replication.batch_log_entries(req)).await;
+                let result = time(self.remote.replication().batch_log_entries(req)).await;
                 (result, false)
             }
         };
+        if let (Err(status), _) = &frames {
+            self.remote.note_replication_error(status);
+        }
         let res = self.handle_next_frames_response(frames, prefetched).await;
         (res, time)
     }
@@ -290,11 +299,11 @@ impl RemoteClient {
             wal_flavor: None,
         });
         let sync_stats = self.sync_stats.clone();
-        let mut frames = self
-            .remote
-            .replication
-            .snapshot(req)
-            .await?
+        let snapshot = self.remote.replication().snapshot(req).await;
+        if let Err(status) = &snapshot {
+            self.remote.note_replication_error(status);
+        }
+        let mut frames = snapshot?
             .into_inner()
             .map_err(|e| e.into())
             .map_ok(move |f| {
@@ -1,4 +1,4 @@
-use crate::{Result, Value, ValueType};
+use crate::{CoercionPolicy, Result, Value, ValueType};
 use std::fmt;
 
 /// Represents a libsql column.
@@ -119,6 +119,27 @@ impl Row {
         T::from_sql(val)
     }
 
+    /// Like [`Row::get`], but using `policy` for this call only, instead of the
+    /// [`CoercionPolicy::Strict`] default `get` applies. Useful for reading a `NUMERIC`-affinity
+    /// column that might come back as either an `INTEGER` or a `REAL` depending on the row.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn run(row: &libsql::Row) {
+    /// use libsql::CoercionPolicy;
+    /// // `price` has NUMERIC affinity and this row happens to hold an integer value.
+    /// let price: f64 = row.get_with(0, CoercionPolicy::Lossy).unwrap();
+    /// # }
+    /// ```
+    pub fn get_with<T>(&self, idx: i32, policy: CoercionPolicy) -> Result<T>
+    where
+        T: FromValue,
+    {
+        let val = self.inner.column_value(idx)?;
+        T::from_sql_with_policy(val, policy)
+    }
+
     /// Fetch the value at the provided column index.
     pub fn get_value(&self, idx: i32) -> Result<Value> {
         self.inner.column_value(idx)
@@ -144,6 +165,28 @@ impl Row {
     pub fn column_type(&self, idx: i32) -> Result<ValueType> {
         self.inner.column_type(idx)
     }
+
+    /// Deserializes this row into `T`, mapping each column by name to a field of `T`. See
+    /// [`crate::de`] for details, including how to deserialize a `TEXT` column storing a JSON
+    /// document with [`crate::de::Json`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn run(row: &libsql::Row) {
+    /// #[derive(serde::Deserialize)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u64,
+    /// }
+    /// let person: Person = row.deserialize().unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn deserialize<'de, T: serde::Deserialize<'de>>(&'de self) -> Result<T> {
+        crate::de::from_row(self).map_err(|e| crate::Error::Misuse(e.to_string()))
+    }
 }
 
 impl fmt::Debug for Row {
@@ -157,6 +200,17 @@ pub trait FromValue: Sealed {
     fn from_sql(val: Value) -> Result<Self>
     where
         Self: Sized;
+
+    /// Like [`FromValue::from_sql`], but given the chance to apply a looser
+    /// [`CoercionPolicy`]. Implementors with no extra coercions to offer can rely on this
+    /// default, which ignores `policy` and defers to [`FromValue::from_sql`].
+    fn from_sql_with_policy(val: Value, policy: CoercionPolicy) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        let _ = policy;
+        Self::from_sql(val)
+    }
 }
 
 impl FromValue for crate::Value {
@@ -174,6 +228,13 @@ impl FromValue for i32 {
             _ => unreachable!("invalid value type"),
         }
     }
+
+    fn from_sql_with_policy(val: Value, policy: CoercionPolicy) -> Result<Self> {
+        match (val, policy) {
+            (Value::Real(f), CoercionPolicy::Lossy) => Ok(f as i32),
+            (val, _) => Self::from_sql(val),
+        }
+    }
 }
 impl Sealed for i32 {}
 
@@ -185,6 +246,13 @@ impl FromValue for u32 {
             _ => unreachable!("invalid value type"),
         }
     }
+
+    fn from_sql_with_policy(val: Value, policy: CoercionPolicy) -> Result<Self> {
+        match (val, policy) {
+            (Value::Real(f), CoercionPolicy::Lossy) => Ok(f as u32),
+            (val, _) => Self::from_sql(val),
+        }
+    }
 }
 impl Sealed for u32 {}
 
@@ -196,6 +264,13 @@ impl FromValue for i64 {
             _ => unreachable!("invalid value type"),
         }
     }
+
+    fn from_sql_with_policy(val: Value, policy: CoercionPolicy) -> Result<Self> {
+        match (val, policy) {
+            (Value::Real(f), CoercionPolicy::Lossy) => Ok(f as i64),
+            (val, _) => Self::from_sql(val),
+        }
+    }
 }
 impl Sealed for i64 {}
 
@@ -207,6 +282,13 @@ impl FromValue for u64 {
             _ => unreachable!("invalid value type"),
         }
     }
+
+    fn from_sql_with_policy(val: Value, policy: CoercionPolicy) -> Result<Self> {
+        match (val, policy) {
+            (Value::Real(f), CoercionPolicy::Lossy) => Ok(f as u64),
+            (val, _) => Self::from_sql(val),
+        }
+    }
 }
 impl Sealed for u64 {}
 
@@ -218,6 +300,13 @@ impl FromValue for f64 {
             _ => unreachable!("invalid value type"),
         }
     }
+
+    fn from_sql_with_policy(val: Value, policy: CoercionPolicy) -> Result<Self> {
+        match (val, policy) {
+            (Value::Integer(i), CoercionPolicy::Lossy) => Ok(i as f64),
+            (val, _) => Self::from_sql(val),
+        }
+    }
 }
 impl Sealed for f64 {}
 
@@ -281,6 +370,13 @@ where
             _ => T::from_sql(val).map(Some),
         }
     }
+
+    fn from_sql_with_policy(val: Value, policy: CoercionPolicy) -> Result<Self> {
+        match val {
+            Value::Null => Ok(None),
+            _ => T::from_sql_with_policy(val, policy).map(Some),
+        }
+    }
 }
 impl<T> Sealed for Option<T> {}
 
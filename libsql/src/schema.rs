@@ -0,0 +1,170 @@
+//! A per-[`Connection`] cache of table metadata, refreshed automatically whenever SQLite's
+//! `schema_version` changes so callers don't have to remember to invalidate it themselves.
+
+use crate::{Connection, Result, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// A table's columns and indexes, as reported by `PRAGMA table_info`/`PRAGMA index_list`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableSchema {
+    pub name: String,
+    pub columns: Vec<ColumnSchema>,
+    pub indexes: Vec<IndexSchema>,
+}
+
+/// A single column, as reported by `PRAGMA table_info`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub decl_type: Option<String>,
+    pub not_null: bool,
+    pub primary_key: bool,
+    pub default_value: Option<String>,
+}
+
+/// A single index, as reported by `PRAGMA index_list`/`PRAGMA index_info`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexSchema {
+    pub name: String,
+    pub unique: bool,
+    pub columns: Vec<String>,
+}
+
+/// No real `schema_version` is ever negative, so this sentinel can never collide with one and
+/// marks a cache that hasn't been populated yet.
+const UNPOPULATED: i64 = i64::MIN;
+
+pub(crate) struct SchemaCache {
+    version: AtomicI64,
+    tables: RwLock<Arc<HashMap<String, TableSchema>>>,
+}
+
+impl Default for SchemaCache {
+    fn default() -> Self {
+        SchemaCache {
+            version: AtomicI64::new(UNPOPULATED),
+            tables: RwLock::new(Arc::new(HashMap::new())),
+        }
+    }
+}
+
+impl Connection {
+    /// Returns this connection's tables, keyed by name, rebuilding the cache if the database's
+    /// schema has changed (tracked via SQLite's `PRAGMA schema_version`) since it was last built.
+    pub async fn schema(&self) -> Result<Arc<HashMap<String, TableSchema>>> {
+        let version = self.schema_version().await?;
+        if self.schema_cache.version.load(Ordering::Acquire) == version {
+            return Ok(self.schema_cache.tables.read().unwrap().clone());
+        }
+
+        let tables = Arc::new(self.load_schema().await?);
+        *self.schema_cache.tables.write().unwrap() = tables.clone();
+        self.schema_cache.version.store(version, Ordering::Release);
+        Ok(tables)
+    }
+
+    async fn schema_version(&self) -> Result<i64> {
+        let mut rows = self.query("PRAGMA schema_version", ()).await?;
+        let row = rows.next().await?.expect("PRAGMA schema_version always returns one row");
+        row.get(0)
+    }
+
+    async fn load_schema(&self) -> Result<HashMap<String, TableSchema>> {
+        let mut names = Vec::new();
+        let mut rows = self
+            .query(
+                "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+                (),
+            )
+            .await?;
+        while let Some(row) = rows.next().await? {
+            names.push(row.get::<String>(0)?);
+        }
+
+        let mut tables = HashMap::with_capacity(names.len());
+        for name in names {
+            let columns = self.table_columns(&name).await?;
+            let indexes = self.table_indexes(&name).await?;
+            tables.insert(
+                name.clone(),
+                TableSchema {
+                    name,
+                    columns,
+                    indexes,
+                },
+            );
+        }
+        Ok(tables)
+    }
+
+    async fn table_columns(&self, table: &str) -> Result<Vec<ColumnSchema>> {
+        let mut rows = self
+            .query(&format!("PRAGMA table_info({})", quote_ident(table)), ())
+            .await?;
+
+        let mut columns = Vec::new();
+        while let Some(row) = rows.next().await? {
+            // `dflt_value` isn't guaranteed to be text (e.g. `DEFAULT 0` reports an integer), so
+            // go through `get_value` and render it ourselves rather than `FromValue`, which panics
+            // on an unexpected `Value` variant.
+            let default_value = match row.get_value(4)? {
+                Value::Null => None,
+                value => Some(value_to_string(&value)),
+            };
+            columns.push(ColumnSchema {
+                name: row.get(1)?,
+                decl_type: row.get::<Option<String>>(2)?,
+                not_null: row.get::<i64>(3)? != 0,
+                default_value,
+                primary_key: row.get::<i64>(5)? != 0,
+            });
+        }
+        Ok(columns)
+    }
+
+    async fn table_indexes(&self, table: &str) -> Result<Vec<IndexSchema>> {
+        let mut index_rows = self
+            .query(&format!("PRAGMA index_list({})", quote_ident(table)), ())
+            .await?;
+
+        let mut names_and_uniqueness = Vec::new();
+        while let Some(row) = index_rows.next().await? {
+            names_and_uniqueness.push((row.get::<String>(1)?, row.get::<i64>(2)? != 0));
+        }
+
+        let mut indexes = Vec::with_capacity(names_and_uniqueness.len());
+        for (name, unique) in names_and_uniqueness {
+            let mut columns = Vec::new();
+            let mut info_rows = self
+                .query(&format!("PRAGMA index_info({})", quote_ident(&name)), ())
+                .await?;
+            while let Some(row) = info_rows.next().await? {
+                if let Some(column) = row.get::<Option<String>>(2)? {
+                    columns.push(column);
+                }
+            }
+            indexes.push(IndexSchema {
+                name,
+                unique,
+                columns,
+            });
+        }
+        Ok(indexes)
+    }
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Integer(i) => i.to_string(),
+        Value::Real(f) => f.to_string(),
+        Value::Text(s) => s.clone(),
+        Value::Blob(b) => format!("{:?}", b),
+    }
+}
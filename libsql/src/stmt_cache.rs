@@ -0,0 +1,133 @@
+//! An opt-in cache of prepared statements for a single connection, keyed by SQL text, modeled on
+//! rusqlite's `Connection::prepare_cached`. Re-parsing and re-planning hot statements on every
+//! call is measurable overhead for local and embedded replica connections; wrapping a
+//! [`Connection`] in a [`StatementCache`] amortizes that across repeated calls with the same SQL.
+
+use std::collections::VecDeque;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::{Connection, Result, Statement};
+
+/// Hit/miss counters for a [`StatementCache`], snapshotted via [`StatementCache::stats`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StatementCacheStats {
+    /// Number of [`StatementCache::prepare_cached`] calls served from the cache.
+    pub hits: u64,
+    /// Number of [`StatementCache::prepare_cached`] calls that had to prepare a fresh statement.
+    pub misses: u64,
+}
+
+struct Entry {
+    sql: Arc<str>,
+    stmt: Statement,
+}
+
+struct Inner {
+    conn: Connection,
+    capacity: usize,
+    entries: Mutex<VecDeque<Entry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Wraps a [`Connection`] with an LRU cache of prepared [`Statement`]s. Caching is opt-in: only
+/// statements prepared through [`StatementCache::prepare_cached`] are cached, so callers that use
+/// [`Connection::prepare`] directly are unaffected.
+#[derive(Clone)]
+pub struct StatementCache {
+    inner: Arc<Inner>,
+}
+
+impl StatementCache {
+    /// Wraps `conn`, caching up to `capacity` distinct statements. A `capacity` of `0` disables
+    /// caching: every call falls through to [`Connection::prepare`] and nothing is retained.
+    pub fn new(conn: Connection, capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                conn,
+                capacity,
+                entries: Mutex::new(VecDeque::new()),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Prepares `sql`, reusing a cached statement if one is available instead of preparing a new
+    /// one. The returned [`CachedStatement`] is returned to the cache when dropped instead of
+    /// being discarded, unless the cache is already at capacity.
+    pub async fn prepare_cached(&self, sql: &str) -> Result<CachedStatement> {
+        let cached = {
+            let mut entries = self.inner.entries.lock().unwrap();
+            entries
+                .iter()
+                .position(|e| &*e.sql == sql)
+                .map(|i| entries.remove(i).unwrap())
+        };
+
+        let entry = match cached {
+            Some(entry) => {
+                self.inner.hits.fetch_add(1, Ordering::Relaxed);
+                entry
+            }
+            None => {
+                self.inner.misses.fetch_add(1, Ordering::Relaxed);
+                Entry {
+                    sql: Arc::from(sql),
+                    stmt: self.inner.conn.prepare(sql).await?,
+                }
+            }
+        };
+
+        Ok(CachedStatement {
+            entry: Some(entry),
+            cache: self.inner.clone(),
+        })
+    }
+
+    /// Current hit/miss counters, accumulated since the cache was created.
+    pub fn stats(&self) -> StatementCacheStats {
+        StatementCacheStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A [`Statement`] checked out of a [`StatementCache`]. Derefs to the underlying [`Statement`]
+/// and is returned to the cache's LRU list when dropped.
+pub struct CachedStatement {
+    entry: Option<Entry>,
+    cache: Arc<Inner>,
+}
+
+impl Deref for CachedStatement {
+    type Target = Statement;
+
+    fn deref(&self) -> &Statement {
+        &self.entry.as_ref().expect("statement taken").stmt
+    }
+}
+
+impl DerefMut for CachedStatement {
+    fn deref_mut(&mut self) -> &mut Statement {
+        &mut self.entry.as_mut().expect("statement taken").stmt
+    }
+}
+
+impl Drop for CachedStatement {
+    fn drop(&mut self) {
+        let Some(mut entry) = self.entry.take() else {
+            return;
+        };
+
+        entry.stmt.reset();
+
+        let mut entries = self.cache.entries.lock().unwrap();
+        if entries.len() < self.cache.capacity {
+            entries.push_back(entry);
+        }
+    }
+}
@@ -97,6 +97,16 @@ struct InfoResult {
     current_generation: u32,
 }
 
+/// Progress of the one-time database bootstrap performed when an embedded replica is first
+/// created from a non-empty remote database.
+#[derive(Debug, Clone, Copy)]
+pub struct BootstrapProgress {
+    /// Bytes of the database export downloaded so far.
+    pub bytes_downloaded: u64,
+    /// Total size of the export, if the server reported a `Content-Length`.
+    pub total_bytes: Option<u64>,
+}
+
 pub struct SyncContext {
     db_path: String,
     client: hyper::Client<ConnectorService, Body>,
@@ -111,6 +121,8 @@ pub struct SyncContext {
     /// whenever sync is called very first time, we will call the remote server
     /// to get the generation information and sync the db file if needed
     initial_server_sync: bool,
+    /// Invoked with download progress while the initial db bootstrap is in flight.
+    bootstrap_progress_cb: Option<std::sync::Arc<dyn Fn(BootstrapProgress) + Send + Sync>>,
 }
 
 impl SyncContext {
@@ -140,6 +152,7 @@ impl SyncContext {
             durable_generation: 0,
             durable_frame_num: 0,
             initial_server_sync: false,
+            bootstrap_progress_cb: None,
         };
 
         if let Err(e) = me.read_metadata().await {
@@ -156,6 +169,15 @@ impl SyncContext {
         self.push_batch_size = push_batch_size;
     }
 
+    /// Register a callback invoked with [`BootstrapProgress`] while the initial replica
+    /// bootstrap (the one-shot download of the remote database) is in flight.
+    pub fn set_bootstrap_progress_callback(
+        &mut self,
+        cb: impl Fn(BootstrapProgress) + Send + Sync + 'static,
+    ) {
+        self.bootstrap_progress_cb = Some(std::sync::Arc::new(cb));
+    }
+
     #[tracing::instrument(skip(self))]
     pub(crate) async fn pull_one_frame(
         &mut self,
@@ -597,10 +619,27 @@ impl SyncContext {
             );
         }
 
+        let total_bytes = res
+            .headers()
+            .get(hyper::header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok());
+
         // todo: do streaming write to the disk
-        let bytes = hyper::body::to_bytes(res.into_body())
-            .await
-            .map_err(SyncError::HttpBody)?;
+        use futures::StreamExt as _;
+        let mut bytes = bytes::BytesMut::new();
+        let mut body = res.into_body();
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk.map_err(SyncError::HttpBody)?;
+            bytes.extend_from_slice(&chunk);
+            if let Some(cb) = &self.bootstrap_progress_cb {
+                cb(BootstrapProgress {
+                    bytes_downloaded: bytes.len() as u64,
+                    total_bytes,
+                });
+            }
+        }
+        let bytes = bytes.freeze();
 
         atomic_write(&self.db_path, &bytes).await?;
         self.durable_generation = generation;
@@ -152,6 +152,8 @@ impl Conn for SyncedConnection {
             inner: Box::new(tx),
             conn: crate::Connection {
                 conn: Arc::new(self.clone()),
+                schema_cache: Default::default(),
+                query_tag: Default::default(),
             },
             close: None,
         })
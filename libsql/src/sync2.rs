@@ -0,0 +1,93 @@
+//! Changeset-based bidirectional synchronization between two databases, built on top of the
+//! session extension (see [`crate::local::session`]).
+//!
+//! Each side tracks its own changes with a [`SyncSession`]; [`sync_once`] exchanges the
+//! changesets accumulated on each side since the last call and applies them to the other,
+//! resolving any conflicts with a [`ConflictStrategy`]. This covers local<->local sync directly;
+//! local<->remote sync is supported by shipping the changeset returned by
+//! [`SyncSession::changeset`] over whatever transport the remote side uses, then feeding what
+//! comes back into [`SyncSession::apply`].
+
+use crate::local::session::{ConflictAction, ConflictHandler, ConflictType};
+use crate::local::Connection as LocalConnection;
+use crate::local::Session;
+use crate::Result;
+
+/// Decides how to resolve a conflict encountered while applying a changeset during a sync.
+pub trait ConflictStrategy {
+    fn resolve(&mut self, conflict: ConflictType, table: &str) -> ConflictAction;
+}
+
+impl<F> ConflictStrategy for F
+where
+    F: FnMut(ConflictType, &str) -> ConflictAction,
+{
+    fn resolve(&mut self, conflict: ConflictType, table: &str) -> ConflictAction {
+        (self)(conflict, table)
+    }
+}
+
+/// Always takes the incoming side's version of a conflicting row, i.e. whichever side syncs last
+/// wins.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LastWriteWins;
+
+impl ConflictStrategy for LastWriteWins {
+    fn resolve(&mut self, _conflict: ConflictType, _table: &str) -> ConflictAction {
+        ConflictAction::Replace
+    }
+}
+
+struct StrategyAsConflictHandler<'a, S>(&'a mut S);
+
+impl<S: ConflictStrategy> ConflictHandler for StrategyAsConflictHandler<'_, S> {
+    fn handle_conflict(&mut self, conflict: ConflictType, table: &str) -> ConflictAction {
+        self.0.resolve(conflict, table)
+    }
+}
+
+/// Tracks one side of a sync: a connection together with a [`Session`] recording every change
+/// made to it since the last [`changeset`](Self::changeset) call.
+pub struct SyncSession<'conn> {
+    conn: &'conn LocalConnection,
+    session: Session<'conn>,
+}
+
+impl<'conn> SyncSession<'conn> {
+    /// Starts tracking every table of `conn`.
+    pub fn new(conn: &'conn LocalConnection) -> Result<Self> {
+        let mut session = conn.create_session()?;
+        session.attach(None)?;
+        Ok(Self { conn, session })
+    }
+
+    /// Serializes the changes recorded since this session was created or last read, to be sent
+    /// to the other side of the sync.
+    pub fn changeset(&self) -> Result<Vec<u8>> {
+        self.session.changeset()
+    }
+
+    /// Applies a changeset received from the other side of the sync, resolving conflicts with
+    /// `strategy`.
+    pub fn apply<S: ConflictStrategy>(&self, changeset: &[u8], strategy: &mut S) -> Result<()> {
+        if changeset.is_empty() {
+            return Ok(());
+        }
+        self.conn
+            .apply_changeset(changeset, StrategyAsConflictHandler(strategy))
+    }
+}
+
+/// Exchanges and applies the changesets accumulated on `a` and `b` since they were last synced,
+/// resolving conflicts with `strategy`.
+pub fn sync_once<S: ConflictStrategy>(
+    a: &SyncSession<'_>,
+    b: &SyncSession<'_>,
+    mut strategy: S,
+) -> Result<()> {
+    let from_a = a.changeset()?;
+    let from_b = b.changeset()?;
+    b.apply(&from_a, &mut strategy)?;
+    a.apply(&from_b, &mut strategy)?;
+    Ok(())
+}
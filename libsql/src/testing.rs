@@ -0,0 +1,341 @@
+//! An in-process, in-memory stand-in for a remote sqld server, for unit-testing applications
+//! that talk to libsql over [`crate::Builder::new_remote`] without a live server.
+//!
+//! [`MockServer`] speaks just enough of the Hrana-over-HTTP JSON pipeline protocol (the one
+//! served at `/v2/pipeline` and `/v3/pipeline`) to answer the requests a basic connect-and-execute
+//! round trip issues: `Execute`, `Batch`, `GetAutocommit` and `Close` stream requests. It hands
+//! back scripted [`StmtResult`]s (or errors) in the order they were queued, falling back to an
+//! empty success when nothing is scripted, and records every [`Stmt`] it receives so a test can
+//! assert on what the application actually sent.
+//!
+//! `Sequence`, `Describe`, `StoreSql`, `CloseSql` and cursors (`/v3/cursor`) are not implemented -
+//! they back raw-SQL caching and [`crate::Statement::columns`]-style introspection, which a basic
+//! round trip doesn't exercise - and make the mock answer with a stream error instead.
+//!
+//! ```
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! use libsql::Builder;
+//! use libsql::testing::MockServer;
+//!
+//! let server = MockServer::new();
+//! let db = Builder::new_remote(server.url(), String::new())
+//!     .connector(server.connector())
+//!     .build()
+//!     .await?;
+//! let conn = db.connect()?;
+//! conn.execute("INSERT INTO users (email) VALUES ('alice@example.org')", ())
+//!     .await?;
+//!
+//! assert_eq!(server.requests()[0].sql.as_deref(), Some("INSERT INTO users (email) VALUES ('alice@example.org')"));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::util::{ConnectorService, Socket};
+use libsql_hrana::proto::{
+    BatchResult, BatchStreamResp, CloseStreamResp, Error as HranaError, ExecuteStreamResp,
+    GetAutocommitStreamResp, PipelineReqBody, PipelineRespBody, Stmt, StmtResult, StreamRequest,
+    StreamResponse, StreamResult,
+};
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tokio::io::{duplex, AsyncRead, AsyncWrite, DuplexStream};
+use tower::Service;
+
+/// Baton handed back for every stream the mock keeps open. The real protocol uses batons to let
+/// the server route a request to the right in-flight stream state; since [`MockServer`] only ever
+/// serves one connection at a time it doesn't need to tell streams apart, so a constant works.
+const MOCK_BATON: &str = "mock-baton";
+
+/// A scripted answer to the next statement [`MockServer`] is asked to run.
+#[derive(Debug, Clone)]
+pub enum ScriptedResponse {
+    /// Answer as if the statement succeeded with this result.
+    Result(StmtResult),
+    /// Answer as if the statement failed with this message.
+    Error(String),
+}
+
+impl From<StmtResult> for ScriptedResponse {
+    fn from(result: StmtResult) -> Self {
+        ScriptedResponse::Result(result)
+    }
+}
+
+#[derive(Default)]
+struct State {
+    script: Mutex<VecDeque<ScriptedResponse>>,
+    requests: Mutex<Vec<Stmt>>,
+}
+
+impl State {
+    fn record_and_answer(&self, stmt: Stmt) -> Result<StmtResult, HranaError> {
+        let answer = match self.script.lock().unwrap().pop_front() {
+            Some(ScriptedResponse::Result(result)) => Ok(result),
+            Some(ScriptedResponse::Error(message)) => Err(HranaError {
+                message,
+                code: "MOCK_ERROR".to_string(),
+            }),
+            None => Ok(StmtResult::default()),
+        };
+        self.requests.lock().unwrap().push(stmt);
+        answer
+    }
+}
+
+/// An in-process mock of a remote sqld server's Hrana-over-HTTP pipeline endpoint.
+pub struct MockServer {
+    url: String,
+    connector: ConnectorService,
+    state: Arc<State>,
+}
+
+impl Default for MockServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockServer {
+    /// Starts a new mock server. Each connection made through [`MockServer::connector`] is
+    /// served in-process over an in-memory duplex pipe, with no real networking involved.
+    pub fn new() -> Self {
+        let state = Arc::new(State::default());
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<DuplexStream>(8);
+        let connector = ConnectorService::new(MockConnector { tx });
+
+        let accept_state = state.clone();
+        tokio::spawn(async move {
+            while let Some(server_stream) = rx.recv().await {
+                let state = accept_state.clone();
+                tokio::spawn(async move {
+                    use hyper::service::service_fn;
+
+                    let service = service_fn(move |req: hyper::Request<hyper::Body>| {
+                        let state = state.clone();
+                        async move { Ok::<_, hyper::Error>(handle_request(&state, req).await) }
+                    });
+
+                    if let Err(err) = hyper::server::conn::Http::new()
+                        .serve_connection(server_stream, service)
+                        .await
+                    {
+                        tracing::debug!("MockServer connection closed: {err}");
+                    }
+                });
+            }
+        });
+
+        MockServer {
+            url: "http://mock.server".to_string(),
+            connector,
+            state,
+        }
+    }
+
+    /// The URL to pass to [`crate::Builder::new_remote`]. Since the connector returned by
+    /// [`MockServer::connector`] never actually resolves it, its value doesn't matter beyond
+    /// being a well-formed URL.
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// A connector that routes all traffic to this mock server, for use with
+    /// [`crate::Builder::connector`] (available on the builder returned by
+    /// [`crate::Builder::new_remote`]).
+    pub fn connector(&self) -> ConnectorService {
+        self.connector.clone()
+    }
+
+    /// Queues an answer for the next statement the server is asked to run. Statements are
+    /// answered in the order they're scripted; once the queue runs dry, statements succeed with
+    /// an empty [`StmtResult`].
+    pub fn script(&self, response: impl Into<ScriptedResponse>) {
+        self.state.script.lock().unwrap().push_back(response.into());
+    }
+
+    /// The statements the server has been asked to run so far, in the order it received them.
+    pub fn requests(&self) -> Vec<Stmt> {
+        self.state.requests.lock().unwrap().clone()
+    }
+}
+
+async fn handle_request(
+    state: &State,
+    req: hyper::Request<hyper::Body>,
+) -> hyper::Response<hyper::Body> {
+    if !req.uri().path().ends_with("/pipeline") {
+        return hyper::Response::builder()
+            .status(404)
+            .body(hyper::Body::empty())
+            .unwrap();
+    }
+
+    let body = match hyper::body::to_bytes(req.into_body()).await {
+        Ok(body) => body,
+        Err(err) => {
+            return hyper::Response::builder()
+                .status(500)
+                .body(hyper::Body::from(err.to_string()))
+                .unwrap()
+        }
+    };
+    let req: PipelineReqBody = match serde_json::from_slice(&body) {
+        Ok(req) => req,
+        Err(err) => {
+            return hyper::Response::builder()
+                .status(400)
+                .body(hyper::Body::from(format!("invalid pipeline request: {err}")))
+                .unwrap()
+        }
+    };
+
+    let resp = handle_pipeline(state, req);
+    let body = serde_json::to_vec(&resp).unwrap();
+    hyper::Response::builder()
+        .status(200)
+        .body(hyper::Body::from(body))
+        .unwrap()
+}
+
+fn handle_pipeline(state: &State, req: PipelineReqBody) -> PipelineRespBody {
+    let mut closed = false;
+    let results = req
+        .requests
+        .into_iter()
+        .map(|req| handle_stream_request(state, req, &mut closed))
+        .collect();
+
+    PipelineRespBody {
+        baton: if closed {
+            None
+        } else {
+            Some(MOCK_BATON.to_string())
+        },
+        base_url: None,
+        results,
+    }
+}
+
+fn handle_stream_request(state: &State, req: StreamRequest, closed: &mut bool) -> StreamResult {
+    match req {
+        StreamRequest::Execute(req) => match state.record_and_answer(req.stmt) {
+            Ok(result) => StreamResult::Ok {
+                response: StreamResponse::Execute(ExecuteStreamResp { result }),
+            },
+            Err(error) => StreamResult::Error { error },
+        },
+        StreamRequest::Batch(req) => {
+            let mut step_results = Vec::with_capacity(req.batch.steps.len());
+            let mut step_errors = Vec::with_capacity(req.batch.steps.len());
+            for step in req.batch.steps {
+                match state.record_and_answer(step.stmt) {
+                    Ok(result) => {
+                        step_results.push(Some(result));
+                        step_errors.push(None);
+                    }
+                    Err(error) => {
+                        step_results.push(None);
+                        step_errors.push(Some(error));
+                    }
+                }
+            }
+            StreamResult::Ok {
+                response: StreamResponse::Batch(BatchStreamResp {
+                    result: BatchResult {
+                        step_results,
+                        step_errors,
+                        replication_index: None,
+                    },
+                }),
+            }
+        }
+        StreamRequest::GetAutocommit(_) => StreamResult::Ok {
+            response: StreamResponse::GetAutocommit(GetAutocommitStreamResp {
+                is_autocommit: true,
+            }),
+        },
+        StreamRequest::Close(_) => {
+            *closed = true;
+            StreamResult::Ok {
+                response: StreamResponse::Close(CloseStreamResp {}),
+            }
+        }
+        other => StreamResult::Error {
+            error: HranaError {
+                message: format!("MockServer does not implement {:?} stream requests", other),
+                code: "NOT_IMPLEMENTED".to_string(),
+            },
+        },
+    }
+}
+
+#[derive(Clone)]
+struct MockConnector {
+    tx: tokio::sync::mpsc::Sender<DuplexStream>,
+}
+
+impl Service<http::Uri> for MockConnector {
+    type Response = Box<dyn Socket>;
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+    type Future =
+        Pin<Box<dyn std::future::Future<Output = std::result::Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _uri: http::Uri) -> Self::Future {
+        let (client_stream, server_stream) = duplex(8 * 1024);
+        let tx = self.tx.clone();
+        Box::pin(async move {
+            tx.send(server_stream)
+                .await
+                .map_err(|_| "MockServer has shut down")?;
+            Ok(Box::new(MockConnection {
+                stream: client_stream,
+            }) as Box<dyn Socket>)
+        })
+    }
+}
+
+struct MockConnection {
+    stream: DuplexStream,
+}
+
+impl AsyncRead for MockConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for MockConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
+
+impl hyper::client::connect::Connection for MockConnection {
+    fn connected(&self) -> hyper::client::connect::Connected {
+        hyper::client::connect::Connected::new()
+    }
+}
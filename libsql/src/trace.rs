@@ -0,0 +1,294 @@
+//! Process-wide policy for the statement tracing done by [`crate::Connection`], so applications
+//! can turn on query logging in production without echoing user data into their logs.
+//!
+//! [`set_trace_policy`] controls three independent knobs: redacting literal values out of the
+//! logged SQL text, hashing bound parameters instead of logging them, and sampling only a
+//! fraction of statements. All three default to "log everything, as-is", matching the
+//! `tracing::trace!` calls this replaces.
+//!
+//! Separately, [`set_trace_callback`] installs a raw callback that receives every `execute`,
+//! `query`, and `sync` as a [`TraceEvent`] (SQL digest, rows, duration), for applications with
+//! their own telemetry pipeline. With the `tracing` feature enabled, the same operations are
+//! also emitted as `tracing` spans.
+
+use crate::params::Params;
+use std::borrow::Cow;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+static REDACT_LITERALS: AtomicBool = AtomicBool::new(false);
+static HASH_PARAMS: AtomicBool = AtomicBool::new(false);
+/// Sample rate, stored as parts-per-thousand so it fits in an atomic integer. `1000` means "log
+/// every statement".
+static SAMPLE_PER_MILLE: AtomicU32 = AtomicU32::new(1000);
+static SAMPLE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Controls what [`crate::Connection`]'s statement tracing logs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TracePolicy {
+    /// Replace literal values (strings, blobs, numbers) in the logged SQL text with `?`. The
+    /// statement that's actually executed is never affected, only what gets logged.
+    pub redact_literals: bool,
+    /// Replace each bound parameter with a short non-reversible hash instead of logging its
+    /// value.
+    pub hash_params: bool,
+    /// Fraction of statements to log, from `0.0` (none) to `1.0` (all, the default). Sampling
+    /// keeps a deterministic share of statements (every Nth one) rather than a randomized one,
+    /// so re-running the same workload produces the same trace volume.
+    pub sample_rate: f32,
+}
+
+impl Default for TracePolicy {
+    fn default() -> Self {
+        TracePolicy {
+            redact_literals: false,
+            hash_params: false,
+            sample_rate: 1.0,
+        }
+    }
+}
+
+/// Installs `policy` as the process-wide statement tracing policy. Takes effect for every
+/// [`crate::Connection`] already open as well as ones opened afterwards.
+pub fn set_trace_policy(policy: TracePolicy) {
+    REDACT_LITERALS.store(policy.redact_literals, Ordering::Relaxed);
+    HASH_PARAMS.store(policy.hash_params, Ordering::Relaxed);
+    let per_mille = (policy.sample_rate.clamp(0.0, 1.0) * 1000.0).round() as u32;
+    SAMPLE_PER_MILLE.store(per_mille, Ordering::Relaxed);
+}
+
+/// A single statement execution, passed to the callback installed with [`set_trace_callback`].
+#[derive(Debug, Clone, Copy)]
+pub struct TraceEvent<'a> {
+    /// The operation that produced this event, e.g. `"execute"`, `"query"`, `"sync"`.
+    pub op: &'static str,
+    /// The SQL text that was run, rendered under the current [`TracePolicy`]. Empty for
+    /// operations, like `sync`, that aren't a single statement.
+    pub sql: &'a str,
+    /// How long the operation took, including any network round-trip for remote and Hrana
+    /// connections.
+    pub duration: Duration,
+    /// Rows returned by a query, or changed by an execute, if known.
+    pub rows: Option<u64>,
+}
+
+/// Raw callback invoked after every traced operation, independent of the `tracing` feature and
+/// of [`set_trace_policy`]'s sampling rate. Modeled after SQLite's `sqlite3_trace_v2`, for
+/// applications that want to feed their own telemetry pipeline instead of (or in addition to)
+/// `tracing` spans.
+pub type TraceCallback = Arc<dyn Fn(&TraceEvent) + Send + Sync>;
+
+static TRACE_CALLBACK: RwLock<Option<TraceCallback>> = RwLock::new(None);
+
+/// Installs `callback` as the process-wide raw trace callback, replacing whichever one was
+/// installed before. Pass `None` to remove it.
+pub fn set_trace_callback(callback: Option<TraceCallback>) {
+    *TRACE_CALLBACK.write().unwrap() = callback;
+}
+
+pub(crate) fn invoke_trace_callback(event: TraceEvent) {
+    if let Some(callback) = TRACE_CALLBACK.read().unwrap().as_ref() {
+        callback(&event);
+    }
+}
+
+/// Opens a `tracing` span for a statement operation, with `rows` and `duration_ms` left empty to
+/// be filled in with [`tracing::Span::record`] once the operation completes. Only compiled with
+/// the `tracing` feature, since creating and entering a span isn't free even without a
+/// subscriber attached.
+#[cfg(feature = "tracing")]
+pub(crate) fn statement_span(op: &'static str, sql: &str) -> tracing::Span {
+    tracing::info_span!(
+        "libsql.statement",
+        op,
+        sql = %traced_sql(sql),
+        rows = tracing::field::Empty,
+        duration_ms = tracing::field::Empty,
+    )
+}
+
+/// Whether the next statement should be logged under the current sampling rate. Call once per
+/// statement, right before logging it.
+pub(crate) fn should_log_statement() -> bool {
+    match SAMPLE_PER_MILLE.load(Ordering::Relaxed) {
+        0 => false,
+        1000 => true,
+        per_mille => (SAMPLE_COUNTER.fetch_add(1, Ordering::Relaxed) % 1000) < per_mille as u64,
+    }
+}
+
+/// Renders `sql` the way it should appear in a trace log under the current policy.
+pub(crate) fn traced_sql(sql: &str) -> Cow<'_, str> {
+    if REDACT_LITERALS.load(Ordering::Relaxed) {
+        Cow::Owned(redact_literals(sql))
+    } else {
+        Cow::Borrowed(sql)
+    }
+}
+
+/// Renders `params` the way they should appear in a trace log under the current policy.
+pub(crate) fn traced_params(params: &Params) -> String {
+    if !HASH_PARAMS.load(Ordering::Relaxed) {
+        return format!("{:?}", params);
+    }
+    match params {
+        Params::None => "[]".to_string(),
+        Params::Positional(values) => {
+            let hashes: Vec<String> = values.iter().map(hash_value).collect();
+            format!("[{}]", hashes.join(", "))
+        }
+        Params::Named(values) => {
+            let hashes: Vec<String> = values
+                .iter()
+                .map(|(name, value)| format!("{name}={}", hash_value(value)))
+                .collect();
+            format!("[{}]", hashes.join(", "))
+        }
+    }
+}
+
+fn hash_value(value: &crate::Value) -> String {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    // `Value` doesn't implement `Hash` (floats aren't totally ordered), so hash its `Debug`
+    // rendering instead - good enough to tell two parameters apart without logging either.
+    format!("{:?}", value).hash(&mut hasher);
+    format!("h:{:016x}", hasher.finish())
+}
+
+/// Replaces string, blob and numeric literals in `sql` with `?`, using
+/// [`crate::parser::Statement::parse`] to normalize the statement first when the `parser`
+/// feature is available. Identifiers and keywords are left untouched; double-quoted tokens are
+/// also left untouched since SQLite allows them to be either a string literal or an identifier
+/// and telling which would require full semantic analysis.
+fn redact_literals(sql: &str) -> String {
+    #[cfg(feature = "parser")]
+    {
+        let normalized: Vec<String> = crate::parser::Statement::parse(sql)
+            .filter_map(|stmt| stmt.ok())
+            .map(|stmt| redact_literal_tokens(&stmt.stmt))
+            .collect();
+        if !normalized.is_empty() {
+            return normalized.join("; ");
+        }
+    }
+    redact_literal_tokens(sql)
+}
+
+/// A single lexical pass that copies `sql` verbatim except for single-quoted string literals
+/// (with `''`-escaped quotes), `x'...'`/`X'...'` blob literals, and standalone numeric literals,
+/// which are replaced with a single `?`.
+fn redact_literal_tokens(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            for c in chars.by_ref() {
+                if c == '\'' {
+                    if chars.peek() == Some(&'\'') {
+                        chars.next();
+                        continue;
+                    }
+                    break;
+                }
+            }
+            out.push('?');
+        } else if (c == 'x' || c == 'X') && chars.peek() == Some(&'\'') && !ends_identifier(&out) {
+            chars.next(); // consume opening quote
+            for c in chars.by_ref() {
+                if c == '\'' {
+                    break;
+                }
+            }
+            out.push('?');
+        } else if c.is_ascii_digit() && !ends_identifier(&out) {
+            while let Some(&next) = chars.peek() {
+                if next.is_ascii_alphanumeric() || next == '.' || next == '_' {
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            out.push('?');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Whether `out` currently ends with a character that can continue an identifier, meaning the
+/// next byte in the input is part of that identifier rather than the start of a new literal.
+fn ends_identifier(out: &str) -> bool {
+    matches!(out.chars().next_back(), Some(c) if c.is_ascii_alphanumeric() || c == '_' || c == '$' || c == ':' || c == '?')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_string_literals() {
+        assert_eq!(
+            redact_literal_tokens("SELECT * FROM users WHERE email = 'alice@example.org'"),
+            "SELECT * FROM users WHERE email = ?"
+        );
+    }
+
+    #[test]
+    fn redacts_escaped_quotes_in_string_literals() {
+        assert_eq!(
+            redact_literal_tokens("SELECT 'it''s here'"),
+            "SELECT ?"
+        );
+    }
+
+    #[test]
+    fn redacts_numeric_literals_but_not_identifiers() {
+        assert_eq!(
+            redact_literal_tokens("SELECT col1 FROM t WHERE id = 42"),
+            "SELECT col1 FROM t WHERE id = ?"
+        );
+    }
+
+    #[test]
+    fn leaves_bind_parameters_alone() {
+        assert_eq!(
+            redact_literal_tokens("SELECT * FROM t WHERE id = ?1"),
+            "SELECT * FROM t WHERE id = ?1"
+        );
+    }
+
+    #[test]
+    fn redacts_blob_literals() {
+        assert_eq!(
+            redact_literal_tokens("INSERT INTO t (b) VALUES (x'AB01')"),
+            "INSERT INTO t (b) VALUES (?)"
+        );
+    }
+
+    #[test]
+    fn trace_callback_receives_events_until_cleared() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        let event = TraceEvent {
+            op: "execute",
+            sql: "SELECT 1",
+            duration: Duration::from_millis(1),
+            rows: Some(1),
+        };
+
+        set_trace_callback(Some(Arc::new(|event: &TraceEvent| {
+            assert_eq!(event.op, "execute");
+            CALLS.fetch_add(1, Ordering::SeqCst);
+        })));
+        invoke_trace_callback(event);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        set_trace_callback(None);
+        invoke_trace_callback(event);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
+}
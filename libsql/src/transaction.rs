@@ -6,7 +6,7 @@ use super::Connection;
 
 /// Transaction types that correlate to sqlite3 transactions and
 /// additional ones introduced by libsql.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum TransactionBehavior {
     Deferred,
     Immediate,
@@ -56,3 +56,77 @@ pub(crate) trait Tx {
     async fn commit(&mut self) -> Result<()>;
     async fn rollback(&mut self) -> Result<()>;
 }
+
+/// Collects statements and their parameters ahead of time and executes them as a single
+/// atomic transaction, giving access to each statement's affected row count individually.
+///
+/// This is convenient for the common "N dependent writes" pattern, where it saves having to
+/// manually open a [`Transaction`] and thread it through each call site.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn run(conn: &libsql::Connection) -> libsql::Result<()> {
+/// use libsql::TransactionBuilder;
+///
+/// let affected = TransactionBuilder::new()
+///     .statement("INSERT INTO foo (id) VALUES (?1)", [1])?
+///     .statement("UPDATE foo SET name = ?1 WHERE id = ?2", ("bar", 1))?
+///     .execute(conn)
+///     .await?;
+///
+/// assert_eq!(affected, vec![1, 1]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct TransactionBuilder {
+    behavior: TransactionBehavior,
+    statements: Vec<(String, crate::params::Params)>,
+}
+
+impl Default for TransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransactionBuilder {
+    /// Create an empty builder using the default (`DEFERRED`) transaction behavior.
+    pub fn new() -> Self {
+        Self {
+            behavior: TransactionBehavior::Deferred,
+            statements: Vec::new(),
+        }
+    }
+
+    /// Set the transaction behavior used when the statements are executed.
+    pub fn behavior(mut self, behavior: TransactionBehavior) -> Self {
+        self.behavior = behavior;
+        self
+    }
+
+    /// Append a statement and its parameters to the set to be executed.
+    pub fn statement(
+        mut self,
+        sql: impl Into<String>,
+        params: impl crate::params::IntoParams,
+    ) -> Result<Self> {
+        self.statements.push((sql.into(), params.into_params()?));
+        Ok(self)
+    }
+
+    /// Execute all collected statements atomically on `conn`, in the order they were added,
+    /// and return the number of rows affected by each statement.
+    pub async fn execute(self, conn: &Connection) -> Result<Vec<u64>> {
+        let tx = conn.transaction_with_behavior(self.behavior).await?;
+
+        let mut affected = Vec::with_capacity(self.statements.len());
+        for (sql, params) in self.statements {
+            affected.push(tx.execute(&sql, params).await?);
+        }
+
+        tx.commit().await?;
+
+        Ok(affected)
+    }
+}
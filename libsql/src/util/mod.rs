@@ -2,6 +2,11 @@ cfg_replication_or_remote_or_sync! {
     pub mod box_clone_service;
     mod http;
     pub(crate) use self::http::{ConnectorService, Socket};
+
+    #[cfg(feature = "tls")]
+    mod tls;
+    #[cfg(feature = "tls")]
+    pub use self::tls::TlsConfig;
 }
 
 cfg_replication! {
@@ -0,0 +1,219 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tower::Service;
+
+use super::{ConnectorService, Socket};
+
+/// Custom TLS settings for outbound HTTPS connections made by a `remote`/`sync` connection.
+///
+/// By default the built-in connector trusts the platform's native root store and derives the
+/// TLS server name from the connection URL. Set this to reach a self-hosted sqld behind a
+/// private CA, to present a client certificate for mutual TLS, or to override the server name
+/// sent during the handshake, without having to hand-roll a whole [`crate::Builder::connector`].
+///
+/// Has no effect if a custom connector is also provided; a custom connector is expected to
+/// configure its own TLS.
+#[derive(Debug, Clone, Default)]
+pub struct TlsConfig {
+    pub(crate) root_certificates: Vec<Vec<u8>>,
+    pub(crate) identity: Option<(Vec<u8>, Vec<u8>)>,
+    pub(crate) server_name: Option<String>,
+}
+
+impl TlsConfig {
+    /// Start building a [`TlsConfig`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust an additional PEM-encoded root CA certificate. Can be called multiple times to
+    /// trust several CAs; once any root certificate is added, the platform's native root store
+    /// is no longer trusted.
+    pub fn root_certificate(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates.push(pem.into());
+        self
+    }
+
+    /// Present a PEM-encoded client certificate and its PEM-encoded private key during the TLS
+    /// handshake, for servers that require mutual TLS.
+    pub fn client_certificate(
+        mut self,
+        cert_pem: impl Into<Vec<u8>>,
+        key_pem: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.identity = Some((cert_pem.into(), key_pem.into()));
+        self
+    }
+
+    /// Override the server name sent via SNI and checked against the certificate, instead of
+    /// deriving it from the connection URL's host. Useful when the URL names an IP address or a
+    /// proxy that fronts multiple hostnames behind one certificate.
+    pub fn server_name(mut self, server_name: impl Into<String>) -> Self {
+        self.server_name = Some(server_name.into());
+        self
+    }
+
+    fn root_store(&self) -> crate::Result<rustls::RootCertStore> {
+        let mut roots = rustls::RootCertStore::empty();
+        if self.root_certificates.is_empty() {
+            for cert in rustls_native_certs::load_native_certs()
+                .map_err(crate::Error::InvalidTlsConfiguration)?
+            {
+                roots.add(cert).map_err(|e| {
+                    crate::Error::InvalidTlsConfiguration(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        e,
+                    ))
+                })?;
+            }
+        } else {
+            for pem in &self.root_certificates {
+                for cert in rustls_pemfile::certs(&mut pem.as_slice()) {
+                    let cert =
+                        cert.map_err(crate::Error::InvalidTlsConfiguration)?;
+                    roots.add(cert).map_err(|e| {
+                        crate::Error::InvalidTlsConfiguration(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            e,
+                        ))
+                    })?;
+                }
+            }
+        }
+        Ok(roots)
+    }
+
+    fn client_config(&self) -> crate::Result<rustls::ClientConfig> {
+        let roots = self.root_store()?;
+        let builder = rustls::ClientConfig::builder().with_root_certificates(roots);
+
+        let config = if let Some((cert_pem, key_pem)) = &self.identity {
+            let certs = rustls_pemfile::certs(&mut cert_pem.as_slice())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(crate::Error::InvalidTlsConfiguration)?;
+            let key = rustls_pemfile::private_key(&mut key_pem.as_slice())
+                .map_err(crate::Error::InvalidTlsConfiguration)?
+                .ok_or_else(|| {
+                    crate::Error::InvalidTlsConfiguration(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "no private key found in client certificate key PEM",
+                    ))
+                })?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(|e| {
+                    crate::Error::InvalidTlsConfiguration(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        e,
+                    ))
+                })?
+        } else {
+            builder.with_no_client_auth()
+        };
+
+        Ok(config)
+    }
+
+    /// Build the [`ConnectorService`] used by the `remote`/`sync` builders when this
+    /// configuration is set instead of a raw connector.
+    pub(crate) fn build_connector(&self) -> crate::Result<ConnectorService> {
+        use tower::ServiceExt;
+
+        let mut http = hyper::client::HttpConnector::new();
+        http.enforce_http(false);
+        http.set_nodelay(true);
+
+        let tls = tokio_rustls::TlsConnector::from(Arc::new(self.client_config()?));
+        let connector = SniConnector {
+            http,
+            tls,
+            server_name: self.server_name.clone(),
+        };
+
+        let svc = connector
+            .map_err(|e: std::io::Error| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+            .map_response(|s| Box::new(s) as Box<dyn Socket>);
+
+        Ok(ConnectorService::new(svc))
+    }
+}
+
+/// Dials a plain TCP connection with the wrapped [`hyper::client::HttpConnector`] and then
+/// performs the TLS handshake with an explicit server name, instead of deriving it from the
+/// dialed URI the way [`hyper_rustls::HttpsConnector`] does. This is what lets `server_name`
+/// differ from the connection URL's host, e.g. dialing an IP while presenting the hostname the
+/// certificate was issued for.
+#[derive(Clone)]
+struct SniConnector {
+    http: hyper::client::HttpConnector,
+    tls: tokio_rustls::TlsConnector,
+    server_name: Option<String>,
+}
+
+impl Service<http::Uri> for SniConnector {
+    type Response = TlsStream;
+    type Error = std::io::Error;
+    type Future =
+        Pin<Box<dyn std::future::Future<Output = std::io::Result<Self::Response>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.http.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: http::Uri) -> Self::Future {
+        let mut http = self.http.clone();
+        let tls = self.tls.clone();
+        let name = self
+            .server_name
+            .clone()
+            .or_else(|| uri.host().map(str::to_owned))
+            .unwrap_or_default();
+
+        Box::pin(async move {
+            let server_name = rustls::pki_types::ServerName::try_from(name)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+            let tcp = http.call(uri).await?;
+            let stream = tls.connect(server_name, tcp).await?;
+            Ok(TlsStream(stream))
+        })
+    }
+}
+
+struct TlsStream(tokio_rustls::client::TlsStream<tokio::net::TcpStream>);
+
+impl AsyncRead for TlsStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for TlsStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
+
+impl hyper::client::connect::Connection for TlsStream {
+    fn connected(&self) -> hyper::client::connect::Connected {
+        self.0.get_ref().0.connected()
+    }
+}
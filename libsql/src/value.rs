@@ -22,6 +22,25 @@ pub enum ValueType {
     Null,
 }
 
+/// Controls how [`crate::Row::get_with`] converts a SQLite value into a Rust type when the
+/// column's storage class doesn't exactly match what was asked for. [`crate::Row::get`] always
+/// uses [`CoercionPolicy::Strict`].
+///
+/// This mostly matters for columns with `NUMERIC` affinity: SQLite stores whatever the inserted
+/// value's natural type was, so the same column can hold an `INTEGER` in one row and a `REAL` in
+/// another.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CoercionPolicy {
+    /// Only convert when the column's storage class already matches the requested Rust type
+    /// (modulo width, e.g. an `INTEGER` column into an `i32`). Anything else is an error.
+    #[default]
+    Strict,
+    /// Additionally accept the `INTEGER`/`REAL` mismatches a `NUMERIC`-affinity column can
+    /// produce: an integer column read as a float widens losslessly, and a float column read as
+    /// an integer truncates toward zero, following Rust's `as` cast semantics.
+    Lossy,
+}
+
 impl FromStr for ValueType {
     type Err = ();
 
@@ -16,12 +16,14 @@ impl CloudflareSender {
         CloudflareSender(())
     }
 
-    async fn send(url: Arc<str>, auth: Arc<str>, body: String) -> Result<HttpBody<HttpStream>> {
+    async fn send(url: Arc<str>, auth: Arc<str>, body: Bytes) -> Result<HttpBody<HttpStream>> {
         use worker::{
             CfProperties, Fetch, Headers, Method, Request, RequestInit, RequestRedirect,
             ResponseBody,
         };
 
+        // Only JSON is supported here; the body is always a UTF-8 JSON document.
+        let body = String::from_utf8_lossy(&body).into_owned();
         let mut response = Fetch::Request(Request::new_with_init(
             url.as_ref(),
             &RequestInit {
@@ -56,12 +58,12 @@ impl HttpSend for CloudflareSender {
     type Stream = HttpBody<HttpStream>;
     type Result = Pin<Box<dyn Future<Output = Result<Self::Stream>>>>;
 
-    fn http_send(&self, url: Arc<str>, auth: Arc<str>, body: String) -> Self::Result {
+    fn http_send(&self, url: Arc<str>, auth: Arc<str>, body: Bytes) -> Self::Result {
         let fut = Self::send(url, auth, body);
         Box::pin(fut)
     }
 
-    fn oneshot(self, url: Arc<str>, auth: Arc<str>, body: String) {
+    fn oneshot(self, url: Arc<str>, auth: Arc<str>, body: Bytes) {
         worker::wasm_bindgen_futures::spawn_local(async move {
             let _ = Self::send(url, auth, body).await;
         });
@@ -57,7 +57,12 @@ cfg_cloudflare! {
     impl Connection<CloudflareSender> {
         pub fn open_cloudflare_worker(url: impl Into<String>, auth_token: impl Into<String>) -> Self    {
             Connection {
-                conn: HttpConnection::new(url.into(), auth_token.into(), CloudflareSender::new()),
+                conn: HttpConnection::new(
+                    url.into(),
+                    auth_token.into(),
+                    CloudflareSender::new(),
+                    crate::hrana::HranaEncoding::Json,
+                ),
             }
         }
     }
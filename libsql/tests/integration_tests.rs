@@ -4,7 +4,7 @@ use futures::{StreamExt, TryStreamExt};
 use libsql::{
     named_params, params,
     params::{IntoParams, IntoValue},
-    AuthAction, Authorization, Connection, Database, Result, Value,
+    AuthAction, Authorization, Connection, Database, Result, UpdateHookAction, Value,
 };
 use rand::distributions::Uniform;
 use rand::prelude::*;
@@ -842,6 +842,113 @@ async fn test_ignore_authorizer() {
     assert_eq!(rows.into_stream().count().await, 0);
 }
 
+#[tokio::test]
+async fn test_update_hook() {
+    let db = Database::open(":memory:").unwrap();
+    let conn = db.connect().unwrap();
+    conn.execute("CREATE TABLE users (id INTEGER, name TEXT)", ())
+        .await
+        .unwrap();
+    let changes = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let changes_ = changes.clone();
+    conn.update_hook(Some(Arc::new(move |action, db_name, table_name, rowid| {
+        changes_
+            .lock()
+            .unwrap()
+            .push((action, db_name.to_string(), table_name.to_string(), rowid));
+    })))
+    .unwrap();
+    conn.execute("INSERT INTO users (id, name) VALUES (1, 'Alice')", ())
+        .await
+        .unwrap();
+    assert_eq!(
+        *changes.lock().unwrap(),
+        vec![(UpdateHookAction::Insert, "main".to_string(), "users".to_string(), 1)]
+    );
+    conn.update_hook(None).unwrap();
+    conn.execute("INSERT INTO users (id, name) VALUES (2, 'Bob')", ())
+        .await
+        .unwrap();
+    // No new entry recorded after clearing the hook
+    assert_eq!(changes.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_commit_hook() {
+    let db = Database::open(":memory:").unwrap();
+    let conn = db.connect().unwrap();
+    conn.execute("CREATE TABLE users (id INTEGER, name TEXT)", ())
+        .await
+        .unwrap();
+    let commits = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let commits_ = commits.clone();
+    conn.commit_hook(Some(Arc::new(move || {
+        commits_.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        false
+    })))
+    .unwrap();
+    conn.execute("INSERT INTO users (id, name) VALUES (1, 'Alice')", ())
+        .await
+        .unwrap();
+    assert_eq!(commits.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+    conn.commit_hook(Some(Arc::new(|| true))).unwrap();
+    let res = conn
+        .execute("INSERT INTO users (id, name) VALUES (2, 'Bob')", ())
+        .await;
+    assert!(res.is_err());
+    conn.commit_hook(None).unwrap();
+}
+
+#[tokio::test]
+async fn test_rollback_hook() {
+    let db = Database::open(":memory:").unwrap();
+    let conn = db.connect().unwrap();
+    conn.execute("CREATE TABLE users (id INTEGER, name TEXT)", ())
+        .await
+        .unwrap();
+    let rollbacks = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let rollbacks_ = rollbacks.clone();
+    conn.rollback_hook(Some(Arc::new(move || {
+        rollbacks_.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    })))
+    .unwrap();
+    conn.execute("BEGIN", ()).await.unwrap();
+    conn.execute("INSERT INTO users (id, name) VALUES (1, 'Alice')", ())
+        .await
+        .unwrap();
+    conn.execute("ROLLBACK", ()).await.unwrap();
+    assert_eq!(rollbacks.load(std::sync::atomic::Ordering::SeqCst), 1);
+    conn.rollback_hook(None).unwrap();
+}
+
+#[tokio::test]
+async fn test_rows_into_stream_combinators() {
+    let db = Database::open(":memory:").unwrap();
+    let conn = db.connect().unwrap();
+    conn.execute("CREATE TABLE users (id INTEGER)", ())
+        .await
+        .unwrap();
+    for id in 0..5 {
+        conn.execute("INSERT INTO users (id) VALUES (?)", libsql::params![id])
+            .await
+            .unwrap();
+    }
+
+    let rows = conn.query("SELECT id FROM users ORDER BY id", ()).await.unwrap();
+    // `into_stream()` should compose with arbitrary `TryStreamExt` combinators, chunking
+    // rows as they're pulled off the cursor one at a time instead of buffering them all up
+    // front.
+    let chunks: Vec<Vec<i64>> = rows
+        .into_stream()
+        .map_ok(|row| row.get::<i64>(0).unwrap())
+        .try_chunks(2)
+        .map(|chunk| chunk.unwrap())
+        .collect()
+        .await;
+    assert_eq!(chunks, vec![vec![0, 1], vec![2, 3], vec![4]]);
+}
+
 fn assert_sqlite_error<T>(res: Result<T>, code: i32) {
     match res {
         Ok(_) => panic!("Expected error, got Ok"),
@@ -1,6 +1,9 @@
 #![allow(deprecated)]
 
-use libsql::{replication::Frames, Database};
+use libsql::{
+    replication::{inspect, Frames},
+    Database,
+};
 use libsql_replication::{
     frame::{FrameBorrowed, FrameHeader, FrameMut},
     LIBSQL_PAGE_SIZE,
@@ -96,6 +99,39 @@ async fn inject_frames() {
     );
 }
 
+#[tokio::test]
+async fn inspect_replica_meta() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db_path = tmp.path().join("data");
+    let db = Database::open_with_local_sync(db_path.to_str().unwrap(), None)
+        .await
+        .unwrap();
+
+    let meta = inspect::read_replica_meta(&db_path).await.unwrap().unwrap();
+    assert_eq!(meta.committed_frame_no, None);
+
+    let mut frames: Vec<FrameMut> = DB
+        .chunks(LIBSQL_PAGE_SIZE)
+        .enumerate()
+        .map(|(i, data)| {
+            let header = FrameHeader {
+                frame_no: (i as u64).into(),
+                checksum: 0.into(),
+                page_no: (i as u32 + 1).into(),
+                size_after: 0.into(),
+            };
+            FrameBorrowed::from_parts(&header, data).into()
+        })
+        .collect();
+    frames.last_mut().unwrap().header_mut().size_after = (frames.len() as u32).into();
+    let frames = frames.into_iter().map(Into::into).collect();
+
+    let last_frame_no = db.sync_frames(Frames::Vec(frames)).await.unwrap().unwrap();
+
+    let meta = inspect::read_replica_meta(&db_path).await.unwrap().unwrap();
+    assert_eq!(meta.committed_frame_no, Some(last_frame_no));
+}
+
 #[tokio::test]
 async fn inject_frames_split_txn() {
     let tmp = tempfile::tempdir().unwrap();
@@ -79,11 +79,11 @@ pub(crate) fn is_identifier(name: &str) -> bool {
         && (bytes.len() == 1 || bytes[1..].iter().all(|b| is_identifier_continue(*b)))
 }
 
-pub(crate) fn is_identifier_start(b: u8) -> bool {
+const fn is_identifier_start_byte(b: u8) -> bool {
     b.is_ascii_uppercase() || b == b'_' || b.is_ascii_lowercase() || b > b'\x7F'
 }
 
-pub(crate) fn is_identifier_continue(b: u8) -> bool {
+const fn is_identifier_continue_byte(b: u8) -> bool {
     b == b'$'
         || b.is_ascii_digit()
         || b.is_ascii_uppercase()
@@ -92,6 +92,38 @@ pub(crate) fn is_identifier_continue(b: u8) -> bool {
         || b > b'\x7F'
 }
 
+// Precomputed at compile time so that scanning an identifier (the hottest path when
+// tokenizing a large schema dump) is a single table lookup per byte rather than a chain of
+// comparisons. Portable SIMD classification would shave this further, but that needs
+// nightly-only `std::simd`, which this workspace's pinned stable toolchain can't use.
+static IDENTIFIER_START_TABLE: [bool; 256] = {
+    let mut table = [false; 256];
+    let mut b = 0usize;
+    while b < 256 {
+        table[b] = is_identifier_start_byte(b as u8);
+        b += 1;
+    }
+    table
+};
+
+static IDENTIFIER_CONTINUE_TABLE: [bool; 256] = {
+    let mut table = [false; 256];
+    let mut b = 0usize;
+    while b < 256 {
+        table[b] = is_identifier_continue_byte(b as u8);
+        b += 1;
+    }
+    table
+};
+
+pub(crate) fn is_identifier_start(b: u8) -> bool {
+    IDENTIFIER_START_TABLE[b as usize]
+}
+
+pub(crate) fn is_identifier_continue(b: u8) -> bool {
+    IDENTIFIER_CONTINUE_TABLE[b as usize]
+}
+
 // keyword may become an identifier
 // see %fallback in parse.y
 pub(crate) fn from_token(ty: u16, value: Token) -> String {